@@ -0,0 +1,93 @@
+//! Per-instruction account metas
+//!
+//! Each helper returns the accounts in the order the program parses them,
+//! with the signer and writable flags it checks, so callers never hand-roll
+//! them.
+
+use solana_instruction::AccountMeta;
+use solana_pubkey::Pubkey;
+
+use crate::{
+    instructions::{DeregisterAgent, RegisterAgent, UpdateAgent},
+    AGENTMAIL_ID,
+};
+
+/// The system program, which RegisterAgent creates the registry through
+pub const SYSTEM_PROGRAM_ID: Pubkey = solana_pubkey::pubkey!("11111111111111111111111111111111");
+
+impl RegisterAgent {
+    /// Account metas for RegisterAgent.
+    pub fn account_metas(
+        payer: &Pubkey,
+        agent_authority: &Pubkey,
+        agent_registry: &Pubkey,
+    ) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*agent_authority, true),
+            AccountMeta::new(*agent_registry, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(AGENTMAIL_ID, false),
+        ]
+    }
+}
+
+impl UpdateAgent {
+    /// Account metas for UpdateAgent. The authority only signs; it is never written.
+    pub fn account_metas(agent_authority: &Pubkey, agent_registry: &Pubkey) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(*agent_authority, true),
+            AccountMeta::new(*agent_registry, false),
+            AccountMeta::new_readonly(AGENTMAIL_ID, false),
+        ]
+    }
+
+    /// Account metas for an UpdateAgent that may grow a legacy registry.
+    ///
+    /// Appends the system program and, when given, `payer`, which funds the
+    /// rent top-up. Without a payer the authority pays and is writable.
+    pub fn account_metas_with_payer(
+        agent_authority: &Pubkey,
+        agent_registry: &Pubkey,
+        payer: Option<&Pubkey>,
+    ) -> Vec<AccountMeta> {
+        let mut metas = vec![
+            AccountMeta::new(*agent_authority, true),
+            AccountMeta::new(*agent_registry, false),
+            AccountMeta::new_readonly(AGENTMAIL_ID, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ];
+        if let Some(payer) = payer {
+            metas[0].is_writable = false;
+            metas.push(AccountMeta::new(*payer, true));
+        }
+        metas
+    }
+}
+
+impl DeregisterAgent {
+    /// Account metas for DeregisterAgent.
+    ///
+    /// The authority is writable since it receives the reclaimed rent.
+    pub fn account_metas(agent_authority: &Pubkey, agent_registry: &Pubkey) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(*agent_authority, true),
+            AccountMeta::new(*agent_registry, false),
+            AccountMeta::new_readonly(AGENTMAIL_ID, false),
+        ]
+    }
+
+    /// Account metas for a DeregisterAgent that closes into `rent_vault`.
+    ///
+    /// Appends the writable vault, which receives the reclaimed rent instead
+    /// of the authority.
+    pub fn account_metas_to_vault(
+        agent_authority: &Pubkey,
+        agent_registry: &Pubkey,
+        rent_vault: &Pubkey,
+    ) -> Vec<AccountMeta> {
+        let mut metas = Self::account_metas(agent_authority, agent_registry);
+        metas.push(AccountMeta::new(*rent_vault, false));
+        metas
+    }
+}
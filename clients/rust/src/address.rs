@@ -0,0 +1,19 @@
+//! Pubkey / Address conversions
+//!
+//! The client speaks `solana_pubkey::Pubkey` while the program (via
+//! pinocchio) speaks `solana_address::Address`. Both are the same 32-byte
+//! array, so these conversions copy the bytes and never fail; use them
+//! instead of ad-hoc `.into()` calls where the two types meet.
+
+use solana_address::Address;
+use solana_pubkey::Pubkey;
+
+/// Converts a client `Pubkey` into the program's `Address`.
+pub fn to_address(pubkey: Pubkey) -> Address {
+    Address::new_from_array(pubkey.to_bytes())
+}
+
+/// Converts the program's `Address` into a client `Pubkey`.
+pub fn to_pubkey(address: Address) -> Pubkey {
+    Pubkey::new_from_array(address.to_bytes())
+}
@@ -0,0 +1,173 @@
+//! Program account health check
+//!
+//! Scans accounts owned by the AgentMail program and reports anything a
+//! current-layout AgentRegistry or RentVault would never contain. Useful after a migration
+//! to confirm every registry was restamped and nothing was left corrupt.
+
+use solana_pubkey::Pubkey;
+
+use crate::{
+    preflight::{AGENT_REGISTRY_ACCOUNT_LEN, MAX_INBOX_URL_LEN, MAX_NAME_LEN},
+    registry::{
+        account_type, find_agent_registry_pda, find_rent_vault_pda, AccountKind,
+        AGENT_REGISTRY_AUTHORITY_OFFSET, RENT_VAULT_ACCOUNT_LEN, RENT_VAULT_AUTHORITY_OFFSET,
+    },
+};
+
+/// Current AgentRegistry layout version, mirroring the program
+pub const AGENT_REGISTRY_VERSION: u8 = 8;
+
+/// Current RentVault layout version, mirroring the program
+pub const RENT_VAULT_VERSION: u8 = 1;
+
+/// Maximum number of secondary inbox URLs, mirroring the program
+pub const MAX_INBOX_URLS: usize = 4;
+
+/// Maximum secondary inbox URL and metadata URI length in bytes, mirroring the program
+pub const MAX_SHORT_URL_LEN: usize = 128;
+
+/// Offsets of the length-prefixed fields within AgentRegistry account data
+const NAME_OFFSET: usize = AGENT_REGISTRY_AUTHORITY_OFFSET + 32;
+const INBOX_URL_OFFSET: usize = NAME_OFFSET + 4 + MAX_NAME_LEN;
+const INBOX_URLS_OFFSET: usize = INBOX_URL_OFFSET + 4 + MAX_INBOX_URL_LEN + 3 * 8;
+const METADATA_URI_OFFSET: usize = INBOX_URLS_OFFSET + MAX_INBOX_URLS * (4 + MAX_SHORT_URL_LEN);
+
+/// Byte offset of `url_count` within AgentRegistry account data
+const URL_COUNT_OFFSET: usize = 4;
+
+/// A single anomaly found on a program account.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AuditIssue {
+    /// The account is empty or its first byte is not a known discriminator
+    ///
+    /// A registry allocated by AllocateRegistry but not yet initialized is
+    /// all zeroes and reports `Some(0)`.
+    UnknownDiscriminator(Option<u8>),
+    /// The account is not the length of its account type, e.g.
+    /// `AGENT_REGISTRY_ACCOUNT_LEN` bytes for a registry
+    WrongLength { len: usize },
+    /// The header or inner version byte is not the current version of its
+    /// account type, e.g. `AGENT_REGISTRY_VERSION` for a registry
+    VersionMismatch { header: u8, inner: u8 },
+    /// A length prefix exceeds the capacity of its field
+    CorruptLengthPrefix { field: &'static str, len: u32 },
+    /// `url_count` exceeds `MAX_INBOX_URLS`
+    UrlCountOutOfRange(u8),
+    /// The address or bump is not the canonical PDA of the stored authority,
+    /// for a registry or a rent vault
+    PdaMismatch { expected: Pubkey, bump: u8 },
+}
+
+/// An [`AuditIssue`] together with the account it was found on.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditFinding {
+    pub address: Pubkey,
+    pub issue: AuditIssue,
+}
+
+/// Checks one program-owned account and returns every anomaly found.
+///
+/// An account that fails the discriminator or length check gets no further
+/// checks, since its remaining fields cannot be located reliably.
+pub fn audit_account(address: &Pubkey, data: &[u8]) -> Vec<AuditFinding> {
+    let mut issues = Vec::new();
+
+    let kind = account_type(data);
+    if kind == Some(AccountKind::RentVault) {
+        audit_rent_vault(address, data, &mut issues);
+    } else if kind != Some(AccountKind::AgentRegistry) {
+        issues.push(AuditIssue::UnknownDiscriminator(data.first().copied()));
+    } else if data.len() != AGENT_REGISTRY_ACCOUNT_LEN {
+        issues.push(AuditIssue::WrongLength { len: data.len() });
+    } else {
+        let (header, bump, inner) = (data[1], data[2], data[3]);
+        if header != AGENT_REGISTRY_VERSION || inner != AGENT_REGISTRY_VERSION {
+            issues.push(AuditIssue::VersionMismatch { header, inner });
+        }
+
+        let mut prefixed = vec![
+            ("name", NAME_OFFSET, MAX_NAME_LEN),
+            ("inbox_url", INBOX_URL_OFFSET, MAX_INBOX_URL_LEN),
+            ("metadata_uri", METADATA_URI_OFFSET, MAX_SHORT_URL_LEN),
+        ];
+        const INBOX_URLS_NAMES: [&str; MAX_INBOX_URLS] = [
+            "inbox_urls[0]",
+            "inbox_urls[1]",
+            "inbox_urls[2]",
+            "inbox_urls[3]",
+        ];
+        for (i, field) in INBOX_URLS_NAMES.into_iter().enumerate() {
+            let offset = INBOX_URLS_OFFSET + i * (4 + MAX_SHORT_URL_LEN);
+            prefixed.push((field, offset, MAX_SHORT_URL_LEN));
+        }
+        for (field, offset, max) in prefixed {
+            let len = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+            if len as usize > max {
+                issues.push(AuditIssue::CorruptLengthPrefix { field, len });
+            }
+        }
+
+        let url_count = data[URL_COUNT_OFFSET];
+        if url_count as usize > MAX_INBOX_URLS {
+            issues.push(AuditIssue::UrlCountOutOfRange(url_count));
+        }
+
+        let authority = Pubkey::try_from(
+            &data[AGENT_REGISTRY_AUTHORITY_OFFSET..AGENT_REGISTRY_AUTHORITY_OFFSET + 32],
+        )
+        .unwrap();
+        let (expected, canonical_bump) = find_agent_registry_pda(&authority);
+        if expected != *address || canonical_bump != bump {
+            issues.push(AuditIssue::PdaMismatch { expected, bump });
+        }
+    }
+
+    issues
+        .into_iter()
+        .map(|issue| AuditFinding {
+            address: *address,
+            issue,
+        })
+        .collect()
+}
+
+/// Checks a RentVault's length, versions and PDA derivation.
+fn audit_rent_vault(address: &Pubkey, data: &[u8], issues: &mut Vec<AuditIssue>) {
+    if data.len() != RENT_VAULT_ACCOUNT_LEN {
+        issues.push(AuditIssue::WrongLength { len: data.len() });
+        return;
+    }
+
+    let (header, bump, inner) = (data[1], data[2], data[3]);
+    if header != RENT_VAULT_VERSION || inner != RENT_VAULT_VERSION {
+        issues.push(AuditIssue::VersionMismatch { header, inner });
+    }
+
+    let authority = Pubkey::try_from(&data[RENT_VAULT_AUTHORITY_OFFSET..]).unwrap();
+    let (expected, canonical_bump) = find_rent_vault_pda(&authority);
+    if expected != *address || canonical_bump != bump {
+        issues.push(AuditIssue::PdaMismatch { expected, bump });
+    }
+}
+
+/// Fetches every account owned by the program and audits it.
+///
+/// Unlike [`crate::registry::fetch_all_agents`] no filters are applied, so
+/// accounts with a stale size or unknown discriminator are reported rather
+/// than skipped. Findings are sorted by address. This is a full
+/// `getProgramAccounts` scan and transfers every account's data.
+#[cfg(feature = "fetch")]
+pub fn audit_program(
+    rpc: &solana_client::rpc_client::RpcClient,
+) -> Result<Vec<AuditFinding>, std::io::Error> {
+    let accounts = rpc
+        .get_program_accounts(&crate::AGENTMAIL_ID)
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+
+    let mut findings: Vec<AuditFinding> = accounts
+        .iter()
+        .flat_map(|(address, account)| audit_account(address, &account.data))
+        .collect();
+    findings.sort_by(|a, b| a.address.cmp(&b.address));
+    Ok(findings)
+}
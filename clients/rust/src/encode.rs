@@ -0,0 +1,113 @@
+//! Canonical instruction-data encoders
+//!
+//! Produce the exact bytes the program parses, so apps and tests don't each
+//! hand-pack their own.
+
+use crate::preflight::{check_register_args, PreflightError, MIN_NAME_LEN};
+
+/// RegisterAgent instruction discriminator
+pub const REGISTER_AGENT_DISCRIMINATOR: u8 = 3;
+
+/// UpdateAgent instruction discriminator
+pub const UPDATE_AGENT_DISCRIMINATOR: u8 = 4;
+
+/// DeregisterAgent instruction discriminator
+pub const DEREGISTER_AGENT_DISCRIMINATOR: u8 = 5;
+
+/// SetAccepting instruction discriminator
+pub const SET_ACCEPTING_DISCRIMINATOR: u8 = 7;
+
+/// MigrateRegistry instruction discriminator
+pub const MIGRATE_REGISTRY_DISCRIMINATOR: u8 = 8;
+
+/// SetOperator instruction discriminator
+pub const SET_OPERATOR_DISCRIMINATOR: u8 = 9;
+
+/// CheckRegistryBatch instruction discriminator
+pub const CHECK_REGISTRY_BATCH_DISCRIMINATOR: u8 = 10;
+
+/// SoftDeregisterAgent instruction discriminator
+pub const SOFT_DEREGISTER_AGENT_DISCRIMINATOR: u8 = 11;
+
+/// ReactivateAgent instruction discriminator
+pub const REACTIVATE_AGENT_DISCRIMINATOR: u8 = 12;
+
+/// AllocateRegistry instruction discriminator
+pub const ALLOCATE_REGISTRY_DISCRIMINATOR: u8 = 13;
+
+/// InitializeRegistry instruction discriminator
+pub const INITIALIZE_REGISTRY_DISCRIMINATOR: u8 = 14;
+
+/// CreateRentVault instruction discriminator
+pub const CREATE_RENT_VAULT_DISCRIMINATOR: u8 = 15;
+
+/// WithdrawRentVault instruction discriminator
+pub const WITHDRAW_RENT_VAULT_DISCRIMINATOR: u8 = 16;
+
+/// Shortest RegisterAgent instruction data the program accepts, discriminator
+/// included: `bump`, a [`MIN_NAME_LEN`] name and an empty inbox URL
+pub const REGISTER_AGENT_MIN_DATA_LEN: usize = 1 + 1 + 4 + MIN_NAME_LEN + 4;
+
+/// Shortest UpdateAgent instruction data the program accepts, discriminator
+/// included: a version 3 update with an empty field mask and no metadata
+pub const UPDATE_AGENT_MIN_DATA_LEN: usize = 1 + 1 + 1 + 1;
+
+/// SetAccepting instruction data length, discriminator included
+pub const SET_ACCEPTING_MIN_DATA_LEN: usize = 1 + 1;
+
+/// SetOperator instruction data length, discriminator included
+pub const SET_OPERATOR_MIN_DATA_LEN: usize = 1 + 32;
+
+/// Shortest CheckRegistryBatch instruction data the program accepts,
+/// discriminator included: the count and a single authority
+pub const CHECK_REGISTRY_BATCH_MIN_DATA_LEN: usize = 1 + 4 + 32;
+
+/// Encodes RegisterAgent instruction data, rejecting out-of-range fields.
+///
+/// Layout: discriminator, `bump`, then `name` and `inbox_url` as u32-LE
+/// length-prefixed strings. The trailing metadata and operator tags are omitted.
+pub fn encode_register_agent(
+    bump: u8,
+    name: &str,
+    inbox_url: &str,
+) -> Result<Vec<u8>, PreflightError> {
+    check_register_args(name, inbox_url)?;
+    Ok(encode_register_agent_unchecked(bump, name, inbox_url))
+}
+
+/// Encodes RegisterAgent instruction data without length checks.
+///
+/// Only useful for exercising the program's own validation.
+pub fn encode_register_agent_unchecked(bump: u8, name: &str, inbox_url: &str) -> Vec<u8> {
+    let mut data = Vec::with_capacity(2 + 4 + name.len() + 4 + inbox_url.len());
+    data.push(REGISTER_AGENT_DISCRIMINATOR);
+    data.push(bump);
+    push_str(&mut data, name);
+    push_str(&mut data, inbox_url);
+    data
+}
+
+/// Encodes UpdateAgent instruction data, rejecting over-long fields.
+///
+/// Layout: discriminator, then `name` and `inbox_url` as u32-LE length-prefixed
+/// strings. The trailing metadata tag is omitted, leaving metadata unchanged.
+pub fn encode_update_agent(name: &str, inbox_url: &str) -> Result<Vec<u8>, PreflightError> {
+    check_register_args(name, inbox_url)?;
+    Ok(encode_update_agent_unchecked(name, inbox_url))
+}
+
+/// Encodes UpdateAgent instruction data without length checks.
+///
+/// Only useful for exercising the program's own validation.
+pub fn encode_update_agent_unchecked(name: &str, inbox_url: &str) -> Vec<u8> {
+    let mut data = Vec::with_capacity(1 + 4 + name.len() + 4 + inbox_url.len());
+    data.push(UPDATE_AGENT_DISCRIMINATOR);
+    push_str(&mut data, name);
+    push_str(&mut data, inbox_url);
+    data
+}
+
+fn push_str(data: &mut Vec<u8>, value: &str) {
+    data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    data.extend_from_slice(value.as_bytes());
+}
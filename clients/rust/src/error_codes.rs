@@ -0,0 +1,61 @@
+//! Program error code table
+//!
+//! The program returns its errors as `Custom(100 + index)`, while the
+//! generated [`crate::AgentmailError`] numbers them from 0. SDKs in other
+//! languages can copy [`ERROR_CODES`] to map an on-chain code to its meaning.
+
+/// Offset the program adds to every `AgentMailProgramError` index
+pub const ERROR_CODE_OFFSET: u32 = 100;
+
+/// Every program error as `(custom code, message)`, in code order.
+///
+/// Codes are stable: retired errors keep their slot rather than shifting
+/// the ones after them.
+pub const ERROR_CODES: &[(u32, &str)] = &[
+    (
+        100,
+        "Authority invalid or does not match registry authority",
+    ),
+    (101, "Agent name is too long (max 64 bytes)"),
+    (102, "Inbox URL is too long (max 256 bytes)"),
+    (103, "Invalid name length in stored data"),
+    (104, "Invalid inbox URL length in stored data"),
+    (105, "Invalid UTF-8 data in string fields"),
+    (106, "Agent registry already exists for this authority"),
+    (107, "Agent registry does not exist for this authority"),
+    (108, "Invalid account size for agent registry"),
+    (109, "Invalid account data format"),
+    (110, "Agent name contains control characters"),
+    (111, "Counter instructions were removed from this program"),
+    (112, "Batch exceeds the maximum number of entries"),
+    (113, "Agent registry account is smaller than expected"),
+    (114, "Agent registry account is larger than expected"),
+    (115, "Too many inbox URLs (max 4 additional)"),
+    (
+        116,
+        "Account discriminator does not match the expected account type",
+    ),
+    (117, "Metadata URI is too long (max 128 bytes)"),
+    (118, "Update would not change the registry"),
+    (119, "Agent name is too short (min 2 bytes)"),
+    (120, "Inbox URL has no path after the host"),
+    (121, "Clock sysvar is unavailable"),
+    (
+        122,
+        "Update nonce is not greater than the last accepted nonce",
+    ),
+    (123, "Too many accepted MIME types"),
+    (
+        124,
+        "MIME type must be 1-32 printable ASCII bytes with a slash",
+    ),
+];
+
+/// Looks up the message for a `Custom` error code returned by the program.
+///
+/// Returns `None` for codes outside the program's range, such as those from
+/// the system program.
+pub fn error_message(code: u32) -> Option<&'static str> {
+    let index = code.checked_sub(ERROR_CODE_OFFSET)?;
+    ERROR_CODES.get(index as usize).map(|(_, message)| *message)
+}
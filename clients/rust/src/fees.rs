@@ -0,0 +1,79 @@
+//! Transaction cost estimation
+//!
+//! Helpers for showing users what an AgentMail instruction will cost before
+//! they sign it.
+
+use solana_hash::Hash;
+use solana_instruction::Instruction;
+use solana_message::Message;
+
+/// Builds the single-instruction message whose fee `getFeeForMessage` reports.
+///
+/// The first signer account of `instruction` is used as the fee payer, which
+/// is the `payer` for RegisterAgent and the `agent_authority` for the others.
+pub fn fee_message(
+    instruction: &Instruction,
+    recent_blockhash: &Hash,
+) -> Result<Message, std::io::Error> {
+    let payer = instruction
+        .accounts
+        .iter()
+        .find(|account| account.is_signer)
+        .map(|account| account.pubkey)
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "instruction has no signer to pay fees",
+            )
+        })?;
+
+    Ok(Message::new_with_blockhash(
+        std::slice::from_ref(instruction),
+        Some(&payer),
+        recent_blockhash,
+    ))
+}
+
+/// Estimates the lamports needed to register `n` agents at `per_agent_lamports` each.
+///
+/// Returns an error instead of wrapping when the total does not fit in a `u64`.
+pub fn total_cost_estimate(per_agent_lamports: u64, n: u64) -> Result<u64, std::io::Error> {
+    per_agent_lamports.checked_mul(n).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("cost of {n} agents at {per_agent_lamports} lamports overflows u64"),
+        )
+    })
+}
+
+/// Estimates the network fee, in lamports, for sending `instruction` on its own.
+#[cfg(feature = "fetch")]
+pub fn estimate_fee(
+    rpc: &solana_client::rpc_client::RpcClient,
+    instruction: &Instruction,
+) -> Result<u64, std::io::Error> {
+    let rpc_err =
+        |e: solana_client::client_error::ClientError| std::io::Error::other(e.to_string());
+    let blockhash = rpc.get_latest_blockhash().map_err(rpc_err)?;
+    let message = fee_message(instruction, &blockhash)?;
+    rpc.get_fee_for_message(&message).map_err(rpc_err)
+}
+
+/// Estimates the total lamports a RegisterAgent costs the payer: the network
+/// fee plus the rent-exempt deposit for the registry account.
+#[cfg(feature = "fetch")]
+pub fn estimate_register_cost(
+    rpc: &solana_client::rpc_client::RpcClient,
+    register_instruction: &Instruction,
+) -> Result<u64, std::io::Error> {
+    let fee = estimate_fee(rpc, register_instruction)?;
+    let rent = rpc
+        .get_minimum_balance_for_rent_exemption(crate::AGENT_REGISTRY_ACCOUNT_LEN)
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    fee.checked_add(rent).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "register cost overflows u64",
+        )
+    })
+}
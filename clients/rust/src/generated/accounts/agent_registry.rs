@@ -15,7 +15,11 @@ pub struct AgentRegistry {
     pub discriminator: u8,
     pub version: u8,
     pub bump: u8,
-    pub padding: [u8; 6],
+    pub data_version: u8,
+    pub url_count: u8,
+    pub accepting: u8,
+    pub deleted: u8,
+    pub padding: [u8; 3],
     #[cfg_attr(
         feature = "serde",
         serde(with = "serde_with::As::<serde_with::DisplayFromStr>")
@@ -27,6 +31,19 @@ pub struct AgentRegistry {
     pub inbox_url: [u8; 260],
     pub created_at: i64,
     pub updated_at: i64,
+    pub created_slot: u64,
+    pub inbox_urls: [[u8; 132]; 4],
+    #[cfg_attr(feature = "serde", serde(with = "serde_big_array::BigArray"))]
+    pub metadata_uri: [u8; 132],
+    pub metadata_hash: [u8; 32],
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "serde_with::As::<serde_with::DisplayFromStr>")
+    )]
+    pub operator: Pubkey,
+    pub reserved: [u8; 4],
+    pub last_nonce: u64,
+    pub accepted_mime_types: [[u8; 32]; 4],
 }
 
 impl AgentRegistry {
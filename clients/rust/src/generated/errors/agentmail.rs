@@ -40,6 +40,51 @@ pub enum AgentmailError {
     /// 9 - Invalid account data format
     #[error("Invalid account data format")]
     InvalidAccountData = 0x9,
+    /// 10 - Agent name contains control characters
+    #[error("Agent name contains control characters")]
+    InvalidNameCharacters = 0xA,
+    /// 11 - Counter instructions were removed from this program
+    #[error("Counter instructions were removed from this program")]
+    CounterInstructionsRemoved = 0xB,
+    /// 12 - Batch exceeds the maximum number of entries
+    #[error("Batch exceeds the maximum number of entries")]
+    BatchTooLarge = 0xC,
+    /// 13 - Agent registry account is smaller than expected
+    #[error("Agent registry account is smaller than expected")]
+    AccountTooSmall = 0xD,
+    /// 14 - Agent registry account is larger than expected
+    #[error("Agent registry account is larger than expected")]
+    AccountTooLarge = 0xE,
+    /// 15 - Too many inbox URLs (max 4 additional)
+    #[error("Too many inbox URLs (max 4 additional)")]
+    TooManyInboxUrls = 0xF,
+    /// 16 - Account discriminator does not match the expected account type
+    #[error("Account discriminator does not match the expected account type")]
+    InvalidDiscriminator = 0x10,
+    /// 17 - Metadata URI is too long (max 128 bytes)
+    #[error("Metadata URI is too long (max 128 bytes)")]
+    MetadataUriTooLong = 0x11,
+    /// 18 - Update would not change the registry
+    #[error("Update would not change the registry")]
+    NoChanges = 0x12,
+    /// 19 - Agent name is too short (min 2 bytes)
+    #[error("Agent name is too short (min 2 bytes)")]
+    NameTooShort = 0x13,
+    /// 20 - Inbox URL has no path after the host
+    #[error("Inbox URL has no path after the host")]
+    InboxUrlMissingPath = 0x14,
+    /// 21 - Clock sysvar is unavailable
+    #[error("Clock sysvar is unavailable")]
+    ClockUnavailable = 0x15,
+    /// 22 - Update nonce is not greater than the last accepted nonce
+    #[error("Update nonce is not greater than the last accepted nonce")]
+    StaleNonce = 0x16,
+    /// 23 - Too many accepted MIME types
+    #[error("Too many accepted MIME types")]
+    TooManyMimeTypes = 0x17,
+    /// 24 - MIME type must be 1-32 printable ASCII bytes with a slash
+    #[error("MIME type must be 1-32 printable ASCII bytes with a slash")]
+    InvalidMimeType = 0x18,
 }
 
 impl From<AgentmailError> for solana_program_error::ProgramError {
@@ -0,0 +1,465 @@
+//! Instruction builders
+//!
+//! The supported way to build AgentMail instructions. Each builder returns an
+//! `Instruction` for the AgentMail program with the accounts from
+//! [`crate::account_metas`] and the data from [`crate::encode`]. The generated
+//! instruction types are re-exported here as well.
+//!
+//! Builders do not check field lengths, so the program's own validation can
+//! still be exercised; run [`crate::preflight::check_register_args`] first to
+//! catch transactions that are certain to fail.
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+use crate::{
+    account_metas::SYSTEM_PROGRAM_ID,
+    encode::{
+        encode_register_agent_unchecked, encode_update_agent_unchecked,
+        ALLOCATE_REGISTRY_DISCRIMINATOR, CHECK_REGISTRY_BATCH_DISCRIMINATOR,
+        CREATE_RENT_VAULT_DISCRIMINATOR, DEREGISTER_AGENT_DISCRIMINATOR,
+        INITIALIZE_REGISTRY_DISCRIMINATOR, MIGRATE_REGISTRY_DISCRIMINATOR,
+        REACTIVATE_AGENT_DISCRIMINATOR, SET_ACCEPTING_DISCRIMINATOR, SET_OPERATOR_DISCRIMINATOR,
+        SOFT_DEREGISTER_AGENT_DISCRIMINATOR, UPDATE_AGENT_DISCRIMINATOR,
+        WITHDRAW_RENT_VAULT_DISCRIMINATOR,
+    },
+    registry::{find_agent_registry_pda, find_rent_vault_pda},
+    AGENTMAIL_ID,
+};
+
+pub use crate::generated::instructions::*;
+
+/// Builds a RegisterAgent instruction for the registry PDA at `agent_registry`.
+///
+/// `bump` must be the canonical bump of that PDA; see
+/// [`crate::registry::find_agent_registry_pda`].
+pub fn register(
+    payer: &Pubkey,
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    bump: u8,
+    name: &str,
+    inbox_url: &str,
+) -> Instruction {
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        accounts: RegisterAgent::account_metas(payer, agent_authority, agent_registry),
+        data: encode_register_agent_unchecked(bump, name, inbox_url),
+    }
+}
+
+/// Builds a RegisterAgent instruction for `agent_authority`'s registry PDA.
+///
+/// Derives the registry address and its canonical bump internally, so the
+/// bump cannot be wrong. Prefer this over [`register`] unless the PDA has
+/// already been derived.
+pub fn register_derived(
+    payer: &Pubkey,
+    agent_authority: &Pubkey,
+    name: &str,
+    inbox_url: &str,
+) -> Instruction {
+    let (agent_registry, bump) = find_agent_registry_pda(agent_authority);
+    register(
+        payer,
+        agent_authority,
+        &agent_registry,
+        bump,
+        name,
+        inbox_url,
+    )
+}
+
+/// Builds a RegisterAgent instruction that is safe to retry.
+///
+/// If the registry already exists with this name and inbox URL (and no
+/// metadata or operator) the program succeeds without changes; any other
+/// existing registry still fails with `RegistryAlreadyExists`.
+pub fn register_idempotent(
+    payer: &Pubkey,
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    bump: u8,
+    name: &str,
+    inbox_url: &str,
+) -> Instruction {
+    let mut instruction = register(
+        payer,
+        agent_authority,
+        agent_registry,
+        bump,
+        name,
+        inbox_url,
+    );
+    // No metadata, no operator, then the idempotency flag
+    instruction.data.extend_from_slice(&[0, 0, 1]);
+    instruction
+}
+
+/// Builds an AllocateRegistry instruction creating the empty registry PDA
+/// for `agent_authority`, funded by `payer`.
+///
+/// The authority does not sign; it fills the registry in later with
+/// [`initialize_registry`]. `bump` must be the canonical bump.
+pub fn allocate_registry(
+    payer: &Pubkey,
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    bump: u8,
+) -> Instruction {
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*agent_authority, false),
+            AccountMeta::new(*agent_registry, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(AGENTMAIL_ID, false),
+        ],
+        data: vec![ALLOCATE_REGISTRY_DISCRIMINATOR, bump],
+    }
+}
+
+/// Builds an InitializeRegistry instruction writing a registry created by
+/// [`allocate_registry`]. The data matches [`register`].
+pub fn initialize_registry(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    bump: u8,
+    name: &str,
+    inbox_url: &str,
+) -> Instruction {
+    let mut data = encode_register_agent_unchecked(bump, name, inbox_url);
+    data[0] = INITIALIZE_REGISTRY_DISCRIMINATOR;
+
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        // Same accounts as UpdateAgent
+        accounts: UpdateAgent::account_metas(agent_authority, agent_registry),
+        data,
+    }
+}
+
+/// Builds an UpdateAgent instruction replacing the name and inbox URL.
+pub fn update(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    name: &str,
+    inbox_url: &str,
+) -> Instruction {
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        accounts: UpdateAgent::account_metas(agent_authority, agent_registry),
+        data: encode_update_agent_unchecked(name, inbox_url),
+    }
+}
+
+/// Builds an UpdateAgent instruction carrying a replay nonce.
+///
+/// The program rejects it with `StaleNonce` unless `nonce` exceeds the
+/// registry's `last_nonce`, so a captured copy cannot be replayed.
+pub fn update_with_nonce(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    name: &str,
+    inbox_url: &str,
+    nonce: u64,
+) -> Instruction {
+    let mut instruction = update(agent_authority, agent_registry, name, inbox_url);
+    // No metadata, then the nonce tag and value
+    instruction.data.extend_from_slice(&[0, 1]);
+    instruction.data.extend_from_slice(&nonce.to_le_bytes());
+    instruction
+}
+
+/// Builds an UpdateAgent instruction replacing only the name.
+pub fn update_name(agent_authority: &Pubkey, agent_registry: &Pubkey, name: &str) -> Instruction {
+    // Data version 3, then a field mask selecting only the name
+    let mut data = vec![UPDATE_AGENT_DISCRIMINATOR, 3, 1 << 0];
+    data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+    data.extend_from_slice(name.as_bytes());
+    // No metadata commitment
+    data.push(0);
+
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        accounts: UpdateAgent::account_metas(agent_authority, agent_registry),
+        data,
+    }
+}
+
+/// Builds an UpdateAgent instruction replacing only the primary inbox URL.
+pub fn update_inbox_url(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    inbox_url: &str,
+) -> Instruction {
+    // Data version 3, then a field mask selecting only the inbox URL
+    let mut data = vec![UPDATE_AGENT_DISCRIMINATOR, 3, 1 << 1];
+    data.extend_from_slice(&(inbox_url.len() as u32).to_le_bytes());
+    data.extend_from_slice(inbox_url.as_bytes());
+    // No metadata commitment
+    data.push(0);
+
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        accounts: UpdateAgent::account_metas(agent_authority, agent_registry),
+        data,
+    }
+}
+
+/// Builds an UpdateAgent instruction replacing the accepted MIME types and
+/// leaving every other field unchanged; an empty slice clears them.
+///
+/// The program allows at most four, each 1 to 32 printable ASCII bytes
+/// containing a `/`.
+pub fn update_mime_types(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    mime_types: &[&str],
+) -> Instruction {
+    // Data version 3, then a field mask selecting only the MIME types
+    let mut data = vec![
+        UPDATE_AGENT_DISCRIMINATOR,
+        3,
+        1 << 2,
+        mime_types.len() as u8,
+    ];
+    for mime_type in mime_types {
+        data.push(mime_type.len() as u8);
+        data.extend_from_slice(mime_type.as_bytes());
+    }
+    // No metadata commitment
+    data.push(0);
+
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        accounts: UpdateAgent::account_metas(agent_authority, agent_registry),
+        data,
+    }
+}
+
+/// Builds an UpdateAgent instruction replacing the additional inbox URLs and
+/// leaving every other field unchanged; an empty slice clears them.
+///
+/// The program allows at most four, each up to 128 bytes. Senders try them
+/// in order after the primary inbox URL.
+pub fn update_inbox_urls(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    inbox_urls: &[&str],
+) -> Instruction {
+    // Data version 3, then a field mask selecting only the inbox URL list
+    let mut data = vec![
+        UPDATE_AGENT_DISCRIMINATOR,
+        3,
+        1 << 3,
+        inbox_urls.len() as u8,
+    ];
+    for inbox_url in inbox_urls {
+        data.extend_from_slice(&(inbox_url.len() as u32).to_le_bytes());
+        data.extend_from_slice(inbox_url.as_bytes());
+    }
+    // No metadata commitment
+    data.push(0);
+
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        accounts: UpdateAgent::account_metas(agent_authority, agent_registry),
+        data,
+    }
+}
+
+/// Builds an UpdateAgent instruction that can also migrate a legacy registry.
+///
+/// The program grows a registry still on a shorter layout before updating
+/// it; `payer` funds the rent top-up, or the authority when `None`.
+pub fn update_with_payer(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    payer: Option<&Pubkey>,
+    name: &str,
+    inbox_url: &str,
+) -> Instruction {
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        accounts: UpdateAgent::account_metas_with_payer(agent_authority, agent_registry, payer),
+        data: encode_update_agent_unchecked(name, inbox_url),
+    }
+}
+
+/// Builds a DeregisterAgent instruction.
+///
+/// The reclaimed rent goes to the authority.
+pub fn deregister(agent_authority: &Pubkey, agent_registry: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        accounts: DeregisterAgent::account_metas(agent_authority, agent_registry),
+        data: vec![DEREGISTER_AGENT_DISCRIMINATOR],
+    }
+}
+
+/// Builds a DeregisterAgent instruction that closes into a rent vault.
+///
+/// The reclaimed rent goes to `rent_vault`, which must be a vault created by
+/// [`create_rent_vault`]; see [`crate::registry::find_rent_vault_pda`].
+pub fn deregister_to_vault(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    rent_vault: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        accounts: DeregisterAgent::account_metas_to_vault(
+            agent_authority,
+            agent_registry,
+            rent_vault,
+        ),
+        data: vec![DEREGISTER_AGENT_DISCRIMINATOR],
+    }
+}
+
+/// Builds a CreateRentVault instruction for `vault_authority`'s vault PDA,
+/// funded by `payer`.
+///
+/// Derives the vault address and its canonical bump internally. Only
+/// `vault_authority` can later withdraw with [`withdraw_rent_vault`].
+pub fn create_rent_vault(payer: &Pubkey, vault_authority: &Pubkey) -> Instruction {
+    let (rent_vault, bump) = find_rent_vault_pda(vault_authority);
+
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*vault_authority, true),
+            AccountMeta::new(rent_vault, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(AGENTMAIL_ID, false),
+        ],
+        data: vec![CREATE_RENT_VAULT_DISCRIMINATOR, bump],
+    }
+}
+
+/// Builds a WithdrawRentVault instruction moving `amount` lamports from
+/// `vault_authority`'s vault to `destination`.
+///
+/// The program keeps the vault's rent-exempt minimum and fails with
+/// `InsufficientFunds` if `amount` would dip into it.
+pub fn withdraw_rent_vault(
+    vault_authority: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (rent_vault, _) = find_rent_vault_pda(vault_authority);
+
+    let mut data = Vec::with_capacity(1 + 8);
+    data.push(WITHDRAW_RENT_VAULT_DISCRIMINATOR);
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*vault_authority, true),
+            AccountMeta::new(rent_vault, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(AGENTMAIL_ID, false),
+        ],
+        data,
+    }
+}
+
+/// Builds a SetAccepting instruction.
+pub fn set_accepting(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    accepting: bool,
+) -> Instruction {
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        // Same accounts as UpdateAgent
+        accounts: UpdateAgent::account_metas(agent_authority, agent_registry),
+        data: vec![SET_ACCEPTING_DISCRIMINATOR, accepting as u8],
+    }
+}
+
+/// Builds a SetOperator instruction; `Pubkey::default()` clears the operator.
+pub fn set_operator(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    operator: &Pubkey,
+) -> Instruction {
+    let mut data = Vec::with_capacity(1 + 32);
+    data.push(SET_OPERATOR_DISCRIMINATOR);
+    data.extend_from_slice(operator.as_ref());
+
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        // Same accounts as UpdateAgent
+        accounts: UpdateAgent::account_metas(agent_authority, agent_registry),
+        data,
+    }
+}
+
+/// Builds a SoftDeregisterAgent instruction.
+///
+/// The registry stays open and keeps its rent; [`reactivate`] undoes it.
+pub fn soft_deregister(agent_authority: &Pubkey, agent_registry: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        // Same accounts as UpdateAgent
+        accounts: UpdateAgent::account_metas(agent_authority, agent_registry),
+        data: vec![SOFT_DEREGISTER_AGENT_DISCRIMINATOR],
+    }
+}
+
+/// Builds a ReactivateAgent instruction for a soft-deregistered agent.
+pub fn reactivate(agent_authority: &Pubkey, agent_registry: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        // Same accounts as UpdateAgent
+        accounts: UpdateAgent::account_metas(agent_authority, agent_registry),
+        data: vec![REACTIVATE_AGENT_DISCRIMINATOR],
+    }
+}
+
+/// Builds a MigrateRegistry instruction; `payer` covers any rent top-up.
+pub fn migrate_registry(
+    payer: &Pubkey,
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        // Same accounts as RegisterAgent
+        accounts: RegisterAgent::account_metas(payer, agent_authority, agent_registry),
+        data: vec![MIGRATE_REGISTRY_DISCRIMINATOR],
+    }
+}
+
+/// Builds a CheckRegistryBatch instruction checking `registries[i]` against
+/// `authorities[i]`.
+///
+/// The program rejects slices of different lengths. Its return data is a u32
+/// (LE) bitmask with bit `i` set when registry `i` passed.
+pub fn check_registry_batch(registries: &[Pubkey], authorities: &[Pubkey]) -> Instruction {
+    let mut accounts = Vec::with_capacity(1 + registries.len());
+    accounts.push(AccountMeta::new_readonly(AGENTMAIL_ID, false));
+    accounts.extend(
+        registries
+            .iter()
+            .map(|registry| AccountMeta::new_readonly(*registry, false)),
+    );
+
+    let mut data = Vec::with_capacity(1 + 4 + 32 * authorities.len());
+    data.push(CHECK_REGISTRY_BATCH_DISCRIMINATOR);
+    data.extend_from_slice(&(authorities.len() as u32).to_le_bytes());
+    for authority in authorities {
+        data.extend_from_slice(authority.as_ref());
+    }
+
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        accounts,
+        data,
+    }
+}
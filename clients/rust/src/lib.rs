@@ -1,13 +1,36 @@
-// Suppress warnings for generated code
-#![allow(warnings)]
-
 extern crate alloc;
 
-// Re-export generated code
+// Re-export generated code, suppressing its warnings
+#[allow(warnings)]
 pub mod generated;
 pub use generated::*;
 
+// Hand-written helpers
+pub mod account_metas;
+pub mod address;
+pub mod audit;
+pub mod encode;
+pub mod error_codes;
+pub mod fees;
+pub mod instructions;
+pub mod preflight;
+pub mod registry;
+pub mod timestamp;
+#[cfg(feature = "websocket")]
+pub mod watch;
+
 // Re-export commonly used items
+pub use account_metas::*;
+pub use address::*;
+pub use audit::*;
+pub use encode::*;
+pub use error_codes::*;
+pub use fees::*;
 pub use generated::accounts::*;
 pub use generated::errors::*;
 pub use generated::programs::*;
+pub use preflight::*;
+pub use registry::*;
+pub use timestamp::*;
+#[cfg(feature = "websocket")]
+pub use watch::*;
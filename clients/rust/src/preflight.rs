@@ -0,0 +1,100 @@
+//! Client-side RegisterAgent preflight
+//!
+//! Catches the common RegisterAgent failures off-chain so users don't pay for
+//! a transaction that is certain to fail.
+
+use solana_pubkey::Pubkey;
+use thiserror::Error;
+
+/// Maximum agent name length in bytes, mirroring the program
+pub const MAX_NAME_LEN: usize = 64;
+
+/// Minimum agent name length in bytes, mirroring the program
+pub const MIN_NAME_LEN: usize = 2;
+
+/// Maximum inbox URL length in bytes, mirroring the program
+pub const MAX_INBOX_URL_LEN: usize = 256;
+
+/// Total AgentRegistry account size in bytes, including discriminator and version
+pub const AGENT_REGISTRY_ACCOUNT_LEN: usize = 1258;
+
+/// Reasons a RegisterAgent transaction would fail.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum PreflightError {
+    #[error("Agent name is too long ({len} bytes, max {MAX_NAME_LEN})")]
+    NameTooLong { len: usize },
+    #[error("Agent name is too short ({len} bytes, min {MIN_NAME_LEN})")]
+    NameTooShort { len: usize },
+    #[error("Inbox URL is too long ({len} bytes, max {MAX_INBOX_URL_LEN})")]
+    InboxUrlTooLong { len: usize },
+    #[error("Agent registry already exists at {0}")]
+    AlreadyRegistered(Pubkey),
+    #[error("Payer balance {balance} is below the {required} lamports needed for rent")]
+    InsufficientFunds { balance: u64, required: u64 },
+    #[error("RPC error: {0}")]
+    Rpc(String),
+}
+
+/// Checks name and inbox URL lengths against the program limits.
+pub fn check_register_args(name: &str, inbox_url: &str) -> Result<(), PreflightError> {
+    if name.len() > MAX_NAME_LEN {
+        return Err(PreflightError::NameTooLong { len: name.len() });
+    }
+    if name.len() < MIN_NAME_LEN {
+        return Err(PreflightError::NameTooShort { len: name.len() });
+    }
+    if inbox_url.len() > MAX_INBOX_URL_LEN {
+        return Err(PreflightError::InboxUrlTooLong {
+            len: inbox_url.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Checks on-chain prerequisites from already-fetched state.
+///
+/// `registry_exists` is whether the registry PDA at `registry` has an account,
+/// `rent_minimum` is the rent-exempt minimum for [`AGENT_REGISTRY_ACCOUNT_LEN`].
+pub fn check_register_state(
+    registry: &Pubkey,
+    registry_exists: bool,
+    payer_balance: u64,
+    rent_minimum: u64,
+) -> Result<(), PreflightError> {
+    if registry_exists {
+        return Err(PreflightError::AlreadyRegistered(*registry));
+    }
+    if payer_balance < rent_minimum {
+        return Err(PreflightError::InsufficientFunds {
+            balance: payer_balance,
+            required: rent_minimum,
+        });
+    }
+    Ok(())
+}
+
+/// Runs every RegisterAgent preflight check against the cluster.
+#[cfg(feature = "fetch")]
+pub fn preflight_register(
+    rpc: &solana_client::rpc_client::RpcClient,
+    payer: &Pubkey,
+    authority: &Pubkey,
+    name: &str,
+    inbox_url: &str,
+) -> Result<(), PreflightError> {
+    check_register_args(name, inbox_url)?;
+
+    let rpc_err = |e: solana_client::client_error::ClientError| PreflightError::Rpc(e.to_string());
+    let (registry, _) = crate::find_agent_registry_pda(authority);
+    let registry_exists = rpc
+        .get_account_with_commitment(&registry, rpc.commitment())
+        .map_err(rpc_err)?
+        .value
+        .is_some();
+    let payer_balance = rpc.get_balance(payer).map_err(rpc_err)?;
+    let rent_minimum = rpc
+        .get_minimum_balance_for_rent_exemption(AGENT_REGISTRY_ACCOUNT_LEN)
+        .map_err(rpc_err)?;
+
+    check_register_state(&registry, registry_exists, payer_balance, rent_minimum)
+}
@@ -0,0 +1,438 @@
+//! AgentMail Registry Client
+//!
+//! Utilities for interacting with the AgentMail on-chain registry.
+
+use solana_instruction::Instruction;
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+use solana_signer::Signer;
+
+use crate::{accounts::AgentRegistry, AGENTMAIL_ID};
+
+/// Static prefix seed for AgentRegistry PDAs
+pub const AGENT_REGISTRY_SEED: &[u8] = b"agentmail";
+
+/// Account discriminator byte identifying AgentRegistry accounts
+pub const AGENT_REGISTRY_DISCRIMINATOR: u8 = 1;
+
+/// Static prefix seed for RentVault PDAs
+pub const RENT_VAULT_SEED: &[u8] = b"rent_vault";
+
+/// Account discriminator byte identifying RentVault accounts
+pub const RENT_VAULT_DISCRIMINATOR: u8 = 2;
+
+/// Total RentVault account size: discriminator, version, bump, inner
+/// version and the 32-byte authority
+pub const RENT_VAULT_ACCOUNT_LEN: usize = 36;
+
+/// Byte offset of the authority within RentVault account data
+pub const RENT_VAULT_AUTHORITY_OFFSET: usize = 4;
+
+/// Account types owned by the AgentMail program, keyed by discriminator byte.
+///
+/// Mirrors the program's `AgentMailAccountDiscriminators`; the integration
+/// tests check the two agree on every byte.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum AccountKind {
+    AgentRegistry = AGENT_REGISTRY_DISCRIMINATOR,
+    RentVault = RENT_VAULT_DISCRIMINATOR,
+}
+
+impl TryFrom<u8> for AccountKind {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            AGENT_REGISTRY_DISCRIMINATOR => Ok(Self::AgentRegistry),
+            RENT_VAULT_DISCRIMINATOR => Ok(Self::RentVault),
+            other => Err(other),
+        }
+    }
+}
+
+/// Identifies which AgentMail account type `data` holds from its discriminator.
+///
+/// Returns `None` for empty data or an unknown discriminator.
+pub fn account_type(data: &[u8]) -> Option<AccountKind> {
+    data.first()
+        .and_then(|&byte| AccountKind::try_from(byte).ok())
+}
+
+/// Maximum number of accounts accepted by a single `getMultipleAccounts` call
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Byte offset of `authority` within AgentRegistry account data.
+///
+/// discriminator (1) + account version (1) + bump (1) + version (1) +
+/// url_count (1) + accepting (1) + deleted (1) + padding (3), unchanged across
+/// every layout up to the current 1258 byte version 8.
+pub const AGENT_REGISTRY_AUTHORITY_OFFSET: usize = 1 + 1 + 1 + 1 + 1 + 1 + 1 + 3;
+
+/// Byte offset of the `deleted` flag within AgentRegistry account data.
+///
+/// Non-zero once the agent soft-deregisters; senders treat it as absent.
+pub const AGENT_REGISTRY_DELETED_OFFSET: usize = 6;
+
+/// Byte offset of `operator` within AgentRegistry account data.
+///
+/// Follows `metadata_hash`; all zeroes when the agent has no operator.
+pub const AGENT_REGISTRY_OPERATOR_OFFSET: usize = 1086;
+
+/// Derives the AgentRegistry PDA address for a given agent authority.
+pub fn find_agent_registry_pda(agent_authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[AGENT_REGISTRY_SEED, agent_authority.as_ref()],
+        &AGENTMAIL_ID,
+    )
+}
+
+/// Derives the RentVault PDA address for a given vault authority.
+pub fn find_rent_vault_pda(vault_authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RENT_VAULT_SEED, vault_authority.as_ref()], &AGENTMAIL_ID)
+}
+
+/// Derives the AgentRegistry PDA for each authority, index-aligned with `authorities`.
+pub fn derive_registry_pdas(authorities: &[Pubkey]) -> Vec<(Pubkey, u8)> {
+    authorities.iter().map(find_agent_registry_pda).collect()
+}
+
+/// Returns the minimal signer set for `RegisterAgent`.
+///
+/// Both the payer and the agent authority must sign; when they are the same key
+/// it is only included once.
+pub fn register_signers<'a>(payer: &'a Keypair, authority: &'a Keypair) -> Vec<&'a dyn Signer> {
+    if payer.pubkey() == authority.pubkey() {
+        vec![payer]
+    } else {
+        vec![payer, authority]
+    }
+}
+
+/// Decodes registry account data, returning `None` when the account is absent.
+///
+/// Data that does not start with the AgentRegistry discriminator, e.g. an
+/// empty system account sitting at the PDA, is also `None`; only a registry
+/// that fails to decode is an error.
+pub fn decode_maybe_registry(data: Option<&[u8]>) -> Result<Option<AgentRegistry>, std::io::Error> {
+    data.filter(|data| is_registry_data(Some(data)))
+        .map(AgentRegistry::from_bytes)
+        .transpose()
+}
+
+/// Whether account data, or its first byte, is an AgentRegistry.
+///
+/// Only the discriminator is checked, so a one-byte data slice is enough.
+pub fn is_registry_data(data: Option<&[u8]>) -> bool {
+    data.and_then(account_type) == Some(AccountKind::AgentRegistry)
+}
+
+/// Returns whether `authority` has a live registry, without decoding it.
+///
+/// Fetches only the bytes up to the `deleted` flag and checks the registry
+/// is owned by the program, carries the AgentRegistry discriminator and has
+/// not soft-deregistered.
+#[cfg(feature = "fetch")]
+pub fn is_registered(
+    rpc: &solana_client::rpc_client::RpcClient,
+    authority: &Pubkey,
+) -> Result<bool, std::io::Error> {
+    use solana_account_decoder_client_types::{UiAccountEncoding, UiDataSliceConfig};
+    use solana_client::rpc_config::RpcAccountInfoConfig;
+
+    let (address, _) = find_agent_registry_pda(authority);
+    let config = RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        data_slice: Some(UiDataSliceConfig {
+            offset: 0,
+            length: AGENT_REGISTRY_DELETED_OFFSET + 1,
+        }),
+        ..Default::default()
+    };
+    let account = rpc
+        .get_account_with_config(&address, config)
+        .map_err(|e| std::io::Error::other(e.to_string()))?
+        .value;
+
+    Ok(account.is_some_and(|account| {
+        account.owner == AGENTMAIL_ID
+            && is_registry_data(Some(&account.data))
+            && account.data.get(AGENT_REGISTRY_DELETED_OFFSET) == Some(&0)
+    }))
+}
+
+/// Builds a `getProgramAccounts` memcmp filter matching registries owned by `authority`.
+#[cfg(feature = "fetch")]
+pub fn authority_memcmp_filter(authority: &Pubkey) -> solana_client::rpc_filter::RpcFilterType {
+    solana_client::rpc_filter::RpcFilterType::Memcmp(
+        solana_client::rpc_filter::Memcmp::new_raw_bytes(
+            AGENT_REGISTRY_AUTHORITY_OFFSET,
+            authority.to_bytes().to_vec(),
+        ),
+    )
+}
+
+/// Builds a `getProgramAccounts` memcmp filter matching registries in `operator`'s fleet.
+#[cfg(feature = "fetch")]
+pub fn operator_memcmp_filter(operator: &Pubkey) -> solana_client::rpc_filter::RpcFilterType {
+    solana_client::rpc_filter::RpcFilterType::Memcmp(
+        solana_client::rpc_filter::Memcmp::new_raw_bytes(
+            AGENT_REGISTRY_OPERATOR_OFFSET,
+            operator.to_bytes().to_vec(),
+        ),
+    )
+}
+
+/// Decodes a fixed-size, u32 LE length-prefixed string field.
+///
+/// Returns an error instead of panicking when the prefix points past the field
+/// or the bytes are not valid UTF-8.
+fn decode_prefixed_str(field: &[u8]) -> Result<String, std::io::Error> {
+    let invalid = |msg: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, msg.to_string());
+
+    let prefix: [u8; 4] = field
+        .get(..4)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| invalid("field shorter than its length prefix"))?;
+    let len = u32::from_le_bytes(prefix) as usize;
+    let bytes = field
+        .get(4..)
+        .and_then(|data| data.get(..len))
+        .ok_or_else(|| invalid("length prefix points past the field"))?;
+    String::from_utf8(bytes.to_vec()).map_err(|_| invalid("field is not valid UTF-8"))
+}
+
+impl AgentRegistry {
+    /// Decodes the agent name.
+    pub fn name_str(&self) -> Result<String, std::io::Error> {
+        decode_prefixed_str(&self.name)
+    }
+
+    /// Decodes the primary inbox URL.
+    pub fn inbox_url_str(&self) -> Result<String, std::io::Error> {
+        decode_prefixed_str(&self.inbox_url)
+    }
+
+    /// Decodes the additional inbox URLs, in the order senders should try them
+    /// after the primary one.
+    pub fn inbox_urls(&self) -> Result<Vec<String>, std::io::Error> {
+        let count = usize::from(self.url_count).min(self.inbox_urls.len());
+        self.inbox_urls[..count]
+            .iter()
+            .map(|slot| decode_prefixed_str(&slot[..]))
+            .collect()
+    }
+
+    /// Decodes the accepted MIME types in the order the agent listed them.
+    pub fn mime_types(&self) -> Result<Vec<String>, std::io::Error> {
+        self.accepted_mime_types
+            .iter()
+            .map(|slot| &slot[..slot.iter().position(|&b| b == 0).unwrap_or(slot.len())])
+            .take_while(|mime_type| !mime_type.is_empty())
+            .map(|mime_type| {
+                String::from_utf8(mime_type.to_vec()).map_err(|_| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "MIME type is not valid UTF-8",
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Whether the agent lists `mime_type`, ignoring ASCII case.
+    ///
+    /// Check before formatting a message. An agent listing no MIME types
+    /// has advertised nothing, which is not the same as accepting nothing.
+    pub fn accepts_mime_type(&self, mime_type: &str) -> bool {
+        self.mime_types()
+            .is_ok_and(|mime_types| mime_types.iter().any(|m| m.eq_ignore_ascii_case(mime_type)))
+    }
+
+    /// Whether the agent soft-deregistered and should be treated as absent.
+    pub fn is_deleted(&self) -> bool {
+        self.deleted != 0
+    }
+
+    /// Builds the RegisterAgent instruction that re-creates this registry,
+    /// e.g. when copying agents from mainnet to devnet.
+    ///
+    /// Only the name and primary inbox URL carry over; the program assigns
+    /// fresh timestamps, and secondary URLs, MIME types, metadata and the
+    /// operator must be set again afterwards. The PDA and bump are derived
+    /// from `authority`, which must sign the transaction.
+    pub fn register_instruction(&self, payer: &Pubkey) -> Result<Instruction, std::io::Error> {
+        let (agent_registry, bump) = find_agent_registry_pda(&self.authority);
+        Ok(crate::instructions::register(
+            payer,
+            &self.authority,
+            &agent_registry,
+            bump,
+            &self.name_str()?,
+            &self.inbox_url_str()?,
+        ))
+    }
+}
+
+/// Whether `signature` over `message` was made by the agent behind `registry`.
+///
+/// Agents sign off-chain messages with their authority key (see
+/// MESSAGE_FORMAT.md), so this checks the ed25519 signature against the
+/// registry's stored authority. Pass the exact signed bytes, e.g. the
+/// serialized offchain message envelope, not the decoded JSON payload.
+pub fn verify_agent_signature(
+    registry: &AgentRegistry,
+    message: &[u8],
+    signature: &Signature,
+) -> bool {
+    signature.verify(registry.authority.as_ref(), message)
+}
+
+/// Fetches the registries for a set of known authorities in as few RPC round trips as possible.
+///
+/// Derives every registry PDA, fetches them with `getMultipleAccounts` (chunked to
+/// [`MAX_MULTIPLE_ACCOUNTS`]) and decodes each one. The result is index-aligned with
+/// `authorities`, with `None` where the authority has no registry, including
+/// when the PDA holds an account the program does not own or that lacks the
+/// AgentRegistry discriminator.
+///
+/// Unlike a bare `Vec<Option<AgentRegistry>>`, the result is wrapped in a
+/// `Result`: a failed RPC call or a program-owned registry that does not
+/// decode is an error, never folded into `None`, which would claim the agent
+/// is absent.
+#[cfg(feature = "fetch")]
+pub fn fetch_registries(
+    rpc: &solana_client::rpc_client::RpcClient,
+    authorities: &[Pubkey],
+) -> Result<Vec<Option<AgentRegistry>>, std::io::Error> {
+    let addresses: Vec<Pubkey> = derive_registry_pdas(authorities)
+        .into_iter()
+        .map(|(address, _)| address)
+        .collect();
+
+    let mut registries = Vec::with_capacity(addresses.len());
+    for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let accounts = rpc
+            .get_multiple_accounts(chunk)
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
+        for account in accounts {
+            let data = account
+                .as_ref()
+                .filter(|account| account.owner == AGENTMAIL_ID)
+                .map(|account| account.data.as_slice());
+            registries.push(decode_maybe_registry(data)?);
+        }
+    }
+    Ok(registries)
+}
+
+/// Fetches every AgentRegistry owned by the program.
+///
+/// Only live registries on the current 1258 byte layout are returned, so a
+/// registry on an older layout (394, 922, 1090, 1122 or 1130 bytes) is left
+/// out until MigrateRegistry, or an UpdateAgent that migrates on the way,
+/// grows it. Use [`crate::audit::audit_program`] to find those.
+///
+/// Results are sorted with [`sort_by_authority`], so the order is deterministic
+/// across calls and RPC nodes even though `getProgramAccounts` itself makes no
+/// ordering guarantee.
+#[cfg(feature = "fetch")]
+pub fn fetch_all_agents(
+    rpc: &solana_client::rpc_client::RpcClient,
+) -> Result<Vec<crate::shared::DecodedAccount<AgentRegistry>>, std::io::Error> {
+    use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+
+    let config = RpcProgramAccountsConfig {
+        filters: Some(registry_filters()),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(solana_account_decoder_client_types::UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let accounts = rpc
+        .get_program_accounts_with_config(&AGENTMAIL_ID, config)
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+
+    let mut agents = accounts
+        .into_iter()
+        .map(|(address, account)| {
+            let data = AgentRegistry::from_bytes(&account.data)?;
+            Ok(crate::shared::DecodedAccount {
+                address,
+                account,
+                data,
+            })
+        })
+        .collect::<Result<Vec<_>, std::io::Error>>()?;
+    sort_by_authority(&mut agents);
+    Ok(agents)
+}
+
+/// Counts registered agents without transferring any account data.
+///
+/// Uses the same filters as [`fetch_all_agents`] with a zero-length data
+/// slice, so the response only carries addresses. Registries that have not
+/// been migrated to the current layout are not counted. This is still a full
+/// `getProgramAccounts` scan on the RPC node.
+#[cfg(feature = "fetch")]
+pub fn count_agents(rpc: &solana_client::rpc_client::RpcClient) -> Result<usize, std::io::Error> {
+    use solana_account_decoder_client_types::{UiAccountEncoding, UiDataSliceConfig};
+    use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+
+    let config = RpcProgramAccountsConfig {
+        filters: Some(registry_filters()),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            data_slice: Some(UiDataSliceConfig {
+                offset: 0,
+                length: 0,
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let accounts = rpc
+        .get_program_accounts_with_config(&AGENTMAIL_ID, config)
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+    Ok(accounts.len())
+}
+
+/// `getProgramAccounts` filters matching live, current-layout AgentRegistry accounts.
+///
+/// Soft-deregistered agents are excluded, and so are un-migrated registries,
+/// since the size filter only matches the current account length.
+#[cfg(feature = "fetch")]
+pub fn registry_filters() -> Vec<solana_client::rpc_filter::RpcFilterType> {
+    use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+
+    vec![
+        RpcFilterType::DataSize(crate::preflight::AGENT_REGISTRY_ACCOUNT_LEN as u64),
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, vec![AGENT_REGISTRY_DISCRIMINATOR])),
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            AGENT_REGISTRY_DELETED_OFFSET,
+            vec![0],
+        )),
+    ]
+}
+
+/// Sorts registries by authority pubkey, breaking ties by registry address.
+///
+/// The authority is read from the raw account data at
+/// [`AGENT_REGISTRY_AUTHORITY_OFFSET`], so the order only depends on the
+/// accounts themselves and not on the order they were returned in.
+#[cfg(feature = "fetch")]
+pub fn sort_by_authority(agents: &mut [crate::shared::DecodedAccount<AgentRegistry>]) {
+    agents.sort_by(|a, b| {
+        raw_authority(&a.account.data)
+            .cmp(&raw_authority(&b.account.data))
+            .then_with(|| a.address.cmp(&b.address))
+    });
+}
+
+/// Returns the authority bytes of raw registry account data, if present.
+#[cfg(feature = "fetch")]
+fn raw_authority(data: &[u8]) -> Option<&[u8]> {
+    data.get(AGENT_REGISTRY_AUTHORITY_OFFSET..AGENT_REGISTRY_AUTHORITY_OFFSET + 32)
+}
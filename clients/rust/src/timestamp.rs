@@ -0,0 +1,93 @@
+//! Unix timestamp helpers
+//!
+//! Typed wrapper around the `i64` unix timestamps stored on AgentRegistry.
+
+use crate::accounts::AgentRegistry;
+
+/// Unix timestamp in seconds, as stored in `created_at` / `updated_at`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Timestamp(pub i64);
+
+impl Timestamp {
+    /// Seconds elapsed between this timestamp and `now`.
+    ///
+    /// Negative when the timestamp is in the future relative to `now`.
+    pub fn age_from(&self, now: i64) -> i64 {
+        now.saturating_sub(self.0)
+    }
+
+    /// Returns true when more than `secs` seconds have elapsed by `now`.
+    pub fn is_older_than(&self, secs: i64, now: i64) -> bool {
+        self.age_from(now) > secs
+    }
+
+    /// Converts to a UTC datetime, returning `None` when out of range.
+    #[cfg(feature = "chrono")]
+    pub fn to_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        chrono::DateTime::from_timestamp(self.0, 0)
+    }
+}
+
+impl From<i64> for Timestamp {
+    fn from(value: i64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Timestamp> for i64 {
+    fn from(value: Timestamp) -> Self {
+        value.0
+    }
+}
+
+impl AgentRegistry {
+    /// Registration time as a [`Timestamp`].
+    pub fn created_at(&self) -> Timestamp {
+        Timestamp(self.created_at)
+    }
+
+    /// Last update time as a [`Timestamp`].
+    pub fn updated_at(&self) -> Timestamp {
+        Timestamp(self.updated_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_age_from() {
+        let ts = Timestamp(1_700_000_000);
+        assert_eq!(ts.age_from(1_700_000_060), 60);
+        assert_eq!(ts.age_from(1_700_000_000), 0);
+        assert_eq!(ts.age_from(1_699_999_990), -10);
+    }
+
+    #[test]
+    fn test_age_from_saturates() {
+        assert_eq!(Timestamp(i64::MIN).age_from(i64::MAX), i64::MAX);
+    }
+
+    #[test]
+    fn test_is_older_than() {
+        let ts = Timestamp(1_000);
+        assert!(ts.is_older_than(59, 1_060));
+        assert!(!ts.is_older_than(60, 1_060));
+        assert!(!ts.is_older_than(0, 900));
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(Timestamp(1) < Timestamp(2));
+        assert_eq!(i64::from(Timestamp::from(42)), 42);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_to_datetime() {
+        let dt = Timestamp(0).to_datetime().unwrap();
+        assert_eq!(dt.to_rfc3339(), "1970-01-01T00:00:00+00:00");
+        assert!(Timestamp(i64::MAX).to_datetime().is_none());
+    }
+}
@@ -0,0 +1,85 @@
+//! Push updates for a single agent via `accountSubscribe`.
+
+use std::thread::JoinHandle;
+
+use solana_account_decoder_client_types::{UiAccount, UiAccountEncoding};
+use solana_client::{
+    pubsub_client::{PubsubAccountClientSubscription, PubsubClient},
+    rpc_config::RpcAccountInfoConfig,
+    rpc_response::Response,
+};
+use solana_pubkey::Pubkey;
+
+use crate::{accounts::AgentRegistry, registry::find_agent_registry_pda};
+
+/// A running [`watch_agent`] subscription.
+///
+/// Dropping it leaves the subscription running; call [`AgentWatch::unsubscribe`]
+/// to stop receiving updates.
+pub struct AgentWatch {
+    subscription: PubsubAccountClientSubscription,
+    handle: JoinHandle<()>,
+}
+
+impl AgentWatch {
+    /// Closes the subscription and waits for the callback thread to finish.
+    pub fn unsubscribe(mut self) -> Result<(), std::io::Error> {
+        self.subscription
+            .shutdown()
+            .map_err(|_| std::io::Error::other("subscription panicked"))?;
+        self.handle
+            .join()
+            .map_err(|_| std::io::Error::other("callback panicked"))
+    }
+}
+
+/// Watches the registry of `authority`, calling `callback` with the freshly
+/// decoded [`AgentRegistry`] every time the account changes.
+///
+/// `pubsub_url` is the RPC node's websocket endpoint. Updates that do not
+/// decode as a registry (for example after deregistration) are skipped.
+pub fn watch_agent<F>(
+    pubsub_url: &str,
+    authority: &Pubkey,
+    callback: F,
+) -> Result<AgentWatch, std::io::Error>
+where
+    F: FnMut(AgentRegistry) + Send + 'static,
+{
+    let (address, _) = find_agent_registry_pda(authority);
+    let config = RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        ..Default::default()
+    };
+    let (subscription, receiver) =
+        PubsubClient::account_subscribe(pubsub_url, &address, Some(config))
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
+
+    let handle = std::thread::spawn(move || forward_agent_updates(receiver, callback));
+
+    Ok(AgentWatch {
+        subscription,
+        handle,
+    })
+}
+
+/// Decodes each account notification and passes the registry to `callback`.
+///
+/// This is the loop [`watch_agent`] runs on its background thread.
+pub fn forward_agent_updates<I, F>(updates: I, mut callback: F)
+where
+    I: IntoIterator<Item = Response<UiAccount>>,
+    F: FnMut(AgentRegistry),
+{
+    for update in updates {
+        if let Some(registry) = decode_account_update(&update.value) {
+            callback(registry);
+        }
+    }
+}
+
+/// Decodes the registry carried by an account notification.
+pub fn decode_account_update(account: &UiAccount) -> Option<AgentRegistry> {
+    let data = account.data.decode()?;
+    AgentRegistry::from_bytes(&data).ok()
+}
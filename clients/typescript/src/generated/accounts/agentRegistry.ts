@@ -6,22 +6,22 @@
  * @see https://github.com/codama-idl/codama
  */
 
-import { assertAccountExists, assertAccountsExist, combineCodec, decodeAccount, fetchEncodedAccount, fetchEncodedAccounts, getAddressDecoder, getAddressEncoder, getArrayDecoder, getArrayEncoder, getI64Decoder, getI64Encoder, getStructDecoder, getStructEncoder, getU8Decoder, getU8Encoder, type Account, type Address, type EncodedAccount, type FetchAccountConfig, type FetchAccountsConfig, type FixedSizeCodec, type FixedSizeDecoder, type FixedSizeEncoder, type MaybeAccount, type MaybeEncodedAccount } from '@solana/kit';
+import { assertAccountExists, assertAccountsExist, combineCodec, decodeAccount, fetchEncodedAccount, fetchEncodedAccounts, getAddressDecoder, getAddressEncoder, getArrayDecoder, getArrayEncoder, getI64Decoder, getI64Encoder, getStructDecoder, getStructEncoder, getU64Decoder, getU64Encoder, getU8Decoder, getU8Encoder, type Account, type Address, type EncodedAccount, type FetchAccountConfig, type FetchAccountsConfig, type FixedSizeCodec, type FixedSizeDecoder, type FixedSizeEncoder, type MaybeAccount, type MaybeEncodedAccount } from '@solana/kit';
 
 
 
-export type AgentRegistry = { discriminator: number; version: number; bump: number; dataVersion: number; padding: Array<number>; authority: Address; name: Array<number>; inboxUrl: Array<number>; createdAt: bigint; updatedAt: bigint;  };
+export type AgentRegistry = { discriminator: number; version: number; bump: number; dataVersion: number; urlCount: number; accepting: number; deleted: number; padding: Array<number>; authority: Address; name: Array<number>; inboxUrl: Array<number>; createdAt: bigint; updatedAt: bigint; createdSlot: bigint; inboxUrls: Array<Array<number>>; metadataUri: Array<number>; metadataHash: Array<number>; operator: Address; reserved: Array<number>; lastNonce: bigint; acceptedMimeTypes: Array<Array<number>>;  };
 
-export type AgentRegistryArgs = { discriminator: number; version: number; bump: number; dataVersion: number; padding: Array<number>; authority: Address; name: Array<number>; inboxUrl: Array<number>; createdAt: number | bigint; updatedAt: number | bigint;  };
+export type AgentRegistryArgs = { discriminator: number; version: number; bump: number; dataVersion: number; urlCount: number; accepting: number; deleted: number; padding: Array<number>; authority: Address; name: Array<number>; inboxUrl: Array<number>; createdAt: number | bigint; updatedAt: number | bigint; createdSlot: number | bigint; inboxUrls: Array<Array<number>>; metadataUri: Array<number>; metadataHash: Array<number>; operator: Address; reserved: Array<number>; lastNonce: number | bigint; acceptedMimeTypes: Array<Array<number>>;  };
 
 /** Gets the encoder for {@link AgentRegistryArgs} account data. */
 export function getAgentRegistryEncoder(): FixedSizeEncoder<AgentRegistryArgs> {
-    return getStructEncoder([['discriminator', getU8Encoder()], ['version', getU8Encoder()], ['bump', getU8Encoder()], ['dataVersion', getU8Encoder()], ['padding', getArrayEncoder(getU8Encoder(), { size: 6 })], ['authority', getAddressEncoder()], ['name', getArrayEncoder(getU8Encoder(), { size: 68 })], ['inboxUrl', getArrayEncoder(getU8Encoder(), { size: 260 })], ['createdAt', getI64Encoder()], ['updatedAt', getI64Encoder()]]);
+    return getStructEncoder([['discriminator', getU8Encoder()], ['version', getU8Encoder()], ['bump', getU8Encoder()], ['dataVersion', getU8Encoder()], ['urlCount', getU8Encoder()], ['accepting', getU8Encoder()], ['deleted', getU8Encoder()], ['padding', getArrayEncoder(getU8Encoder(), { size: 3 })], ['authority', getAddressEncoder()], ['name', getArrayEncoder(getU8Encoder(), { size: 68 })], ['inboxUrl', getArrayEncoder(getU8Encoder(), { size: 260 })], ['createdAt', getI64Encoder()], ['updatedAt', getI64Encoder()], ['createdSlot', getU64Encoder()], ['inboxUrls', getArrayEncoder(getArrayEncoder(getU8Encoder(), { size: 132 }), { size: 4 })], ['metadataUri', getArrayEncoder(getU8Encoder(), { size: 132 })], ['metadataHash', getArrayEncoder(getU8Encoder(), { size: 32 })], ['operator', getAddressEncoder()], ['reserved', getArrayEncoder(getU8Encoder(), { size: 4 })], ['lastNonce', getU64Encoder()], ['acceptedMimeTypes', getArrayEncoder(getArrayEncoder(getU8Encoder(), { size: 32 }), { size: 4 })]]);
 }
 
 /** Gets the decoder for {@link AgentRegistry} account data. */
 export function getAgentRegistryDecoder(): FixedSizeDecoder<AgentRegistry> {
-    return getStructDecoder([['discriminator', getU8Decoder()], ['version', getU8Decoder()], ['bump', getU8Decoder()], ['dataVersion', getU8Decoder()], ['padding', getArrayDecoder(getU8Decoder(), { size: 6 })], ['authority', getAddressDecoder()], ['name', getArrayDecoder(getU8Decoder(), { size: 68 })], ['inboxUrl', getArrayDecoder(getU8Decoder(), { size: 260 })], ['createdAt', getI64Decoder()], ['updatedAt', getI64Decoder()]]);
+    return getStructDecoder([['discriminator', getU8Decoder()], ['version', getU8Decoder()], ['bump', getU8Decoder()], ['dataVersion', getU8Decoder()], ['urlCount', getU8Decoder()], ['accepting', getU8Decoder()], ['deleted', getU8Decoder()], ['padding', getArrayDecoder(getU8Decoder(), { size: 3 })], ['authority', getAddressDecoder()], ['name', getArrayDecoder(getU8Decoder(), { size: 68 })], ['inboxUrl', getArrayDecoder(getU8Decoder(), { size: 260 })], ['createdAt', getI64Decoder()], ['updatedAt', getI64Decoder()], ['createdSlot', getU64Decoder()], ['inboxUrls', getArrayDecoder(getArrayDecoder(getU8Decoder(), { size: 132 }), { size: 4 })], ['metadataUri', getArrayDecoder(getU8Decoder(), { size: 132 })], ['metadataHash', getArrayDecoder(getU8Decoder(), { size: 32 })], ['operator', getAddressDecoder()], ['reserved', getArrayDecoder(getU8Decoder(), { size: 4 })], ['lastNonce', getU64Decoder()], ['acceptedMimeTypes', getArrayDecoder(getArrayDecoder(getU8Decoder(), { size: 32 }), { size: 4 })]]);
 }
 
 /** Gets the codec for {@link AgentRegistry} account data. */
@@ -19,12 +19,27 @@ export const AGENTMAIL_ERROR__REGISTRY_ALREADY_EXISTS = 0x6; // 6
 export const AGENTMAIL_ERROR__REGISTRY_DOES_NOT_EXIST = 0x7; // 7
 export const AGENTMAIL_ERROR__INVALID_ACCOUNT_SIZE = 0x8; // 8
 export const AGENTMAIL_ERROR__INVALID_ACCOUNT_DATA = 0x9; // 9
+export const AGENTMAIL_ERROR__INVALID_NAME_CHARACTERS = 0xa; // 10
+export const AGENTMAIL_ERROR__COUNTER_INSTRUCTIONS_REMOVED = 0xb; // 11
+export const AGENTMAIL_ERROR__BATCH_TOO_LARGE = 0xc; // 12
+export const AGENTMAIL_ERROR__ACCOUNT_TOO_SMALL = 0xd; // 13
+export const AGENTMAIL_ERROR__ACCOUNT_TOO_LARGE = 0xe; // 14
+export const AGENTMAIL_ERROR__TOO_MANY_INBOX_URLS = 0xf; // 15
+export const AGENTMAIL_ERROR__INVALID_DISCRIMINATOR = 0x10; // 16
+export const AGENTMAIL_ERROR__METADATA_URI_TOO_LONG = 0x11; // 17
+export const AGENTMAIL_ERROR__NO_CHANGES = 0x12; // 18
+export const AGENTMAIL_ERROR__NAME_TOO_SHORT = 0x13; // 19
+export const AGENTMAIL_ERROR__INBOX_URL_MISSING_PATH = 0x14; // 20
+export const AGENTMAIL_ERROR__CLOCK_UNAVAILABLE = 0x15; // 21
+export const AGENTMAIL_ERROR__STALE_NONCE = 0x16; // 22
+export const AGENTMAIL_ERROR__TOO_MANY_MIME_TYPES = 0x17; // 23
+export const AGENTMAIL_ERROR__INVALID_MIME_TYPE = 0x18; // 24
 
-export type AgentmailError = typeof AGENTMAIL_ERROR__INBOX_URL_TOO_LONG | typeof AGENTMAIL_ERROR__INVALID_ACCOUNT_DATA | typeof AGENTMAIL_ERROR__INVALID_ACCOUNT_SIZE | typeof AGENTMAIL_ERROR__INVALID_AUTHORITY | typeof AGENTMAIL_ERROR__INVALID_INBOX_URL_LENGTH | typeof AGENTMAIL_ERROR__INVALID_NAME_LENGTH | typeof AGENTMAIL_ERROR__INVALID_UTF8 | typeof AGENTMAIL_ERROR__NAME_TOO_LONG | typeof AGENTMAIL_ERROR__REGISTRY_ALREADY_EXISTS | typeof AGENTMAIL_ERROR__REGISTRY_DOES_NOT_EXIST;
+export type AgentmailError = typeof AGENTMAIL_ERROR__ACCOUNT_TOO_LARGE | typeof AGENTMAIL_ERROR__ACCOUNT_TOO_SMALL | typeof AGENTMAIL_ERROR__BATCH_TOO_LARGE | typeof AGENTMAIL_ERROR__CLOCK_UNAVAILABLE | typeof AGENTMAIL_ERROR__COUNTER_INSTRUCTIONS_REMOVED | typeof AGENTMAIL_ERROR__INBOX_URL_MISSING_PATH | typeof AGENTMAIL_ERROR__INBOX_URL_TOO_LONG | typeof AGENTMAIL_ERROR__INVALID_ACCOUNT_DATA | typeof AGENTMAIL_ERROR__INVALID_ACCOUNT_SIZE | typeof AGENTMAIL_ERROR__INVALID_AUTHORITY | typeof AGENTMAIL_ERROR__INVALID_DISCRIMINATOR | typeof AGENTMAIL_ERROR__INVALID_INBOX_URL_LENGTH | typeof AGENTMAIL_ERROR__INVALID_MIME_TYPE | typeof AGENTMAIL_ERROR__INVALID_NAME_CHARACTERS | typeof AGENTMAIL_ERROR__INVALID_NAME_LENGTH | typeof AGENTMAIL_ERROR__INVALID_UTF8 | typeof AGENTMAIL_ERROR__METADATA_URI_TOO_LONG | typeof AGENTMAIL_ERROR__NAME_TOO_LONG | typeof AGENTMAIL_ERROR__NAME_TOO_SHORT | typeof AGENTMAIL_ERROR__NO_CHANGES | typeof AGENTMAIL_ERROR__REGISTRY_ALREADY_EXISTS | typeof AGENTMAIL_ERROR__REGISTRY_DOES_NOT_EXIST | typeof AGENTMAIL_ERROR__STALE_NONCE | typeof AGENTMAIL_ERROR__TOO_MANY_INBOX_URLS | typeof AGENTMAIL_ERROR__TOO_MANY_MIME_TYPES;
 
 let agentmailErrorMessages: Record<AgentmailError, string> | undefined;
 if (process.env.NODE_ENV !== 'production') {
-  agentmailErrorMessages = { [AGENTMAIL_ERROR__INBOX_URL_TOO_LONG]: `Inbox URL is too long (max 256 bytes)`, [AGENTMAIL_ERROR__INVALID_ACCOUNT_DATA]: `Invalid account data format`, [AGENTMAIL_ERROR__INVALID_ACCOUNT_SIZE]: `Invalid account size for agent registry`, [AGENTMAIL_ERROR__INVALID_AUTHORITY]: `Authority invalid or does not match registry authority`, [AGENTMAIL_ERROR__INVALID_INBOX_URL_LENGTH]: `Invalid inbox URL length in stored data`, [AGENTMAIL_ERROR__INVALID_NAME_LENGTH]: `Invalid name length in stored data`, [AGENTMAIL_ERROR__INVALID_UTF8]: `Invalid UTF-8 data in string fields`, [AGENTMAIL_ERROR__NAME_TOO_LONG]: `Agent name is too long (max 64 bytes)`, [AGENTMAIL_ERROR__REGISTRY_ALREADY_EXISTS]: `Agent registry already exists for this authority`, [AGENTMAIL_ERROR__REGISTRY_DOES_NOT_EXIST]: `Agent registry does not exist for this authority` };
+  agentmailErrorMessages = { [AGENTMAIL_ERROR__ACCOUNT_TOO_LARGE]: `Agent registry account is larger than expected`, [AGENTMAIL_ERROR__ACCOUNT_TOO_SMALL]: `Agent registry account is smaller than expected`, [AGENTMAIL_ERROR__BATCH_TOO_LARGE]: `Batch exceeds the maximum number of entries`, [AGENTMAIL_ERROR__CLOCK_UNAVAILABLE]: `Clock sysvar is unavailable`, [AGENTMAIL_ERROR__COUNTER_INSTRUCTIONS_REMOVED]: `Counter instructions were removed from this program`, [AGENTMAIL_ERROR__INBOX_URL_MISSING_PATH]: `Inbox URL has no path after the host`, [AGENTMAIL_ERROR__INBOX_URL_TOO_LONG]: `Inbox URL is too long (max 256 bytes)`, [AGENTMAIL_ERROR__INVALID_ACCOUNT_DATA]: `Invalid account data format`, [AGENTMAIL_ERROR__INVALID_ACCOUNT_SIZE]: `Invalid account size for agent registry`, [AGENTMAIL_ERROR__INVALID_AUTHORITY]: `Authority invalid or does not match registry authority`, [AGENTMAIL_ERROR__INVALID_DISCRIMINATOR]: `Account discriminator does not match the expected account type`, [AGENTMAIL_ERROR__INVALID_INBOX_URL_LENGTH]: `Invalid inbox URL length in stored data`, [AGENTMAIL_ERROR__INVALID_MIME_TYPE]: `MIME type must be 1-32 printable ASCII bytes with a slash`, [AGENTMAIL_ERROR__INVALID_NAME_CHARACTERS]: `Agent name contains control characters`, [AGENTMAIL_ERROR__INVALID_NAME_LENGTH]: `Invalid name length in stored data`, [AGENTMAIL_ERROR__INVALID_UTF8]: `Invalid UTF-8 data in string fields`, [AGENTMAIL_ERROR__METADATA_URI_TOO_LONG]: `Metadata URI is too long (max 128 bytes)`, [AGENTMAIL_ERROR__NAME_TOO_LONG]: `Agent name is too long (max 64 bytes)`, [AGENTMAIL_ERROR__NAME_TOO_SHORT]: `Agent name is too short (min 2 bytes)`, [AGENTMAIL_ERROR__NO_CHANGES]: `Update would not change the registry`, [AGENTMAIL_ERROR__REGISTRY_ALREADY_EXISTS]: `Agent registry already exists for this authority`, [AGENTMAIL_ERROR__REGISTRY_DOES_NOT_EXIST]: `Agent registry does not exist for this authority`, [AGENTMAIL_ERROR__STALE_NONCE]: `Update nonce is not greater than the last accepted nonce`, [AGENTMAIL_ERROR__TOO_MANY_INBOX_URLS]: `Too many inbox URLs (max 4 additional)`, [AGENTMAIL_ERROR__TOO_MANY_MIME_TYPES]: `Too many accepted MIME types` };
 }
 
 export function getAgentmailErrorMessage(code: AgentmailError): string {
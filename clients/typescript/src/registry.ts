@@ -32,6 +32,9 @@ export async function findAgentRegistryPda(
   return [pda, bump];
 }
 
+/** All-zero address stored when an agent has no operator */
+const NO_OPERATOR = '11111111111111111111111111111111' as Address;
+
 /**
  * Represents an agent's registry information with parsed string fields.
  */
@@ -42,8 +45,21 @@ export type ParsedAgentRegistry = {
   authority: Address;
   name: string;
   inboxUrl: string;
+  /** Additional endpoints, tried in order after inboxUrl */
+  inboxUrls: string[];
+  /** Whether the agent accepts new conversations; check before routing */
+  accepting: boolean;
+  /** Whether the agent soft-deregistered; treat it as absent when true */
+  deleted: boolean;
+  /** Off-chain metadata URI, empty when no commitment is stored */
+  metadataUri: string;
+  /** Hash committing to the metadata document at metadataUri */
+  metadataHash: Uint8Array;
+  /** Fleet operator, or null when the agent has none */
+  operator: Address | null;
   createdAt: bigint;
   updatedAt: bigint;
+  createdSlot: bigint;
 };
 
 /**
@@ -58,6 +74,15 @@ export function parseAgentRegistry(registry: AgentRegistry): ParsedAgentRegistry
   const inboxUrlLength = new DataView(new Uint8Array(inboxUrlBytes.slice(0, 4)).buffer).getUint32(0, true);
   const inboxUrl = new TextDecoder().decode(new Uint8Array(inboxUrlBytes.slice(4, 4 + inboxUrlLength)));
 
+  const inboxUrls = registry.inboxUrls.slice(0, registry.urlCount).map((urlBytes) => {
+    const urlLength = new DataView(new Uint8Array(urlBytes.slice(0, 4)).buffer).getUint32(0, true);
+    return new TextDecoder().decode(new Uint8Array(urlBytes.slice(4, 4 + urlLength)));
+  });
+
+  const metadataUriBytes = registry.metadataUri;
+  const metadataUriLength = new DataView(new Uint8Array(metadataUriBytes.slice(0, 4)).buffer).getUint32(0, true);
+  const metadataUri = new TextDecoder().decode(new Uint8Array(metadataUriBytes.slice(4, 4 + metadataUriLength)));
+
   return {
     discriminator: registry.discriminator,
     version: registry.version,
@@ -65,8 +90,15 @@ export function parseAgentRegistry(registry: AgentRegistry): ParsedAgentRegistry
     authority: registry.authority,
     name,
     inboxUrl,
+    inboxUrls,
+    accepting: registry.dataVersion < 5 || registry.accepting !== 0,
+    deleted: registry.deleted !== 0,
+    metadataUri,
+    metadataHash: new Uint8Array(registry.metadataHash),
+    operator: registry.operator === NO_OPERATOR ? null : registry.operator,
     createdAt: registry.createdAt,
     updatedAt: registry.updatedAt,
+    createdSlot: registry.createdSlot,
   };
 }
 
@@ -0,0 +1,68 @@
+//! AgentRegistry serialization benchmarks
+//!
+//! Measures the hot paths every instruction goes through: `to_bytes` (which
+//! allocates a `Vec` per write), zero-copy `from_bytes`, and the in-place
+//! update round trip UpdateAgent performs. Run with:
+//!
+//! ```bash
+//! cargo bench -p agentmail --bench agent_registry
+//! ```
+//!
+//! Criterion keeps the previous run under `target/criterion`, so rerunning on
+//! the same machine reports regressions against it. Baseline numbers depend
+//! on the host; record them together with the machine they came from.
+
+use agentmail::{
+    state::AgentRegistry,
+    traits::{AccountDeserialize, AccountSerialize},
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use pinocchio::Address;
+
+fn registry() -> AgentRegistry {
+    AgentRegistry::new(
+        255,
+        Address::new_from_array([1u8; 32]),
+        "bench-agent",
+        "https://bench.example.com/inbox",
+        1707523200,
+        1,
+    )
+    .unwrap()
+}
+
+fn bench_to_bytes(c: &mut Criterion) {
+    let registry = registry();
+    c.bench_function("agent_registry/to_bytes", |b| {
+        b.iter(|| black_box(&registry).to_bytes())
+    });
+}
+
+fn bench_from_bytes(c: &mut Criterion) {
+    let bytes = registry().to_bytes();
+    c.bench_function("agent_registry/from_bytes", |b| {
+        b.iter(|| AgentRegistry::from_bytes(black_box(&bytes)).unwrap().bump)
+    });
+}
+
+fn bench_update_round_trip(c: &mut Criterion) {
+    let mut bytes = registry().to_bytes();
+    c.bench_function("agent_registry/update_round_trip", |b| {
+        b.iter(|| {
+            let registry = AgentRegistry::from_bytes_mut(black_box(&mut bytes)).unwrap();
+            registry.set_name(black_box("renamed-agent")).unwrap();
+            registry
+                .set_inbox_url(black_box("https://renamed.example.com/inbox"))
+                .unwrap();
+            registry.touch(black_box(1707523201));
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_to_bytes,
+    bench_from_bytes,
+    bench_update_round_trip
+);
+criterion_main!(benches);
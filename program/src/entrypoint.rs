@@ -1,7 +1,13 @@
 use pinocchio::{account::AccountView, entrypoint, error::ProgramError, Address, ProgramResult};
 
 use crate::{
-    instructions::{process_deregister_agent, process_register_agent, process_update_agent},
+    instructions::{
+        process_allocate_registry, process_check_registry_batch, process_create_rent_vault,
+        process_deregister_agent, process_initialize_registry, process_migrate_registry,
+        process_reactivate_agent, process_register_agent, process_set_accepting,
+        process_set_operator, process_soft_deregister_agent, process_update_agent,
+        process_update_agent_batch, process_withdraw_rent_vault,
+    },
     traits::AgentMailInstructionDiscriminators,
 };
 
@@ -28,5 +34,53 @@ pub fn process_instruction(
         AgentMailInstructionDiscriminators::DeregisterAgent => {
             process_deregister_agent(program_id, accounts, instruction_data)
         }
+        AgentMailInstructionDiscriminators::UpdateAgentBatch => {
+            process_update_agent_batch(program_id, accounts, instruction_data)
+        }
+        AgentMailInstructionDiscriminators::SetAccepting => {
+            process_set_accepting(program_id, accounts, instruction_data)
+        }
+        AgentMailInstructionDiscriminators::MigrateRegistry => {
+            process_migrate_registry(program_id, accounts, instruction_data)
+        }
+        AgentMailInstructionDiscriminators::SetOperator => {
+            process_set_operator(program_id, accounts, instruction_data)
+        }
+        AgentMailInstructionDiscriminators::CheckRegistryBatch => {
+            process_check_registry_batch(program_id, accounts, instruction_data)
+        }
+        AgentMailInstructionDiscriminators::SoftDeregisterAgent => {
+            process_soft_deregister_agent(program_id, accounts, instruction_data)
+        }
+        AgentMailInstructionDiscriminators::ReactivateAgent => {
+            process_reactivate_agent(program_id, accounts, instruction_data)
+        }
+        AgentMailInstructionDiscriminators::AllocateRegistry => {
+            process_allocate_registry(program_id, accounts, instruction_data)
+        }
+        AgentMailInstructionDiscriminators::InitializeRegistry => {
+            process_initialize_registry(program_id, accounts, instruction_data)
+        }
+        AgentMailInstructionDiscriminators::CreateRentVault => {
+            process_create_rent_vault(program_id, accounts, instruction_data)
+        }
+        AgentMailInstructionDiscriminators::WithdrawRentVault => {
+            process_withdraw_rent_vault(program_id, accounts, instruction_data)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::AgentMailProgramError;
+
+    #[test]
+    fn test_counter_discriminator_returns_removed_error() {
+        let result = process_instruction(&crate::ID, &[], &[1]);
+        assert_eq!(
+            result,
+            Err(AgentMailProgramError::CounterInstructionsRemoved.into())
+        );
     }
 }
@@ -38,16 +38,116 @@ pub enum AgentMailProgramError {
     RegistryDoesNotExist,
 
     /// (8) Invalid account size for agent registry
+    ///
+    /// Superseded by `AccountTooSmall` / `AccountTooLarge`; kept so later error codes stay stable.
     #[error("Invalid account size for agent registry")]
     InvalidAccountSize,
 
     /// (9) Invalid account data format
     #[error("Invalid account data format")]
     InvalidAccountData,
+
+    /// (10) Agent name contains control characters
+    #[error("Agent name contains control characters")]
+    InvalidNameCharacters,
+
+    /// (11) Counter instructions were removed from this program
+    #[error("Counter instructions were removed from this program")]
+    CounterInstructionsRemoved,
+
+    /// (12) Batch exceeds the maximum number of entries
+    #[error("Batch exceeds the maximum number of entries")]
+    BatchTooLarge,
+
+    /// (13) Agent registry account is smaller than expected
+    #[error("Agent registry account is smaller than expected")]
+    AccountTooSmall,
+
+    /// (14) Agent registry account is larger than expected
+    #[error("Agent registry account is larger than expected")]
+    AccountTooLarge,
+
+    /// (15) Too many inbox URLs (max 4 additional)
+    #[error("Too many inbox URLs (max 4 additional)")]
+    TooManyInboxUrls,
+
+    /// (16) Account discriminator does not match the expected account type
+    #[error("Account discriminator does not match the expected account type")]
+    InvalidDiscriminator,
+
+    /// (17) Metadata URI is too long (max 128 bytes)
+    #[error("Metadata URI is too long (max 128 bytes)")]
+    MetadataUriTooLong,
+
+    /// (18) Update would not change the registry
+    #[error("Update would not change the registry")]
+    NoChanges,
+
+    /// (19) Agent name is too short (min 2 bytes)
+    #[error("Agent name is too short (min 2 bytes)")]
+    NameTooShort,
+
+    /// (20) Inbox URL has no path after the host
+    #[error("Inbox URL has no path after the host")]
+    InboxUrlMissingPath,
+
+    /// (21) Clock sysvar is unavailable
+    #[error("Clock sysvar is unavailable")]
+    ClockUnavailable,
+
+    /// (22) Update nonce is not greater than the last accepted nonce
+    #[error("Update nonce is not greater than the last accepted nonce")]
+    StaleNonce,
+
+    /// (23) Too many accepted MIME types
+    #[error("Too many accepted MIME types")]
+    TooManyMimeTypes,
+
+    /// (24) MIME type must be 1-32 printable ASCII bytes with a slash
+    #[error("MIME type must be 1-32 printable ASCII bytes with a slash")]
+    InvalidMimeType,
+}
+
+impl AgentMailProgramError {
+    /// Short, stable identifier for on-chain logging
+    ///
+    /// Returns a static string so it can be logged without `alloc` formatting.
+    pub const fn code_str(&self) -> &'static str {
+        match self {
+            Self::InvalidAuthority => "invalid_authority",
+            Self::NameTooLong => "name_too_long",
+            Self::InboxUrlTooLong => "inbox_url_too_long",
+            Self::InvalidNameLength => "invalid_name_length",
+            Self::InvalidInboxUrlLength => "invalid_inbox_url_length",
+            Self::InvalidUtf8 => "invalid_utf8",
+            Self::RegistryAlreadyExists => "registry_already_exists",
+            Self::RegistryDoesNotExist => "registry_does_not_exist",
+            Self::InvalidAccountSize => "invalid_account_size",
+            Self::InvalidAccountData => "invalid_account_data",
+            Self::InvalidNameCharacters => "invalid_name_characters",
+            Self::CounterInstructionsRemoved => "counter_instructions_removed",
+            Self::BatchTooLarge => "batch_too_large",
+            Self::AccountTooSmall => "account_too_small",
+            Self::AccountTooLarge => "account_too_large",
+            Self::TooManyInboxUrls => "too_many_inbox_urls",
+            Self::InvalidDiscriminator => "invalid_discriminator",
+            Self::MetadataUriTooLong => "metadata_uri_too_long",
+            Self::NoChanges => "no_changes",
+            Self::NameTooShort => "name_too_short",
+            Self::InboxUrlMissingPath => "inbox_url_missing_path",
+            Self::ClockUnavailable => "clock_unavailable",
+            Self::StaleNonce => "stale_nonce",
+            Self::TooManyMimeTypes => "too_many_mime_types",
+            Self::InvalidMimeType => "invalid_mime_type",
+        }
+    }
 }
 
 impl From<AgentMailProgramError> for ProgramError {
     fn from(e: AgentMailProgramError) -> Self {
+        #[cfg(feature = "debug-logging")]
+        pinocchio_log::log!("AgentMail error: {}", e.code_str());
+
         ProgramError::Custom(100 + e as u32) // Offset to avoid conflicts
     }
 }
@@ -67,4 +167,20 @@ mod tests {
         let error: ProgramError = AgentMailProgramError::RegistryAlreadyExists.into();
         assert_eq!(error, ProgramError::Custom(106));
     }
+
+    #[test]
+    fn test_agentmail_error_code_str() {
+        assert_eq!(
+            AgentMailProgramError::NameTooLong.code_str(),
+            "name_too_long"
+        );
+        assert_eq!(
+            AgentMailProgramError::InvalidUtf8.code_str(),
+            "invalid_utf8"
+        );
+        assert_eq!(
+            AgentMailProgramError::MetadataUriTooLong.code_str(),
+            "metadata_uri_too_long"
+        );
+    }
 }
@@ -0,0 +1,57 @@
+use pinocchio::{account::AccountView, error::ProgramError};
+
+use crate::{assert_roles, traits::InstructionAccounts, utils::verify_distinct};
+
+/// Accounts for the AllocateRegistry instruction
+///
+/// # Account Layout
+/// 0. `[signer, writable]` payer - Pays for account creation
+/// 1. `[]` agent_authority - Authority the registry is derived from; does not sign
+/// 2. `[writable]` agent_registry - Agent registry PDA to be allocated
+/// 3. `[]` system_program - System program for account creation
+/// 4. `[]` program - Current program
+#[derive(Debug, PartialEq)]
+pub struct AllocateRegistryAccounts<'a> {
+    pub payer: &'a AccountView,
+    pub agent_authority: &'a AccountView,
+    pub agent_registry: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub program: &'a AccountView,
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for AllocateRegistryAccounts<'a> {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [payer, agent_authority, agent_registry, system_program, program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Payer funds the registry, so it cannot be the registry itself
+        verify_distinct(payer, agent_registry)?;
+
+        assert_roles!(
+            payer: [signer, writable],
+            agent_registry: [writable],
+            system_program: [system_program],
+            program: [current_program],
+        );
+
+        // An empty registry will be created, so it must still be system-owned.
+        // An existing one is rejected by the processor
+        if agent_registry.data_len() == 0 {
+            assert_roles!(agent_registry: [system_owned]);
+        }
+
+        Ok(Self {
+            payer,
+            agent_authority,
+            agent_registry,
+            system_program,
+            program,
+        })
+    }
+}
+
+impl<'a> InstructionAccounts<'a> for AllocateRegistryAccounts<'a> {}
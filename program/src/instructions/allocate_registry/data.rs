@@ -0,0 +1,48 @@
+use pinocchio::error::ProgramError;
+
+use crate::{require_len, traits::InstructionData};
+
+/// Instruction data for AllocateRegistry
+///
+/// # Layout
+/// * `bump` (u8) - Canonical bump for the agent registry PDA
+#[derive(Debug, PartialEq)]
+pub struct AllocateRegistryData {
+    pub bump: u8,
+}
+
+impl<'a> TryFrom<&'a [u8]> for AllocateRegistryData {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        require_len!(data, Self::LEN);
+
+        Ok(Self { bump: data[0] })
+    }
+}
+
+impl<'a> InstructionData<'a> for AllocateRegistryData {
+    const LEN: usize = 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocate_registry_data_valid() {
+        assert_eq!(
+            AllocateRegistryData::try_from(&[254u8][..]),
+            Ok(AllocateRegistryData { bump: 254 })
+        );
+    }
+
+    #[test]
+    fn test_allocate_registry_data_empty() {
+        assert_eq!(
+            AllocateRegistryData::try_from(&[][..]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
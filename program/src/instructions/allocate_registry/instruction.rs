@@ -0,0 +1,31 @@
+use super::{AllocateRegistryAccounts, AllocateRegistryData};
+use crate::{impl_instruction, traits::Instruction};
+
+/// AllocateRegistry instruction combining accounts and data
+///
+/// First half of the two-phase registration; see InitializeRegistry.
+pub struct AllocateRegistry<'a> {
+    pub accounts: AllocateRegistryAccounts<'a>,
+    pub data: AllocateRegistryData,
+}
+
+impl_instruction!(
+    AllocateRegistry,
+    AllocateRegistryAccounts,
+    AllocateRegistryData
+);
+
+impl<'a> Instruction<'a> for AllocateRegistry<'a> {
+    type Accounts = AllocateRegistryAccounts<'a>;
+    type Data = AllocateRegistryData;
+
+    #[inline(always)]
+    fn accounts(&self) -> &Self::Accounts {
+        &self.accounts
+    }
+
+    #[inline(always)]
+    fn data(&self) -> &Self::Data {
+        &self.data
+    }
+}
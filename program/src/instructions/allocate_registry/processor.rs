@@ -0,0 +1,54 @@
+use pinocchio::{account::AccountView, cpi::Seed, error::ProgramError, Address, ProgramResult};
+
+use crate::{
+    errors::AgentMailProgramError,
+    instructions::AllocateRegistry,
+    state::AgentRegistry,
+    traits::{AccountSize, Instruction, PdaSeeds},
+    utils::{create_pda_account, verify_registrable_authority},
+};
+
+/// Processes the AllocateRegistry instruction.
+///
+/// Creates the AgentRegistry PDA for `agent_authority` at the current
+/// layout size, funded by `payer` and owned by this program, with zeroed
+/// data. The authority does not sign here; it writes the registry later
+/// with InitializeRegistry, and no other instruction accepts the account
+/// until then.
+pub fn process_allocate_registry(
+    program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let ix = AllocateRegistry::parse(instruction_data, accounts)?;
+    let authority = ix.accounts.agent_authority.address();
+
+    // The authority does not sign here, so reject reserved addresses such as
+    // the system program explicitly
+    verify_registrable_authority(authority)?;
+
+    // Only the canonical PDA can be initialized, so reject any other bump
+    // before the payer's lamports are spent on it
+    let (derived, bump) =
+        Address::find_program_address(&[AgentRegistry::PREFIX, authority.as_ref()], program_id);
+    if bump != ix.data.bump || ix.accounts.agent_registry.address() != &derived {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if ix.accounts.agent_registry.data_len() != 0 {
+        return Err(AgentMailProgramError::RegistryAlreadyExists.into());
+    }
+
+    let bump_seed = [bump];
+    create_pda_account(
+        ix.accounts.payer,
+        AgentRegistry::LEN,
+        program_id,
+        ix.accounts.agent_registry,
+        [
+            Seed::from(AgentRegistry::PREFIX),
+            Seed::from(authority.as_ref()),
+            Seed::from(bump_seed.as_slice()),
+        ],
+    )
+}
@@ -0,0 +1,41 @@
+use pinocchio::{account::AccountView, error::ProgramError};
+
+use crate::{assert_roles, traits::InstructionAccounts};
+
+/// Accounts for the CheckRegistryBatch instruction
+///
+/// # Account Layout
+/// 0. `[]` program - Current program
+///
+/// Followed by one read-only account per expected authority:
+/// * `[]` agent_registry - Account expected to be that authority's registry
+#[derive(Debug, PartialEq)]
+pub struct CheckRegistryBatchAccounts<'a> {
+    pub program: &'a AccountView,
+    pub registries: &'a [AccountView],
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for CheckRegistryBatchAccounts<'a> {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [program, registries @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Verify this is our program
+        assert_roles!(program: [current_program]);
+
+        if registries.is_empty() {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        Ok(Self {
+            program,
+            registries,
+        })
+    }
+}
+
+impl<'a> InstructionAccounts<'a> for CheckRegistryBatchAccounts<'a> {}
@@ -0,0 +1,140 @@
+use alloc::vec::Vec;
+use pinocchio::{error::ProgramError, Address};
+
+use crate::{errors::AgentMailProgramError, require_len, traits::InstructionData};
+
+/// Instruction data for CheckRegistryBatch
+///
+/// # Layout
+/// * `authority_count` (u32, LE) - Number of expected authorities
+/// * `authorities` (32 bytes each) - Expected authority of each registry
+///   account, in account order
+#[derive(Debug, PartialEq)]
+pub struct CheckRegistryBatchData {
+    pub authorities: Vec<Address>,
+}
+
+impl CheckRegistryBatchData {
+    /// Maximum number of registries in a single check, one bit each in the
+    /// u32 result mask.
+    pub const MAX_BATCH_SIZE: usize = 32;
+
+    /// Maximum total instruction data length (1028 bytes): the count plus
+    /// `MAX_BATCH_SIZE` authorities. Longer data is rejected with
+    /// `InvalidInstructionData` before anything is read.
+    pub const MAX_DATA_LEN: usize = 4 + Self::MAX_BATCH_SIZE * 32;
+}
+
+impl<'a> TryFrom<&'a [u8]> for CheckRegistryBatchData {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() > Self::MAX_DATA_LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // Read authority count
+        require_len!(data, 4);
+        let count = u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as usize;
+
+        if count == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if count > Self::MAX_BATCH_SIZE {
+            return Err(AgentMailProgramError::BatchTooLarge.into());
+        }
+
+        // Read each authority back to back
+        require_len!(data, 4 + count * 32);
+        let authorities = data[4..4 + count * 32]
+            .chunks_exact(32)
+            .map(|bytes| {
+                let mut authority = [0u8; 32];
+                authority.copy_from_slice(bytes);
+                Address::new_from_array(authority)
+            })
+            .collect();
+
+        Ok(Self { authorities })
+    }
+}
+
+impl<'a> InstructionData<'a> for CheckRegistryBatchData {
+    const LEN: usize = 0; // Variable length, so we override validation
+
+    /// The count and a single authority
+    const MIN_LEN: usize = 4 + 32;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn create_test_data(authorities: &[[u8; 32]]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&(authorities.len() as u32).to_le_bytes());
+        for authority in authorities {
+            data.extend_from_slice(authority);
+        }
+        data
+    }
+
+    #[test]
+    fn test_check_registry_batch_data_valid() {
+        let data = create_test_data(&[[1u8; 32], [2u8; 32]]);
+        let batch = CheckRegistryBatchData::try_from(&data[..]).unwrap();
+        assert_eq!(
+            batch.authorities,
+            vec![
+                Address::new_from_array([1u8; 32]),
+                Address::new_from_array([2u8; 32])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_registry_batch_data_empty() {
+        let data = create_test_data(&[]);
+        let result = CheckRegistryBatchData::try_from(&data[..]);
+        assert_eq!(result, Err(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn test_check_registry_batch_data_too_large() {
+        let data = create_test_data(&[[1u8; 32]; 33]);
+        let result = CheckRegistryBatchData::try_from(&data[..]);
+        assert_eq!(result, Err(AgentMailProgramError::BatchTooLarge.into()));
+    }
+
+    #[test]
+    fn test_check_registry_batch_data_oversized() {
+        let mut data = create_test_data(&[[1u8; 32]; CheckRegistryBatchData::MAX_BATCH_SIZE]);
+        assert_eq!(data.len(), CheckRegistryBatchData::MAX_DATA_LEN);
+        assert!(CheckRegistryBatchData::try_from(&data[..]).is_ok());
+
+        // Trailing bytes past the cap are rejected even with a valid count
+        data.extend_from_slice(&[0u8; 4096]);
+        let result = CheckRegistryBatchData::try_from(&data[..]);
+        assert_eq!(result, Err(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn test_check_registry_batch_data_min_len() {
+        let data = create_test_data(&[[1u8; 32]]);
+        assert_eq!(data.len(), CheckRegistryBatchData::MIN_LEN);
+        assert!(CheckRegistryBatchData::try_from(&data[..]).is_ok());
+
+        let result = CheckRegistryBatchData::try_from(&data[..data.len() - 1]);
+        assert_eq!(result, Err(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn test_check_registry_batch_data_truncated() {
+        let mut data = create_test_data(&[[1u8; 32]]);
+        data.pop();
+        let result = CheckRegistryBatchData::try_from(&data[..]);
+        assert_eq!(result, Err(ProgramError::InvalidInstructionData));
+    }
+}
@@ -0,0 +1,31 @@
+use crate::traits::Instruction;
+
+use super::{CheckRegistryBatchAccounts, CheckRegistryBatchData};
+
+/// CheckRegistryBatch instruction
+///
+/// Checks several AgentRegistry PDAs against their expected authorities
+/// and returns a bitmask of which passed, for CPI callers.
+pub struct CheckRegistryBatch<'a> {
+    pub accounts: CheckRegistryBatchAccounts<'a>,
+    pub data: CheckRegistryBatchData,
+}
+
+impl<'a> Instruction<'a> for CheckRegistryBatch<'a> {
+    type Accounts = CheckRegistryBatchAccounts<'a>;
+    type Data = CheckRegistryBatchData;
+
+    fn accounts(&self) -> &Self::Accounts {
+        &self.accounts
+    }
+
+    fn data(&self) -> &Self::Data {
+        &self.data
+    }
+}
+
+impl<'a> From<(CheckRegistryBatchAccounts<'a>, CheckRegistryBatchData)> for CheckRegistryBatch<'a> {
+    fn from((accounts, data): (CheckRegistryBatchAccounts<'a>, CheckRegistryBatchData)) -> Self {
+        Self { accounts, data }
+    }
+}
@@ -0,0 +1,72 @@
+use pinocchio::{
+    account::AccountView, cpi::set_return_data, error::ProgramError, Address, ProgramResult,
+};
+
+use crate::{
+    instructions::CheckRegistryBatch,
+    state::AgentRegistry,
+    traits::{Discriminator, Instruction},
+};
+
+/// Processes the CheckRegistryBatch instruction.
+///
+/// Checks each registry account against the expected authority at the same
+/// index and sets the return data to a u32 (LE) bitmask with bit `i` set
+/// when registry `i` passed. A failed check clears its bit instead of
+/// failing the instruction, so a CPI caller can decide per recipient.
+pub fn process_check_registry_batch(
+    program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let ix = CheckRegistryBatch::parse(instruction_data, accounts)?;
+
+    // Every expected authority needs exactly one registry account
+    if ix.data.authorities.len() != ix.accounts.registries.len() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut mask = 0u32;
+    for (i, (registry, authority)) in ix
+        .accounts
+        .registries
+        .iter()
+        .zip(&ix.data.authorities)
+        .enumerate()
+    {
+        if is_registry_for(registry, authority, program_id) {
+            mask |= 1 << i;
+        }
+    }
+
+    set_return_data(&mask.to_le_bytes());
+
+    Ok(())
+}
+
+/// Whether `registry` is a live registry owned by this program for `authority`.
+///
+/// Reads only the stable prefix, so registries on older layouts still pass.
+/// Soft-deregistered agents fail, since senders treat them as absent.
+#[inline(always)]
+fn is_registry_for(registry: &AccountView, authority: &Address, program_id: &Address) -> bool {
+    if !registry.owned_by(program_id) {
+        return false;
+    }
+    let Ok(data) = registry.try_borrow() else {
+        return false;
+    };
+
+    let offset = AgentRegistry::AUTHORITY_OFFSET;
+    data.len() >= offset + 32
+        && data.starts_with(AgentRegistry::DISCRIMINATOR)
+        && data[AgentRegistry::DELETED_OFFSET] == 0
+        && &data[offset..offset + 32] == authority.as_ref()
+        && AgentRegistry::validate_pda_for_authority(
+            registry.address(),
+            authority,
+            program_id,
+            Some(data[2]),
+        )
+        .is_ok()
+}
@@ -0,0 +1,53 @@
+use pinocchio::{account::AccountView, error::ProgramError};
+
+use crate::{assert_roles, traits::InstructionAccounts, utils::verify_distinct};
+
+/// Accounts for the CreateRentVault instruction
+///
+/// # Account Layout
+/// 0. `[signer, writable]` payer - Pays for account creation
+/// 1. `[signer]` vault_authority - Key the vault is derived from; the only one that can withdraw
+/// 2. `[writable]` rent_vault - Rent vault PDA to be created
+/// 3. `[]` system_program - System program for account creation
+/// 4. `[]` program - Current program
+#[derive(Debug, PartialEq)]
+pub struct CreateRentVaultAccounts<'a> {
+    pub payer: &'a AccountView,
+    pub vault_authority: &'a AccountView,
+    pub rent_vault: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub program: &'a AccountView,
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for CreateRentVaultAccounts<'a> {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [payer, vault_authority, rent_vault, system_program, program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Payer funds the vault, so it cannot be the vault itself
+        verify_distinct(payer, rent_vault)?;
+
+        // The vault is created, so it must still be system-owned
+        assert_roles!(
+            payer: [signer, writable],
+            vault_authority: [signer],
+            rent_vault: [writable, system_owned],
+            system_program: [system_program],
+            program: [current_program],
+        );
+
+        Ok(Self {
+            payer,
+            vault_authority,
+            rent_vault,
+            system_program,
+            program,
+        })
+    }
+}
+
+impl<'a> InstructionAccounts<'a> for CreateRentVaultAccounts<'a> {}
@@ -0,0 +1,48 @@
+use pinocchio::error::ProgramError;
+
+use crate::{require_len, traits::InstructionData};
+
+/// Instruction data for CreateRentVault
+///
+/// # Layout
+/// * `bump` (u8) - Canonical bump for the rent vault PDA
+#[derive(Debug, PartialEq)]
+pub struct CreateRentVaultData {
+    pub bump: u8,
+}
+
+impl<'a> TryFrom<&'a [u8]> for CreateRentVaultData {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        require_len!(data, Self::LEN);
+
+        Ok(Self { bump: data[0] })
+    }
+}
+
+impl<'a> InstructionData<'a> for CreateRentVaultData {
+    const LEN: usize = 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_rent_vault_data_valid() {
+        assert_eq!(
+            CreateRentVaultData::try_from(&[254u8][..]),
+            Ok(CreateRentVaultData { bump: 254 })
+        );
+    }
+
+    #[test]
+    fn test_create_rent_vault_data_empty() {
+        assert_eq!(
+            CreateRentVaultData::try_from(&[][..]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use super::{CreateRentVaultAccounts, CreateRentVaultData};
+use crate::{impl_instruction, traits::Instruction};
+
+/// CreateRentVault instruction combining accounts and data
+pub struct CreateRentVault<'a> {
+    pub accounts: CreateRentVaultAccounts<'a>,
+    pub data: CreateRentVaultData,
+}
+
+impl_instruction!(
+    CreateRentVault,
+    CreateRentVaultAccounts,
+    CreateRentVaultData
+);
+
+impl<'a> Instruction<'a> for CreateRentVault<'a> {
+    type Accounts = CreateRentVaultAccounts<'a>;
+    type Data = CreateRentVaultData;
+
+    #[inline(always)]
+    fn accounts(&self) -> &Self::Accounts {
+        &self.accounts
+    }
+
+    #[inline(always)]
+    fn data(&self) -> &Self::Data {
+        &self.data
+    }
+}
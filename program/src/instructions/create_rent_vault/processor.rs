@@ -0,0 +1,45 @@
+use alloc::vec::Vec;
+use pinocchio::{account::AccountView, cpi::Seed, error::ProgramError, Address, ProgramResult};
+
+use crate::{
+    instructions::CreateRentVault,
+    state::RentVault,
+    traits::{AccountSerialize, AccountSize, Instruction, PdaSeeds},
+    utils::create_pda_account,
+};
+
+/// Processes the CreateRentVault instruction.
+///
+/// Creates the RentVault PDA for `vault_authority`, funded by `payer`.
+/// DeregisterAgent can then close registries into it, and only the
+/// authority can withdraw the pooled rent with WithdrawRentVault.
+pub fn process_create_rent_vault(
+    program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let ix = CreateRentVault::parse(instruction_data, accounts)?;
+
+    let rent_vault = RentVault::new(ix.data.bump, *ix.accounts.vault_authority.address());
+
+    // Only the canonical PDA is accepted, so the vault address is unique per
+    // authority
+    rent_vault.validate_pda(ix.accounts.rent_vault, program_id, ix.data.bump)?;
+
+    let vault_bump_seed = [ix.data.bump];
+    let vault_seeds: Vec<Seed> = rent_vault.seeds_with_bump(&vault_bump_seed);
+    let vault_seeds_array: [Seed; 3] = vault_seeds
+        .try_into()
+        .map_err(|_| ProgramError::InvalidArgument)?;
+
+    create_pda_account(
+        ix.accounts.payer,
+        RentVault::LEN,
+        program_id,
+        ix.accounts.rent_vault,
+        vault_seeds_array,
+    )?;
+
+    let mut vault_data = ix.accounts.rent_vault.try_borrow_mut()?;
+    rent_vault.write_to_slice(&mut vault_data)
+}
@@ -0,0 +1,127 @@
+use pinocchio::error::ProgramError;
+
+/// Original variable-length encoding: no version byte, trailing fields optional
+pub const INSTRUCTION_DATA_V1: u8 = 1;
+
+/// Versioned encoding: a leading `2` byte, then the v1 fields with every
+/// trailing option tag present
+pub const INSTRUCTION_DATA_V2: u8 = 2;
+
+/// UpdateAgent-only encoding: a leading `3` byte, a field mask, the fields the
+/// mask selects, then the metadata option tag
+pub const INSTRUCTION_DATA_V3: u8 = 3;
+
+/// Parse variable-length instruction data that may carry a leading
+/// `data_version` byte, returning the value with the number of bytes consumed.
+///
+/// # Scheme
+/// Version 1 data has no prefix, so a leading byte is only treated as a
+/// version when the data cannot be read as version 1:
+/// 1. If `parse_v1` succeeds and consumes every byte, the data is version 1.
+/// 2. Otherwise, if the first byte is [`INSTRUCTION_DATA_V2`] or later and
+///    `parse_versioned_body` recognises that version, returning `Some`, the
+///    data is that version and its result is returned, errors included.
+/// 3. Otherwise the version 1 result is returned, so unknown versions behave
+///    exactly as they did before versioning existed.
+///
+/// A recognised prefix takes the versioned error over the version 1 one,
+/// since the version 1 error comes from reading the prefix as the start of
+/// a length and says nothing useful about the payload.
+///
+/// In practice versioned data never reads as version 1: the bytes after the
+/// prefix become the low bytes of a u32 length that lands past the field's
+/// maximum for any non-empty payload.
+#[inline(always)]
+pub fn parse_versioned<T>(
+    data: &[u8],
+    parse_v1: impl Fn(&[u8]) -> Result<(T, usize), ProgramError>,
+    parse_versioned_body: impl Fn(u8, &[u8]) -> Option<Result<(T, usize), ProgramError>>,
+) -> Result<(T, usize), ProgramError> {
+    let v1 = parse_v1(data);
+    if matches!(&v1, Ok((_, consumed)) if *consumed == data.len()) {
+        return v1;
+    }
+
+    if let Some(&version) = data.first().filter(|&&v| v >= INSTRUCTION_DATA_V2) {
+        if let Some(result) = parse_versioned_body(version, &data[1..]) {
+            return result.map(|(value, consumed)| (value, consumed + 1));
+        }
+    }
+
+    v1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_v1(data: &[u8]) -> Result<(u8, usize), ProgramError> {
+        match data.first() {
+            Some(&byte) if byte <= 64 => Ok((byte, 1)),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+
+    fn parse_v2(version: u8, data: &[u8]) -> Option<Result<(u8, usize), ProgramError>> {
+        match (version, data) {
+            (INSTRUCTION_DATA_V2, [a, b]) => Some(Ok((a.wrapping_add(*b), 2))),
+            (INSTRUCTION_DATA_V2, _) => Some(Err(ProgramError::InvalidArgument)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_parse_versioned_v1() {
+        assert_eq!(parse_versioned(&[7], parse_v1, parse_v2), Ok((7, 1)));
+    }
+
+    #[test]
+    fn test_parse_versioned_v2() {
+        assert_eq!(
+            parse_versioned(&[INSTRUCTION_DATA_V2, 100, 5], parse_v1, parse_v2),
+            Ok((105, 3))
+        );
+    }
+
+    #[test]
+    fn test_parse_versioned_unknown_version_falls_back() {
+        // Well-formed body, but the parser does not accept version 3
+        assert_eq!(
+            parse_versioned(&[INSTRUCTION_DATA_V3, 100, 5], parse_v1, parse_v2),
+            Ok((3, 1))
+        );
+    }
+
+    #[test]
+    fn test_parse_versioned_ambiguous_defaults_to_v1() {
+        // A lone `2` is a complete version 1 payload
+        assert_eq!(
+            parse_versioned(&[INSTRUCTION_DATA_V2], parse_v1, parse_v2),
+            Ok((2, 1))
+        );
+    }
+
+    #[test]
+    fn test_parse_versioned_recognised_prefix_returns_versioned_error() {
+        // Reads as a partial version 1 payload, but 2 is a known version
+        assert_eq!(
+            parse_versioned(&[INSTRUCTION_DATA_V2, 9], parse_v1, parse_v2),
+            Err(ProgramError::InvalidArgument)
+        );
+        // Fails version 1 outright; the version 2 error wins
+        assert_eq!(
+            parse_versioned(&[INSTRUCTION_DATA_V2, 200, 1, 1], parse_v1, parse_v2),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_parse_versioned_falls_back_to_v1_result() {
+        // Trailing bytes after an unknown version keep the version 1 result
+        assert_eq!(parse_versioned(&[3, 9], parse_v1, parse_v2), Ok((3, 1)));
+        assert_eq!(
+            parse_versioned(&[200], parse_v1, parse_v2),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
@@ -1,4 +1,24 @@
-use codama::CodamaInstructions;
+use codama::{CodamaInstructions, CodamaType};
+
+/// Off-chain metadata URI and the hash committing to its contents.
+#[derive(Clone, Debug, PartialEq, CodamaType)]
+pub struct AgentMetadata {
+    /// Metadata URI (UTF-8, max 128 bytes)
+    pub uri: alloc::string::String,
+    /// Hash of the metadata document
+    pub hash: [u8; 32],
+}
+
+/// A single name and inbox URL update within UpdateAgentBatch.
+#[derive(Clone, Debug, PartialEq, CodamaType)]
+pub struct UpdateAgentBatchEntry {
+    /// Agent name (UTF-8, 2 to 64 bytes)
+    pub name: alloc::string::String,
+    /// Inbox URL (UTF-8, max 256 bytes)
+    pub inbox_url: alloc::string::String,
+    /// Optional metadata commitment
+    pub metadata: Option<AgentMetadata>,
+}
 
 /// Instructions for the AgentMail Program.
 #[allow(clippy::large_enum_variant)]
@@ -6,6 +26,9 @@ use codama::CodamaInstructions;
 #[derive(Clone, Debug, PartialEq, CodamaInstructions)]
 pub enum AgentMailInstruction {
     /// Register an agent in the AgentMail protocol.
+    ///
+    /// Registering over the authority's soft-deregistered registry replaces
+    /// it, keeping `created_at` if the agent left within the last 7 days.
     #[codama(account(name = "payer", signer, writable))]
     #[codama(account(name = "agent_authority", signer))]
     #[codama(account(name = "agent_registry", writable))]
@@ -14,26 +37,162 @@ pub enum AgentMailInstruction {
     RegisterAgent {
         /// Bump for the agent registry PDA
         bump: u8,
-        /// Agent name (UTF-8, max 64 bytes)
+        /// Agent name (UTF-8, 2 to 64 bytes)
         name: alloc::string::String,
         /// Inbox URL (UTF-8, max 256 bytes)
         inbox_url: alloc::string::String,
+        /// Optional metadata commitment
+        metadata: Option<AgentMetadata>,
+        /// Optional fleet operator
+        operator: Option<pinocchio::Address>,
+        /// Succeed without changes if an identical registry already exists
+        idempotent: bool,
     } = 3,
 
     /// Update an existing agent registration.
+    ///
+    /// A legacy registry is migrated first; `payer` funds the growth,
+    /// defaulting to the authority.
+    ///
+    /// These fields are the version 1 encoding. Version 3 data leads with a
+    /// `3` byte and a field mask, and carries only the fields the mask
+    /// selects (see `UpdateAgentData`). It shares this discriminator, so it
+    /// cannot be declared here; the client generator adds one form per field
+    /// in `scripts/lib/updates/append-update-agent-v3.ts`, and the Rust client
+    /// builds it with `instructions::update_name` and its siblings.
     #[codama(account(name = "agent_authority", signer))]
     #[codama(account(name = "agent_registry", writable))]
     #[codama(account(name = "agentmail_program"))]
+    #[codama(account(name = "system_program", optional))]
+    #[codama(account(name = "payer", signer, writable, optional))]
     UpdateAgent {
-        /// Agent name (UTF-8, max 64 bytes)
+        /// Agent name (UTF-8, 2 to 64 bytes)
         name: alloc::string::String,
         /// Inbox URL (UTF-8, max 256 bytes)
         inbox_url: alloc::string::String,
+        /// Optional metadata commitment
+        metadata: Option<AgentMetadata>,
+        /// Optional replay nonce; must exceed the registry's last nonce
+        nonce: Option<u64>,
     } = 4,
 
     /// Deregister an agent and reclaim rent.
+    ///
+    /// The rent goes to the authority, or to `rent_vault` when one is passed.
     #[codama(account(name = "agent_authority", signer, writable))]
     #[codama(account(name = "agent_registry", writable))]
     #[codama(account(name = "agentmail_program"))]
+    #[codama(account(name = "rent_vault", writable, optional))]
     DeregisterAgent {} = 5,
+
+    /// Update several agent registrations in one transaction.
+    ///
+    /// Remaining accounts are `[signer] agent_authority, [writable] agent_registry`
+    /// pairs, one per entry.
+    #[codama(account(name = "agentmail_program"))]
+    UpdateAgentBatch {
+        /// Entries applied to the account pairs in order (max 5)
+        entries: alloc::vec::Vec<UpdateAgentBatchEntry>,
+    } = 6,
+
+    /// Set whether an agent is accepting new conversations.
+    #[codama(account(name = "agent_authority", signer))]
+    #[codama(account(name = "agent_registry", writable))]
+    #[codama(account(name = "agentmail_program"))]
+    SetAccepting {
+        /// True to accept new conversations, false to stop
+        accepting: bool,
+    } = 7,
+
+    /// Grow a legacy agent registry to the current layout.
+    #[codama(account(name = "payer", signer, writable))]
+    #[codama(account(name = "agent_authority", signer))]
+    #[codama(account(name = "agent_registry", writable))]
+    #[codama(account(name = "system_program"))]
+    #[codama(account(name = "agentmail_program"))]
+    MigrateRegistry {} = 8,
+
+    /// Set or clear the operator that groups an agent into a fleet.
+    #[codama(account(name = "agent_authority", signer))]
+    #[codama(account(name = "agent_registry", writable))]
+    #[codama(account(name = "agentmail_program"))]
+    SetOperator {
+        /// Operator pubkey, all zeroes to clear
+        operator: pinocchio::Address,
+    } = 9,
+
+    /// Check several registries against their expected authorities.
+    ///
+    /// Remaining accounts are one read-only `agent_registry` per authority.
+    /// Sets return data to a u32 (LE) bitmask of the registries that passed.
+    #[codama(account(name = "agentmail_program"))]
+    CheckRegistryBatch {
+        /// Expected authority of each registry, in account order (max 32)
+        authorities: alloc::vec::Vec<pinocchio::Address>,
+    } = 10,
+
+    /// Hide an agent without closing its registry or reclaiming rent.
+    #[codama(account(name = "agent_authority", signer))]
+    #[codama(account(name = "agent_registry", writable))]
+    #[codama(account(name = "agentmail_program"))]
+    SoftDeregisterAgent {} = 11,
+
+    /// Restore an agent hidden by SoftDeregisterAgent.
+    #[codama(account(name = "agent_authority", signer))]
+    #[codama(account(name = "agent_registry", writable))]
+    #[codama(account(name = "agentmail_program"))]
+    ReactivateAgent {} = 12,
+
+    /// Create an empty agent registry for a later InitializeRegistry.
+    ///
+    /// The payer funds the account; the authority does not sign.
+    #[codama(account(name = "payer", signer, writable))]
+    #[codama(account(name = "agent_authority"))]
+    #[codama(account(name = "agent_registry", writable))]
+    #[codama(account(name = "system_program"))]
+    #[codama(account(name = "agentmail_program"))]
+    AllocateRegistry {
+        /// Canonical bump for the agent registry PDA
+        bump: u8,
+    } = 13,
+
+    /// Write an agent registry created by AllocateRegistry.
+    #[codama(account(name = "agent_authority", signer))]
+    #[codama(account(name = "agent_registry", writable))]
+    #[codama(account(name = "agentmail_program"))]
+    InitializeRegistry {
+        /// Bump for the agent registry PDA
+        bump: u8,
+        /// Agent name (UTF-8, 2 to 64 bytes)
+        name: alloc::string::String,
+        /// Inbox URL (UTF-8, max 256 bytes)
+        inbox_url: alloc::string::String,
+        /// Optional metadata commitment
+        metadata: Option<AgentMetadata>,
+        /// Optional fleet operator
+        operator: Option<pinocchio::Address>,
+        /// Succeed without changes if an identical registry already exists
+        idempotent: bool,
+    } = 14,
+
+    /// Create a rent vault that DeregisterAgent can close registries into.
+    #[codama(account(name = "payer", signer, writable))]
+    #[codama(account(name = "vault_authority", signer))]
+    #[codama(account(name = "rent_vault", writable))]
+    #[codama(account(name = "system_program"))]
+    #[codama(account(name = "agentmail_program"))]
+    CreateRentVault {
+        /// Canonical bump for the rent vault PDA
+        bump: u8,
+    } = 15,
+
+    /// Withdraw pooled rent from a vault, keeping its rent-exempt minimum.
+    #[codama(account(name = "vault_authority", signer))]
+    #[codama(account(name = "rent_vault", writable))]
+    #[codama(account(name = "destination", writable))]
+    #[codama(account(name = "agentmail_program"))]
+    WithdrawRentVault {
+        /// Lamports to withdraw
+        amount: u64,
+    } = 16,
 }
@@ -0,0 +1,7 @@
+use crate::instructions::RegisterAgentData;
+
+/// Instruction data for InitializeRegistry
+///
+/// Identical to RegisterAgent's, including the version 2 encoding and the
+/// `idempotent` flag; see [`RegisterAgentData`].
+pub type InitializeRegistryData = RegisterAgentData;
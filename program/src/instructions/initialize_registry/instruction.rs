@@ -0,0 +1,32 @@
+use super::{InitializeRegistryAccounts, InitializeRegistryData};
+use crate::{impl_instruction, traits::Instruction};
+
+/// InitializeRegistry instruction combining accounts and data
+///
+/// Second half of the two-phase registration: the authority writes the
+/// registry a funder created with AllocateRegistry.
+pub struct InitializeRegistry<'a> {
+    pub accounts: InitializeRegistryAccounts<'a>,
+    pub data: InitializeRegistryData,
+}
+
+impl_instruction!(
+    InitializeRegistry,
+    InitializeRegistryAccounts,
+    InitializeRegistryData
+);
+
+impl<'a> Instruction<'a> for InitializeRegistry<'a> {
+    type Accounts = InitializeRegistryAccounts<'a>;
+    type Data = InitializeRegistryData;
+
+    #[inline(always)]
+    fn accounts(&self) -> &Self::Accounts {
+        &self.accounts
+    }
+
+    #[inline(always)]
+    fn data(&self) -> &Self::Data {
+        &self.data
+    }
+}
@@ -0,0 +1,46 @@
+use pinocchio::{account::AccountView, error::ProgramError, Address, ProgramResult};
+
+use crate::{
+    errors::AgentMailProgramError,
+    instructions::{is_same_registration, new_registry, InitializeRegistry},
+    state::AgentRegistry,
+    traits::{AccountDeserialize, AccountSerialize, AccountSize, Instruction, PdaSeeds},
+};
+
+/// Processes the InitializeRegistry instruction.
+///
+/// Writes the registry into an account allocated by AllocateRegistry,
+/// validating the data and PDA exactly as RegisterAgent does. An allocated
+/// registry is all zeroes; one that already holds a registry fails with
+/// `RegistryAlreadyExists`, unless `idempotent` is set and it matches this
+/// call, as for RegisterAgent.
+pub fn process_initialize_registry(
+    program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let ix = InitializeRegistry::parse(instruction_data, accounts)?;
+
+    let agent_registry = new_registry(&ix.data, ix.accounts.agent_authority.address())?;
+
+    // The account must be this authority's canonical registry PDA
+    agent_registry.validate_pda(ix.accounts.agent_registry, program_id, ix.data.bump)?;
+
+    let mut registry_data = ix.accounts.agent_registry.try_borrow_mut()?;
+    if registry_data.len() != AgentRegistry::LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // A written registry starts with its discriminator, never zero
+    if registry_data[0] != 0 {
+        if ix.data.idempotent
+            && AgentRegistry::from_bytes(&registry_data)
+                .is_ok_and(|existing| is_same_registration(existing, &agent_registry))
+        {
+            return Ok(());
+        }
+        return Err(AgentMailProgramError::RegistryAlreadyExists.into());
+    }
+
+    agent_registry.write_to_slice(&mut registry_data)
+}
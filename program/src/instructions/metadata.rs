@@ -0,0 +1,133 @@
+use alloc::string::String;
+use pinocchio::error::ProgramError;
+
+use crate::{errors::AgentMailProgramError, require_len, state::AgentRegistry};
+
+/// Optional off-chain metadata commitment carried by RegisterAgent and UpdateAgent
+///
+/// # Layout
+/// * `tag` (u8) - 0 for none, 1 for some (omitting it entirely also means none)
+/// * `uri_len` (u32, LE) - Length of metadata URI
+/// * `uri` (variable) - Metadata URI (UTF-8)
+/// * `hash` ([u8; 32]) - Hash committing to the metadata contents
+#[derive(Debug, PartialEq)]
+pub struct AgentMetadataData {
+    pub uri: String,
+    pub hash: [u8; 32],
+}
+
+impl AgentMetadataData {
+    /// Parse an optional metadata commitment from the front of `data`,
+    /// returning it with the number of bytes consumed.
+    ///
+    /// Empty input parses as `None` so instruction data built before metadata
+    /// existed stays valid.
+    #[inline(always)]
+    pub fn parse_option(data: &[u8]) -> Result<(Option<Self>, usize), ProgramError> {
+        let Some(&tag) = data.first() else {
+            return Ok((None, 0));
+        };
+
+        match tag {
+            0 => return Ok((None, 1)),
+            1 => {}
+            _ => return Err(ProgramError::InvalidInstructionData),
+        }
+
+        let mut offset = 1;
+
+        // Read metadata URI length
+        require_len!(data, offset + 4);
+        let uri_len = u32::from_le_bytes([
+            data[offset],
+            data[offset + 1],
+            data[offset + 2],
+            data[offset + 3],
+        ]) as usize;
+        offset += 4;
+
+        // Validate URI length
+        if uri_len > AgentRegistry::MAX_METADATA_URI_LEN {
+            return Err(AgentMailProgramError::MetadataUriTooLong.into());
+        }
+
+        // Read metadata URI data
+        require_len!(data, offset + uri_len);
+        let uri = String::from_utf8(data[offset..offset + uri_len].to_vec())
+            .map_err(|_| AgentMailProgramError::InvalidUtf8)?;
+        offset += uri_len;
+
+        // Read metadata hash
+        require_len!(data, offset + 32);
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&data[offset..offset + 32]);
+        offset += 32;
+
+        Ok((Some(Self { uri, hash }), offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{vec, vec::Vec};
+
+    fn create_test_data(uri: &str, hash: [u8; 32]) -> Vec<u8> {
+        let mut data = vec![1u8];
+        data.extend_from_slice(&(uri.len() as u32).to_le_bytes());
+        data.extend_from_slice(uri.as_bytes());
+        data.extend_from_slice(&hash);
+        data
+    }
+
+    #[test]
+    fn test_parse_option_some() {
+        let data = create_test_data("ar://profile", [9u8; 32]);
+        let (metadata, consumed) = AgentMetadataData::parse_option(&data).unwrap();
+        let metadata = metadata.unwrap();
+        assert_eq!(metadata.uri, "ar://profile");
+        assert_eq!(metadata.hash, [9u8; 32]);
+        assert_eq!(consumed, data.len());
+    }
+
+    #[test]
+    fn test_parse_option_none_and_omitted() {
+        assert_eq!(AgentMetadataData::parse_option(&[]).unwrap(), (None, 0));
+        assert_eq!(AgentMetadataData::parse_option(&[0]).unwrap(), (None, 1));
+    }
+
+    #[test]
+    fn test_parse_option_empty_uri() {
+        let data = create_test_data("", [0u8; 32]);
+        let (metadata, _) = AgentMetadataData::parse_option(&data).unwrap();
+        assert_eq!(
+            metadata,
+            Some(AgentMetadataData {
+                uri: String::new(),
+                hash: [0u8; 32],
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_option_errors() {
+        assert_eq!(
+            AgentMetadataData::parse_option(&[2]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+
+        let long_uri = "a".repeat(129);
+        let data = create_test_data(&long_uri, [0u8; 32]);
+        assert_eq!(
+            AgentMetadataData::parse_option(&data),
+            Err(AgentMailProgramError::MetadataUriTooLong.into())
+        );
+
+        let mut data = create_test_data("ar://profile", [0u8; 32]);
+        data.pop();
+        assert_eq!(
+            AgentMetadataData::parse_option(&data),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use pinocchio::{account::AccountView, error::ProgramError};
+
+use crate::{assert_roles, traits::InstructionAccounts, utils::verify_distinct};
+
+/// Accounts for the MigrateRegistry instruction
+///
+/// # Account Layout
+/// 0. `[signer, writable]` payer - Pays the rent for the grown account
+/// 1. `[signer]` agent_authority - Agent's authority (must match registry authority)
+/// 2. `[writable]` agent_registry - Legacy agent registry PDA to migrate
+/// 3. `[]` system_program - System program for the rent transfer
+/// 4. `[]` program - Current program
+#[derive(Debug, PartialEq)]
+pub struct MigrateRegistryAccounts<'a> {
+    pub payer: &'a AccountView,
+    pub agent_authority: &'a AccountView,
+    pub agent_registry: &'a AccountView,
+    pub system_program: &'a AccountView,
+    pub program: &'a AccountView,
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for MigrateRegistryAccounts<'a> {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [payer, agent_authority, agent_registry, system_program, program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Payer funds the registry, so it cannot be the registry itself
+        verify_distinct(payer, agent_registry)?;
+        verify_distinct(agent_authority, agent_registry)?;
+
+        // The payer funds the extra rent and the registry must already be
+        // owned by this program
+        assert_roles!(
+            payer: [signer, writable],
+            agent_authority: [signer],
+            agent_registry: [writable, program_owned],
+            system_program: [system_program],
+            program: [current_program],
+        );
+
+        Ok(Self {
+            payer,
+            agent_authority,
+            agent_registry,
+            system_program,
+            program,
+        })
+    }
+}
+
+impl<'a> InstructionAccounts<'a> for MigrateRegistryAccounts<'a> {}
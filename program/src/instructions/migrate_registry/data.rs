@@ -0,0 +1,22 @@
+use pinocchio::error::ProgramError;
+
+use crate::traits::InstructionData;
+
+/// Instruction data for MigrateRegistry
+///
+/// This instruction takes no additional data beyond the accounts.
+/// The registry is always migrated to the current layout.
+pub struct MigrateRegistryData;
+
+impl<'a> TryFrom<&'a [u8]> for MigrateRegistryData {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(_data: &'a [u8]) -> Result<Self, Self::Error> {
+        Ok(MigrateRegistryData)
+    }
+}
+
+impl<'a> InstructionData<'a> for MigrateRegistryData {
+    const LEN: usize = 0; // No data required
+}
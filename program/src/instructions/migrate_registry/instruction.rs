@@ -0,0 +1,31 @@
+use crate::traits::Instruction;
+
+use super::{MigrateRegistryAccounts, MigrateRegistryData};
+
+/// MigrateRegistry instruction
+///
+/// Grows a legacy AgentRegistry PDA to the current layout in place.
+/// Only the authority (agent) can migrate their own registry.
+pub struct MigrateRegistry<'a> {
+    pub accounts: MigrateRegistryAccounts<'a>,
+    pub data: MigrateRegistryData,
+}
+
+impl<'a> Instruction<'a> for MigrateRegistry<'a> {
+    type Accounts = MigrateRegistryAccounts<'a>;
+    type Data = MigrateRegistryData;
+
+    fn accounts(&self) -> &Self::Accounts {
+        &self.accounts
+    }
+
+    fn data(&self) -> &Self::Data {
+        &self.data
+    }
+}
+
+impl<'a> From<(MigrateRegistryAccounts<'a>, MigrateRegistryData)> for MigrateRegistry<'a> {
+    fn from((accounts, data): (MigrateRegistryAccounts<'a>, MigrateRegistryData)) -> Self {
+        Self { accounts, data }
+    }
+}
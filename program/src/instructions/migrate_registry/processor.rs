@@ -0,0 +1,105 @@
+use pinocchio::{account::AccountView, Address, ProgramResult};
+
+use crate::{
+    errors::AgentMailProgramError,
+    instructions::MigrateRegistry,
+    state::AgentRegistry,
+    traits::{AccountSize, Discriminator, Instruction, Versioned},
+    utils::realloc_registry,
+};
+
+/// Processes the MigrateRegistry instruction.
+///
+/// Every layout change so far only appended fields or claimed zeroed padding,
+/// so a legacy registry migrates by growing it to `AgentRegistry::LEN`,
+/// zero-filling the new fields and stamping the current version.
+///
+/// The same steps repair an interrupted migration: a registry that already
+/// has the current size but a stale header or inner version byte is
+/// restamped, and one stamped current but still short is grown. The account
+/// size decides which layout the data is on; the version bytes only break
+/// the tie between versions 4 and 5, which share a size.
+///
+/// This is the authority-gated stand-in for an admin repair instruction: the
+/// program has no admin account and emits no events, so a repair is visible
+/// only through the rewritten version bytes.
+pub fn process_migrate_registry(
+    program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let ix = MigrateRegistry::parse(instruction_data, accounts)?;
+
+    migrate_registry_account(
+        program_id,
+        ix.accounts.agent_authority,
+        ix.accounts.agent_registry,
+        ix.accounts.payer,
+    )
+}
+
+/// Brings a single registry up to the current layout, growing it if needed.
+///
+/// Shared by MigrateRegistry and UpdateAgent, which migrates a legacy
+/// registry before applying the update. `payer` funds any rent top-up.
+pub(crate) fn migrate_registry_account(
+    program_id: &Address,
+    agent_authority: &AccountView,
+    registry: &AccountView,
+    payer: &AccountView,
+) -> ProgramResult {
+    if registry.data_len() > AgentRegistry::LEN {
+        return Err(AgentMailProgramError::AccountTooLarge.into());
+    }
+
+    // Legacy layouts cannot be deserialized, so check the stable prefix directly
+    let (header_version, legacy_version) = {
+        let data = registry.try_borrow()?;
+        if data.len() < AgentRegistry::AUTHORITY_OFFSET + 32 {
+            return Err(AgentMailProgramError::AccountTooSmall.into());
+        }
+        if !data.starts_with(AgentRegistry::DISCRIMINATOR) {
+            return Err(AgentMailProgramError::InvalidDiscriminator.into());
+        }
+        if &data[AgentRegistry::AUTHORITY_OFFSET..AgentRegistry::AUTHORITY_OFFSET + 32]
+            != agent_authority.address().as_ref()
+        {
+            return Err(AgentMailProgramError::InvalidAuthority.into());
+        }
+        (data[1], data[3])
+    };
+
+    AgentRegistry::validate_pda_for_authority(
+        registry.address(),
+        agent_authority.address(),
+        program_id,
+        None,
+    )?;
+
+    // Already on the current layout with both version bytes agreeing: nothing to do
+    if AgentRegistry::is_current(header_version)
+        && AgentRegistry::is_current(legacy_version)
+        && registry.data_len() == AgentRegistry::LEN
+    {
+        return Ok(());
+    }
+
+    // Only an account grown from a layout without the flag needs it set; a
+    // larger one already holds the agent's choice, even under a stale version
+    let original_len = registry.data_len();
+    let predates_accepting = original_len < AgentRegistry::V5_ACCOUNT_LEN
+        || (original_len == AgentRegistry::V5_ACCOUNT_LEN && legacy_version < 5);
+
+    realloc_registry(registry, AgentRegistry::LEN, payer)?;
+
+    let mut data = registry.try_borrow_mut()?;
+    data[1] = AgentRegistry::VERSION;
+    let state = AgentRegistry::from_account_mut(&mut data)?;
+    if predates_accepting {
+        // Before version 5 every registry was implicitly accepting
+        state.accepting = 1;
+    }
+    state.version = AgentRegistry::VERSION;
+
+    Ok(())
+}
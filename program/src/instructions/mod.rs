@@ -1,10 +1,36 @@
+pub mod allocate_registry;
+pub mod check_registry_batch;
+pub mod create_rent_vault;
+pub mod data_version;
 pub mod definition;
 pub mod deregister_agent;
+pub mod initialize_registry;
+pub mod metadata;
+pub mod migrate_registry;
+pub mod reactivate_agent;
 pub mod register_agent;
+pub mod set_accepting;
+pub mod set_operator;
+pub mod soft_deregister_agent;
 pub mod update_agent;
+pub mod update_agent_batch;
+pub mod withdraw_rent_vault;
 
+pub use allocate_registry::*;
+pub use check_registry_batch::*;
+pub use create_rent_vault::*;
+pub use data_version::*;
 #[cfg(feature = "idl")]
 pub use definition::*;
 pub use deregister_agent::*;
+pub use initialize_registry::*;
+pub use metadata::*;
+pub use migrate_registry::*;
+pub use reactivate_agent::*;
 pub use register_agent::*;
+pub use set_accepting::*;
+pub use set_operator::*;
+pub use soft_deregister_agent::*;
 pub use update_agent::*;
+pub use update_agent_batch::*;
+pub use withdraw_rent_vault::*;
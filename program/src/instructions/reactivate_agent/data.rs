@@ -0,0 +1,21 @@
+use pinocchio::error::ProgramError;
+
+use crate::traits::InstructionData;
+
+/// Instruction data for ReactivateAgent
+///
+/// This instruction takes no additional data beyond the accounts.
+pub struct ReactivateAgentData;
+
+impl<'a> TryFrom<&'a [u8]> for ReactivateAgentData {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(_data: &'a [u8]) -> Result<Self, Self::Error> {
+        Ok(ReactivateAgentData)
+    }
+}
+
+impl<'a> InstructionData<'a> for ReactivateAgentData {
+    const LEN: usize = 0; // No data required
+}
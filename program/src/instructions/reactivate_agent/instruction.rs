@@ -0,0 +1,31 @@
+use crate::traits::Instruction;
+
+use super::{ReactivateAgentAccounts, ReactivateAgentData};
+
+/// ReactivateAgent instruction
+///
+/// Undoes a SoftDeregisterAgent, making the agent visible again.
+/// Only the authority (agent) can reactivate its own registry.
+pub struct ReactivateAgent<'a> {
+    pub accounts: ReactivateAgentAccounts<'a>,
+    pub data: ReactivateAgentData,
+}
+
+impl<'a> Instruction<'a> for ReactivateAgent<'a> {
+    type Accounts = ReactivateAgentAccounts<'a>;
+    type Data = ReactivateAgentData;
+
+    fn accounts(&self) -> &Self::Accounts {
+        &self.accounts
+    }
+
+    fn data(&self) -> &Self::Data {
+        &self.data
+    }
+}
+
+impl<'a> From<(ReactivateAgentAccounts<'a>, ReactivateAgentData)> for ReactivateAgent<'a> {
+    fn from((accounts, data): (ReactivateAgentAccounts<'a>, ReactivateAgentData)) -> Self {
+        Self { accounts, data }
+    }
+}
@@ -0,0 +1,37 @@
+use pinocchio::{account::AccountView, Address, ProgramResult};
+
+use crate::{
+    errors::AgentMailProgramError, instructions::ReactivateAgent, state::AgentRegistry,
+    traits::Instruction, utils::get_current_timestamp,
+};
+
+/// Processes the ReactivateAgent instruction.
+///
+/// Clears the `deleted` flag and touches `updated_at`. Fails with
+/// `NoChanges` if the agent is not deleted.
+pub fn process_reactivate_agent(
+    _program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let ix = ReactivateAgent::parse(instruction_data, accounts)?;
+
+    // Get current timestamp
+    let timestamp = get_current_timestamp()?;
+
+    // Borrow the registry as a typed view and update it in place
+    let mut registry_data = ix.accounts.agent_registry.try_borrow_mut()?;
+    let registry = AgentRegistry::from_account_mut(&mut registry_data)?;
+
+    // Verify that the signer is the authority for this registry
+    registry.validate_authority(ix.accounts.agent_authority.address())?;
+
+    if !registry.is_deleted() {
+        return Err(AgentMailProgramError::NoChanges.into());
+    }
+
+    registry.set_deleted(false);
+    registry.touch(timestamp);
+
+    Ok(())
+}
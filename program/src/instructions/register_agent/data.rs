@@ -1,28 +1,47 @@
 use alloc::string::String;
-use pinocchio::error::ProgramError;
+use pinocchio::{error::ProgramError, Address};
 
-use crate::{errors::AgentMailProgramError, require_len, traits::InstructionData};
+use crate::{
+    errors::AgentMailProgramError,
+    instructions::{parse_versioned, AgentMetadataData, INSTRUCTION_DATA_V1, INSTRUCTION_DATA_V2},
+    require_len,
+    state::AgentRegistry,
+    traits::InstructionData,
+};
 
 /// Instruction data for RegisterAgent
 ///
+/// Accepts both encodings described in [`parse_versioned`]; version 2 data is
+/// prefixed with a `2` byte and must carry the `metadata` option tag.
+///
 /// # Layout
 /// * `bump` (u8) - Bump for the agent registry PDA
 /// * `name_len` (u32, LE) - Length of agent name
 /// * `name` (variable) - Agent name (UTF-8)
 /// * `inbox_url_len` (u32, LE) - Length of inbox URL
 /// * `inbox_url` (variable) - Inbox URL (UTF-8)
+/// * `metadata` (optional) - See [`AgentMetadataData`]
+/// * `operator` (optional) - Tag byte (0 for none, 1 for some) followed by
+///   the 32-byte operator pubkey; omitting it entirely also means none
+/// * `idempotent` (optional u8) - 1 to accept a retry of an identical
+///   registration as a no-op; omitting it means 0. Requires the operator tag.
 #[derive(Debug, PartialEq)]
 pub struct RegisterAgentData {
     pub bump: u8,
     pub name: String,
     pub inbox_url: String,
+    pub metadata: Option<AgentMetadataData>,
+    pub operator: Option<Address>,
+    pub idempotent: bool,
 }
 
-impl<'a> TryFrom<&'a [u8]> for RegisterAgentData {
-    type Error = ProgramError;
-
+impl RegisterAgentData {
+    /// Parse the fields following any version byte, returning them with the
+    /// number of bytes consumed
+    ///
+    /// Version 2 requires the metadata option tag; version 1 treats it as optional.
     #[inline(always)]
-    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+    fn parse_fields(data: &[u8], version: u8) -> Result<(Self, usize), ProgramError> {
         if data.is_empty() {
             return Err(ProgramError::InvalidInstructionData);
         }
@@ -48,12 +67,16 @@ impl<'a> TryFrom<&'a [u8]> for RegisterAgentData {
         if name_len > 64 {
             return Err(AgentMailProgramError::NameTooLong.into());
         }
+        if name_len < AgentRegistry::MIN_NAME_LEN {
+            return Err(AgentMailProgramError::NameTooShort.into());
+        }
 
         // Read name data
         require_len!(data, offset + name_len);
         let name_bytes = &data[offset..offset + name_len];
         let name = String::from_utf8(name_bytes.to_vec())
             .map_err(|_| AgentMailProgramError::InvalidUtf8)?;
+        AgentRegistry::validate_name_characters(&name)?;
         offset += name_len;
 
         // Read inbox URL length
@@ -76,23 +99,86 @@ impl<'a> TryFrom<&'a [u8]> for RegisterAgentData {
         let url_bytes = &data[offset..offset + url_len];
         let inbox_url = String::from_utf8(url_bytes.to_vec())
             .map_err(|_| AgentMailProgramError::InvalidUtf8)?;
+        offset += url_len;
+
+        // Read metadata commitment
+        if version >= INSTRUCTION_DATA_V2 {
+            require_len!(data, offset + 1);
+        }
+        let (metadata, consumed) = AgentMetadataData::parse_option(&data[offset..])?;
+        offset += consumed;
+
+        // Read operator, which is optional in every version
+        let operator = match data.get(offset) {
+            None => None,
+            Some(0) => {
+                offset += 1;
+                None
+            }
+            Some(1) => {
+                offset += 1;
+                require_len!(data, offset + 32);
+                let mut operator = [0u8; 32];
+                operator.copy_from_slice(&data[offset..offset + 32]);
+                offset += 32;
+                Some(Address::new_from_array(operator))
+            }
+            Some(_) => return Err(ProgramError::InvalidInstructionData),
+        };
 
-        Ok(Self {
-            bump,
-            name,
-            inbox_url,
-        })
+        // Read the idempotency flag, which follows the operator tag
+        let idempotent = match data.get(offset) {
+            None => false,
+            Some(&flag @ (0 | 1)) => {
+                offset += 1;
+                flag == 1
+            }
+            Some(_) => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        Ok((
+            Self {
+                bump,
+                name,
+                inbox_url,
+                metadata,
+                operator,
+                idempotent,
+            },
+            offset,
+        ))
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for RegisterAgentData {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        parse_versioned(
+            data,
+            |data| Self::parse_fields(data, INSTRUCTION_DATA_V1),
+            |version, data| match version {
+                INSTRUCTION_DATA_V2 => Some(Self::parse_fields(data, version)),
+                _ => None,
+            },
+        )
+        .map(|(register_data, _)| register_data)
     }
 }
 
 impl<'a> InstructionData<'a> for RegisterAgentData {
     const LEN: usize = 0; // Variable length, so we override validation
+
+    /// Version 1 data with the shortest allowed name and an empty inbox URL
+    const MIN_LEN: usize = 1 + 4 + AgentRegistry::MIN_NAME_LEN + 4;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use alloc::borrow::ToOwned;
+    use alloc::vec;
     use alloc::vec::Vec;
 
     fn create_test_data(bump: u8, name: &str, url: &str) -> Vec<u8> {
@@ -160,13 +246,167 @@ mod tests {
 
     #[test]
     fn test_register_agent_data_minimum_data() {
-        let data = create_test_data(255, "", "");
+        let data = create_test_data(255, "ab", "");
         let result = RegisterAgentData::try_from(&data[..]);
 
         assert!(result.is_ok());
         let register_data = result.unwrap();
         assert_eq!(register_data.bump, 255);
-        assert_eq!(register_data.name, "");
+        assert_eq!(register_data.name, "ab");
         assert_eq!(register_data.inbox_url, "");
     }
+
+    #[test]
+    fn test_register_agent_data_min_len() {
+        let data = create_test_data(255, "ab", "");
+        assert_eq!(data.len(), RegisterAgentData::MIN_LEN);
+        assert!(RegisterAgentData::try_from(&data[..]).is_ok());
+
+        let result = RegisterAgentData::try_from(&data[..data.len() - 1]);
+        assert_eq!(result, Err(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn test_register_agent_data_name_too_short() {
+        let data = create_test_data(200, "a", "https://test.example.com/inbox");
+        let result = RegisterAgentData::try_from(&data[..]);
+        assert_eq!(result, Err(AgentMailProgramError::NameTooShort.into()));
+    }
+
+    #[test]
+    fn test_register_agent_data_with_metadata() {
+        let mut data = create_test_data(200, "test-agent", "https://test.example.com/inbox");
+        let uri = "https://test.example.com/agent.json";
+        data.push(1);
+        data.extend_from_slice(&(uri.len() as u32).to_le_bytes());
+        data.extend_from_slice(uri.as_bytes());
+        data.extend_from_slice(&[7u8; 32]);
+
+        let register_data = RegisterAgentData::try_from(&data[..]).unwrap();
+        let metadata = register_data.metadata.unwrap();
+        assert_eq!(metadata.uri, uri);
+        assert_eq!(metadata.hash, [7u8; 32]);
+    }
+
+    #[test]
+    fn test_register_agent_data_without_metadata() {
+        let data = create_test_data(200, "test-agent", "https://test.example.com/inbox");
+        let register_data = RegisterAgentData::try_from(&data[..]).unwrap();
+        assert!(register_data.metadata.is_none());
+    }
+
+    #[test]
+    fn test_register_agent_data_with_operator() {
+        let mut data = create_test_data(200, "test-agent", "https://test.example.com/inbox");
+        data.push(0); // no metadata
+        data.push(1);
+        data.extend_from_slice(&[9u8; 32]);
+
+        let register_data = RegisterAgentData::try_from(&data[..]).unwrap();
+        assert!(register_data.metadata.is_none());
+        assert_eq!(
+            register_data.operator,
+            Some(Address::new_from_array([9u8; 32]))
+        );
+
+        // A truncated operator is rejected rather than ignored
+        data.pop();
+        assert_eq!(
+            RegisterAgentData::try_from(&data[..]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_register_agent_data_idempotent_flag() {
+        let mut data = create_test_data(200, "test-agent", "https://test.example.com/inbox");
+        assert!(!RegisterAgentData::try_from(&data[..]).unwrap().idempotent);
+
+        data.push(0); // no metadata
+        data.push(0); // no operator
+        assert!(!RegisterAgentData::try_from(&data[..]).unwrap().idempotent);
+
+        data.push(1);
+        assert!(RegisterAgentData::try_from(&data[..]).unwrap().idempotent);
+
+        *data.last_mut().unwrap() = 2;
+        assert_eq!(
+            RegisterAgentData::try_from(&data[..]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_register_agent_data_v1_encoding() {
+        let data = create_test_data(2, "v1-agent", "https://v1.example.com/inbox");
+        let register_data = RegisterAgentData::try_from(&data[..]).unwrap();
+        assert_eq!(register_data.bump, 2);
+        assert_eq!(register_data.name, "v1-agent");
+        assert_eq!(register_data.inbox_url, "https://v1.example.com/inbox");
+        assert!(register_data.metadata.is_none());
+    }
+
+    #[test]
+    fn test_register_agent_data_v2_encoding() {
+        let mut data = vec![INSTRUCTION_DATA_V2];
+        data.extend(create_test_data(
+            254,
+            "v2-agent",
+            "https://v2.example.com/inbox",
+        ));
+        let uri = "https://v2.example.com/agent.json";
+        data.push(1);
+        data.extend_from_slice(&(uri.len() as u32).to_le_bytes());
+        data.extend_from_slice(uri.as_bytes());
+        data.extend_from_slice(&[9u8; 32]);
+
+        let register_data = RegisterAgentData::try_from(&data[..]).unwrap();
+        assert_eq!(register_data.bump, 254);
+        assert_eq!(register_data.name, "v2-agent");
+        assert_eq!(register_data.inbox_url, "https://v2.example.com/inbox");
+        assert_eq!(register_data.metadata.unwrap().hash, [9u8; 32]);
+    }
+
+    #[test]
+    fn test_register_agent_data_v2_without_metadata() {
+        let mut data = vec![INSTRUCTION_DATA_V2];
+        data.extend(create_test_data(
+            254,
+            "v2-agent",
+            "https://v2.example.com/inbox",
+        ));
+        data.push(0);
+
+        let register_data = RegisterAgentData::try_from(&data[..]).unwrap();
+        assert_eq!(register_data.bump, 254);
+        assert_eq!(register_data.name, "v2-agent");
+        assert!(register_data.metadata.is_none());
+    }
+
+    #[test]
+    fn test_register_agent_data_name_with_newline() {
+        let data = create_test_data(200, "bad\nname", "https://test.example.com/inbox");
+        let result = RegisterAgentData::try_from(&data[..]);
+        assert_eq!(
+            result,
+            Err(AgentMailProgramError::InvalidNameCharacters.into())
+        );
+    }
+
+    #[test]
+    fn test_register_agent_data_name_with_null() {
+        let data = create_test_data(200, "bad\0name", "https://test.example.com/inbox");
+        let result = RegisterAgentData::try_from(&data[..]);
+        assert_eq!(
+            result,
+            Err(AgentMailProgramError::InvalidNameCharacters.into())
+        );
+    }
+
+    #[test]
+    fn test_register_agent_data_name_with_unicode() {
+        let data = create_test_data(200, "nix-🤖-é", "https://test.example.com/inbox");
+        let result = RegisterAgentData::try_from(&data[..]);
+        assert_eq!(result.unwrap().name, "nix-🤖-é");
+    }
 }
@@ -3,16 +3,29 @@ use pinocchio::{account::AccountView, cpi::Seed, error::ProgramError, Address, P
 
 use crate::{
     errors::AgentMailProgramError,
-    instructions::RegisterAgent,
+    instructions::{RegisterAgent, RegisterAgentData},
     state::AgentRegistry,
-    traits::{AccountSerialize, AccountSize, PdaSeeds},
-    utils::{create_pda_account, get_current_timestamp},
+    traits::{AccountDeserialize, AccountSerialize, AccountSize, PdaSeeds},
+    utils::{
+        create_pda_account, get_current_slot, get_current_timestamp, verify_registrable_authority,
+    },
 };
 
 /// Processes the RegisterAgent instruction.
 ///
 /// Creates an AgentRegistry PDA for the specified agent authority,
 /// storing their name and inbox URL for the AgentMail protocol.
+///
+/// If the registry already exists this fails with `RegistryAlreadyExists`,
+/// unless the data sets `idempotent` and the stored registry already holds
+/// the authority, name, inbox URL, metadata and operator this call carries.
+/// That lets a client retry a registration that timed out but landed, while
+/// a conflicting one still fails.
+///
+/// A soft-deregistered registry is instead overwritten with the new
+/// registration. It keeps its `created_at` if the agent returns within
+/// [`AgentRegistry::REREGISTER_GRACE_PERIOD`]; see
+/// [`AgentRegistry::preserve_history`].
 pub fn process_register_agent(
     program_id: &Address,
     accounts: &[AccountView],
@@ -20,27 +33,39 @@ pub fn process_register_agent(
 ) -> ProgramResult {
     let ix = RegisterAgent::try_from((instruction_data, accounts))?;
 
-    // Get current timestamp
-    let timestamp = get_current_timestamp()?;
+    let mut agent_registry = new_registry(&ix.data, ix.accounts.agent_authority.address())?;
 
-    // Create AgentRegistry state
-    let agent_registry = AgentRegistry::new(
-        ix.data.bump,
-        *ix.accounts.agent_authority.address(),
-        &ix.data.name,
-        &ix.data.inbox_url,
-        timestamp,
-    )?;
-
-    // Validate AgentRegistry PDA
+    // Validate AgentRegistry PDA before anything is created, so a mismatched
+    // (account, bump) pair fails with InvalidSeeds instead of inside the CPI
     agent_registry.validate_pda(ix.accounts.agent_registry, program_id, ix.data.bump)?;
 
     // Ensure agent authority matches the PDA derivation
     agent_registry.validate_authority(ix.accounts.agent_authority.address())?;
 
-    // Check that registry doesn't already exist (account should be empty)
+    // Check that registry doesn't already exist, accepting an identical retry
+    // and re-registering in place of a soft-deregistered one
     if ix.accounts.agent_registry.data_len() != 0 {
-        return Err(AgentMailProgramError::RegistryAlreadyExists.into());
+        let mut previous = None;
+        if ix.accounts.agent_registry.owned_by(program_id) {
+            let existing_data = ix.accounts.agent_registry.try_borrow()?;
+            if let Ok(existing) = AgentRegistry::from_bytes(&existing_data) {
+                if ix.data.idempotent && is_same_registration(existing, &agent_registry) {
+                    return Ok(());
+                }
+                if existing.is_deleted() {
+                    previous = Some(*existing);
+                }
+            }
+        }
+        let Some(previous) = previous else {
+            return Err(AgentMailProgramError::RegistryAlreadyExists.into());
+        };
+
+        agent_registry.preserve_history(&previous, agent_registry.get_updated_at());
+
+        let mut registry_data_slice = ix.accounts.agent_registry.try_borrow_mut()?;
+        agent_registry.write_to_slice(&mut registry_data_slice)?;
+        return Ok(());
     }
 
     // Get seeds for AgentRegistry account creation
@@ -66,10 +91,63 @@ pub fn process_register_agent(
     Ok(())
 }
 
+/// Builds the registry RegisterAgent and InitializeRegistry write for
+/// `authority`, stamped with the current time and slot.
+#[inline(always)]
+pub(crate) fn new_registry(
+    data: &RegisterAgentData,
+    authority: &Address,
+) -> Result<AgentRegistry, ProgramError> {
+    // Reserved addresses such as the system program cannot be agents
+    verify_registrable_authority(authority)?;
+
+    // Get current timestamp
+    let timestamp = get_current_timestamp()?;
+
+    // Record the creation slot as a tamper-resistant ordering key
+    let slot = get_current_slot()?;
+
+    // Create AgentRegistry state
+    let mut agent_registry = AgentRegistry::new(
+        data.bump,
+        *authority,
+        &data.name,
+        &data.inbox_url,
+        timestamp,
+        slot,
+    )?;
+
+    // Commit to the off-chain metadata, if provided
+    if let Some(metadata) = &data.metadata {
+        agent_registry.set_metadata(&metadata.uri, metadata.hash)?;
+    }
+
+    // Join the operator's fleet, if provided
+    if let Some(operator) = &data.operator {
+        agent_registry.set_operator(operator);
+    }
+
+    Ok(agent_registry)
+}
+
+/// Whether `existing` holds every field `new` took from the instruction data.
+///
+/// Timestamps and the creation slot are ignored, since a retry runs later.
+/// A soft-deregistered registry never matches.
+#[inline(always)]
+pub(crate) fn is_same_registration(existing: &AgentRegistry, new: &AgentRegistry) -> bool {
+    !existing.is_deleted()
+        && existing.authority == new.authority
+        && existing.name == new.name
+        && existing.inbox_url == new.inbox_url
+        && existing.metadata_uri == new.metadata_uri
+        && existing.metadata_hash == new.metadata_hash
+        && existing.operator == new.operator
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::instructions::RegisterAgentData;
 
     // Helper function to create test instruction data
     fn create_test_instruction_data(bump: u8, name: &str, inbox_url: &str) -> Vec<u8> {
@@ -106,6 +184,7 @@ mod tests {
             "test-agent",
             "https://test.example.com/inbox",
             1707523200,
+            1,
         );
 
         assert!(result.is_ok());
@@ -0,0 +1,62 @@
+use pinocchio::error::ProgramError;
+
+use crate::{require_len, traits::InstructionData};
+
+/// Instruction data for SetAccepting
+///
+/// # Layout
+/// * `accepting` (u8) - 1 to accept new conversations, 0 to stop
+#[derive(Debug, PartialEq)]
+pub struct SetAcceptingData {
+    pub accepting: bool,
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetAcceptingData {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        require_len!(data, Self::LEN);
+
+        let accepting = match data[0] {
+            0 => false,
+            1 => true,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        Ok(Self { accepting })
+    }
+}
+
+impl<'a> InstructionData<'a> for SetAcceptingData {
+    const LEN: usize = 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_accepting_data_valid() {
+        assert_eq!(
+            SetAcceptingData::try_from(&[0u8][..]),
+            Ok(SetAcceptingData { accepting: false })
+        );
+        assert_eq!(
+            SetAcceptingData::try_from(&[1u8][..]),
+            Ok(SetAcceptingData { accepting: true })
+        );
+    }
+
+    #[test]
+    fn test_set_accepting_data_invalid() {
+        assert_eq!(
+            SetAcceptingData::try_from(&[][..]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        assert_eq!(
+            SetAcceptingData::try_from(&[2u8][..]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use crate::traits::Instruction;
+
+use super::{SetAcceptingAccounts, SetAcceptingData};
+
+/// SetAccepting instruction
+///
+/// Toggles whether an agent is accepting new conversations.
+/// Only the authority (agent) can change its own availability.
+pub struct SetAccepting<'a> {
+    pub accounts: SetAcceptingAccounts<'a>,
+    pub data: SetAcceptingData,
+}
+
+impl<'a> Instruction<'a> for SetAccepting<'a> {
+    type Accounts = SetAcceptingAccounts<'a>;
+    type Data = SetAcceptingData;
+
+    fn accounts(&self) -> &Self::Accounts {
+        &self.accounts
+    }
+
+    fn data(&self) -> &Self::Data {
+        &self.data
+    }
+}
+
+impl<'a> From<(SetAcceptingAccounts<'a>, SetAcceptingData)> for SetAccepting<'a> {
+    fn from((accounts, data): (SetAcceptingAccounts<'a>, SetAcceptingData)) -> Self {
+        Self { accounts, data }
+    }
+}
@@ -0,0 +1,33 @@
+use pinocchio::{account::AccountView, Address, ProgramResult};
+
+use crate::{
+    instructions::SetAccepting, state::AgentRegistry, traits::Instruction,
+    utils::get_current_timestamp,
+};
+
+/// Processes the SetAccepting instruction.
+///
+/// Sets the agent-controlled `accepting` flag and touches `updated_at`.
+/// Only the authority (agent) can change its own availability.
+pub fn process_set_accepting(
+    _program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let ix = SetAccepting::parse(instruction_data, accounts)?;
+
+    // Get current timestamp
+    let timestamp = get_current_timestamp()?;
+
+    // Borrow the registry as a typed view and update it in place
+    let mut registry_data = ix.accounts.agent_registry.try_borrow_mut()?;
+    let registry = AgentRegistry::from_account_mut(&mut registry_data)?;
+
+    // Verify that the signer is the authority for this registry
+    registry.validate_authority(ix.accounts.agent_authority.address())?;
+
+    registry.set_accepting(ix.data.accepting);
+    registry.touch(timestamp);
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use pinocchio::{account::AccountView, error::ProgramError};
+
+use crate::{assert_roles, traits::InstructionAccounts, utils::verify_distinct};
+
+/// Accounts for the SetOperator instruction
+///
+/// # Account Layout
+/// 0. `[signer]` agent_authority - Agent's authority (must match registry authority)
+/// 1. `[writable]` agent_registry - Agent registry PDA to be updated
+/// 2. `[]` program - Current program
+#[derive(Debug, PartialEq)]
+pub struct SetOperatorAccounts<'a> {
+    pub agent_authority: &'a AccountView,
+    pub agent_registry: &'a AccountView,
+    pub program: &'a AccountView,
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for SetOperatorAccounts<'a> {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [agent_authority, agent_registry, program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Reject aliased accounts before any borrows happen
+        verify_distinct(agent_authority, agent_registry)?;
+        verify_distinct(agent_authority, program)?;
+        verify_distinct(agent_registry, program)?;
+
+        // Only the authority can change its own operator
+        assert_roles!(
+            agent_authority: [signer],
+            agent_registry: [writable],
+            program: [current_program],
+        );
+
+        Ok(Self {
+            agent_authority,
+            agent_registry,
+            program,
+        })
+    }
+}
+
+impl<'a> InstructionAccounts<'a> for SetOperatorAccounts<'a> {}
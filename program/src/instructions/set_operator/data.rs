@@ -0,0 +1,55 @@
+use pinocchio::{error::ProgramError, Address};
+
+use crate::{require_len, traits::InstructionData};
+
+/// Instruction data for SetOperator
+///
+/// # Layout
+/// * `operator` (32 bytes) - Operator pubkey; all zeroes clears it
+#[derive(Debug, PartialEq)]
+pub struct SetOperatorData {
+    pub operator: Address,
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetOperatorData {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        require_len!(data, Self::LEN);
+
+        let mut operator = [0u8; 32];
+        operator.copy_from_slice(&data[..32]);
+
+        Ok(Self {
+            operator: Address::new_from_array(operator),
+        })
+    }
+}
+
+impl<'a> InstructionData<'a> for SetOperatorData {
+    const LEN: usize = 32;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_operator_data_valid() {
+        assert_eq!(
+            SetOperatorData::try_from(&[7u8; 32][..]),
+            Ok(SetOperatorData {
+                operator: Address::new_from_array([7u8; 32])
+            })
+        );
+    }
+
+    #[test]
+    fn test_set_operator_data_too_short() {
+        assert_eq!(
+            SetOperatorData::try_from(&[7u8; 31][..]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
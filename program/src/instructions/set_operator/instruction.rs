@@ -0,0 +1,31 @@
+use crate::traits::Instruction;
+
+use super::{SetOperatorAccounts, SetOperatorData};
+
+/// SetOperator instruction
+///
+/// Sets or clears the operator key that groups an agent into a fleet.
+/// Only the authority (agent) can change its own operator.
+pub struct SetOperator<'a> {
+    pub accounts: SetOperatorAccounts<'a>,
+    pub data: SetOperatorData,
+}
+
+impl<'a> Instruction<'a> for SetOperator<'a> {
+    type Accounts = SetOperatorAccounts<'a>;
+    type Data = SetOperatorData;
+
+    fn accounts(&self) -> &Self::Accounts {
+        &self.accounts
+    }
+
+    fn data(&self) -> &Self::Data {
+        &self.data
+    }
+}
+
+impl<'a> From<(SetOperatorAccounts<'a>, SetOperatorData)> for SetOperator<'a> {
+    fn from((accounts, data): (SetOperatorAccounts<'a>, SetOperatorData)) -> Self {
+        Self { accounts, data }
+    }
+}
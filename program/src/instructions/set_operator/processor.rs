@@ -0,0 +1,33 @@
+use pinocchio::{account::AccountView, Address, ProgramResult};
+
+use crate::{
+    instructions::SetOperator, state::AgentRegistry, traits::Instruction,
+    utils::get_current_timestamp,
+};
+
+/// Processes the SetOperator instruction.
+///
+/// Sets the operator key, or clears it with all zeroes, and touches `updated_at`.
+/// Only the authority (agent) can change its own operator.
+pub fn process_set_operator(
+    _program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let ix = SetOperator::parse(instruction_data, accounts)?;
+
+    // Get current timestamp
+    let timestamp = get_current_timestamp()?;
+
+    // Borrow the registry as a typed view and update it in place
+    let mut registry_data = ix.accounts.agent_registry.try_borrow_mut()?;
+    let registry = AgentRegistry::from_account_mut(&mut registry_data)?;
+
+    // Verify that the signer is the authority for this registry
+    registry.validate_authority(ix.accounts.agent_authority.address())?;
+
+    registry.set_operator(&ix.data.operator);
+    registry.touch(timestamp);
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use pinocchio::error::ProgramError;
+
+use crate::traits::InstructionData;
+
+/// Instruction data for SoftDeregisterAgent
+///
+/// This instruction takes no additional data beyond the accounts.
+pub struct SoftDeregisterAgentData;
+
+impl<'a> TryFrom<&'a [u8]> for SoftDeregisterAgentData {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(_data: &'a [u8]) -> Result<Self, Self::Error> {
+        Ok(SoftDeregisterAgentData)
+    }
+}
+
+impl<'a> InstructionData<'a> for SoftDeregisterAgentData {
+    const LEN: usize = 0; // No data required
+}
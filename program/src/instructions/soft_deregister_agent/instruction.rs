@@ -0,0 +1,33 @@
+use crate::traits::Instruction;
+
+use super::{SoftDeregisterAgentAccounts, SoftDeregisterAgentData};
+
+/// SoftDeregisterAgent instruction
+///
+/// Marks an agent as deregistered without closing its account.
+/// Only the authority (agent) can soft-deregister its own registry.
+pub struct SoftDeregisterAgent<'a> {
+    pub accounts: SoftDeregisterAgentAccounts<'a>,
+    pub data: SoftDeregisterAgentData,
+}
+
+impl<'a> Instruction<'a> for SoftDeregisterAgent<'a> {
+    type Accounts = SoftDeregisterAgentAccounts<'a>;
+    type Data = SoftDeregisterAgentData;
+
+    fn accounts(&self) -> &Self::Accounts {
+        &self.accounts
+    }
+
+    fn data(&self) -> &Self::Data {
+        &self.data
+    }
+}
+
+impl<'a> From<(SoftDeregisterAgentAccounts<'a>, SoftDeregisterAgentData)>
+    for SoftDeregisterAgent<'a>
+{
+    fn from((accounts, data): (SoftDeregisterAgentAccounts<'a>, SoftDeregisterAgentData)) -> Self {
+        Self { accounts, data }
+    }
+}
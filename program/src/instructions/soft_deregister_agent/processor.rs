@@ -0,0 +1,38 @@
+use pinocchio::{account::AccountView, Address, ProgramResult};
+
+use crate::{
+    errors::AgentMailProgramError, instructions::SoftDeregisterAgent, state::AgentRegistry,
+    traits::Instruction, utils::get_current_timestamp,
+};
+
+/// Processes the SoftDeregisterAgent instruction.
+///
+/// Sets the `deleted` flag and touches `updated_at`, keeping the account,
+/// its rent and `created_at` so the agent can later reactivate. Fails with
+/// `NoChanges` if the agent is already deleted.
+pub fn process_soft_deregister_agent(
+    _program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let ix = SoftDeregisterAgent::parse(instruction_data, accounts)?;
+
+    // Get current timestamp
+    let timestamp = get_current_timestamp()?;
+
+    // Borrow the registry as a typed view and update it in place
+    let mut registry_data = ix.accounts.agent_registry.try_borrow_mut()?;
+    let registry = AgentRegistry::from_account_mut(&mut registry_data)?;
+
+    // Verify that the signer is the authority for this registry
+    registry.validate_authority(ix.accounts.agent_authority.address())?;
+
+    if registry.is_deleted() {
+        return Err(AgentMailProgramError::NoChanges.into());
+    }
+
+    registry.set_deleted(true);
+    registry.touch(timestamp);
+
+    Ok(())
+}
@@ -1,9 +1,6 @@
 use pinocchio::{account::AccountView, error::ProgramError};
 
-use crate::{
-    traits::InstructionAccounts,
-    utils::{verify_current_program, verify_signer, verify_writable},
-};
+use crate::{assert_roles, traits::InstructionAccounts, utils::verify_distinct};
 
 /// Accounts for the UpdateAgent instruction
 ///
@@ -11,11 +8,16 @@ use crate::{
 /// 0. `[signer]` agent_authority - Agent's authority (must match registry authority)
 /// 1. `[writable]` agent_registry - Agent registry PDA to be updated
 /// 2. `[]` program - Current program
+/// 3. `[optional]` system_program - Required only when the registry must grow
+/// 4. `[signer, writable, optional]` payer - Funds the rent for a grown
+///    registry instead of the authority
 #[derive(Debug, PartialEq)]
 pub struct UpdateAgentAccounts<'a> {
     pub agent_authority: &'a AccountView,
     pub agent_registry: &'a AccountView,
     pub program: &'a AccountView,
+    pub system_program: Option<&'a AccountView>,
+    pub payer: Option<&'a AccountView>,
 }
 
 impl<'a> TryFrom<&'a [AccountView]> for UpdateAgentAccounts<'a> {
@@ -23,156 +25,57 @@ impl<'a> TryFrom<&'a [AccountView]> for UpdateAgentAccounts<'a> {
 
     #[inline(always)]
     fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
-        let [agent_authority, agent_registry, program] = accounts else {
-            return Err(ProgramError::NotEnoughAccountKeys);
+        let (agent_authority, agent_registry, program, system_program, payer) = match accounts {
+            [agent_authority, agent_registry, program] => {
+                (agent_authority, agent_registry, program, None, None)
+            }
+            [agent_authority, agent_registry, program, system_program] => (
+                agent_authority,
+                agent_registry,
+                program,
+                Some(system_program),
+                None,
+            ),
+            [agent_authority, agent_registry, program, system_program, payer] => (
+                agent_authority,
+                agent_registry,
+                program,
+                Some(system_program),
+                Some(payer),
+            ),
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
         };
 
-        // Agent authority must be signer (only they can update their registry)
-        verify_signer(agent_authority)?;
+        // Reject aliased accounts before any borrows happen
+        verify_distinct(agent_authority, agent_registry)?;
+        verify_distinct(agent_authority, program)?;
+        verify_distinct(agent_registry, program)?;
 
-        // Agent registry must be writable
-        verify_writable(agent_registry)?;
+        // Only the authority can update its registry
+        assert_roles!(
+            agent_authority: [signer],
+            agent_registry: [writable],
+            program: [current_program],
+        );
+
+        if let Some(system_program) = system_program {
+            assert_roles!(system_program: [system_program]);
+        }
 
-        // Verify this is our program
-        verify_current_program(program)?;
+        // The payer, when given, must be able to fund the rent top-up
+        if let Some(payer) = payer {
+            verify_distinct(payer, agent_registry)?;
+            assert_roles!(payer: [signer, writable]);
+        }
 
         Ok(Self {
             agent_authority,
             agent_registry,
             program,
+            system_program,
+            payer,
         })
     }
 }
 
 impl<'a> InstructionAccounts<'a> for UpdateAgentAccounts<'a> {}
-
-// Unit tests disabled in favor of comprehensive LiteSVM integration tests
-#[cfg(disabled_unit_tests)]
-mod tests {
-    use super::*;
-    use core::ptr;
-    use pinocchio::{error::ProgramError, Address};
-
-    fn create_mock_account(
-        address: Address,
-        owner: Address,
-        lamports: u64,
-        data_len: usize,
-        is_signer: bool,
-        is_writable: bool,
-    ) -> AccountView {
-        unsafe { AccountView::new_unchecked(ptr::null_mut()) }
-    }
-
-    #[test]
-    fn test_update_agent_accounts_valid() {
-        let agent_authority = create_mock_account(
-            Address::new_from_array([1u8; 32]),
-            Address::new_from_array([11u8; 32]),
-            0,
-            0,
-            true,  // signer
-            false, // not writable (just authority)
-        );
-
-        let agent_registry = create_mock_account(
-            Address::new_from_array([2u8; 32]),
-            crate::ID, // Our program owns this account
-            0,
-            384,   // Size of AgentRegistry
-            false, // not signer
-            true,  // writable
-        );
-
-        let program = create_mock_account(
-            crate::ID,                          // Our program ID from declare_id!
-            Address::new_from_array([0u8; 32]), // Native loader
-            1,
-            0,
-            false,
-            false,
-        );
-
-        let accounts = [agent_authority, agent_registry, program];
-        let result = UpdateAgentAccounts::try_from(&accounts[..]);
-
-        assert!(result.is_ok());
-    }
-
-    #[test]
-    fn test_update_agent_accounts_not_enough_keys() {
-        let accounts: [AccountView; 0] = [];
-        let result = UpdateAgentAccounts::try_from(&accounts[..]);
-        assert_eq!(result, Err(ProgramError::NotEnoughAccountKeys));
-    }
-
-    #[test]
-    fn test_update_agent_accounts_authority_not_signer() {
-        let agent_authority = create_mock_account(
-            Address::new_from_array([1u8; 32]),
-            Address::new_from_array([11u8; 32]),
-            0,
-            0,
-            false, // NOT a signer - should fail
-            false,
-        );
-
-        let agent_registry = create_mock_account(
-            Address::new_from_array([2u8; 32]),
-            crate::ID,
-            0,
-            384,
-            false,
-            true,
-        );
-
-        let program = create_mock_account(
-            crate::ID,
-            Address::new_from_array([0u8; 32]),
-            1,
-            0,
-            false,
-            false,
-        );
-
-        let accounts = [agent_authority, agent_registry, program];
-        let result = UpdateAgentAccounts::try_from(&accounts[..]);
-
-        assert!(result.is_err());
-    }
-
-    #[test]
-    fn test_update_agent_accounts_registry_not_writable() {
-        let agent_authority = create_mock_account(
-            Address::new_from_array([1u8; 32]),
-            Address::new_from_array([11u8; 32]),
-            0,
-            0,
-            true,
-            false,
-        );
-
-        let agent_registry = create_mock_account(
-            Address::new_from_array([2u8; 32]),
-            crate::ID,
-            0,
-            384,
-            false,
-            false, // NOT writable - should fail
-        );
-
-        let program = create_mock_account(
-            crate::ID,
-            Address::new_from_array([0u8; 32]),
-            1,
-            0,
-            false,
-            false,
-        );
-
-        let accounts = [agent_authority, agent_registry, program];
-        let result = UpdateAgentAccounts::try_from(&accounts[..]);
-
-        assert!(result.is_err());
-    }
-}
@@ -1,32 +1,204 @@
 use alloc::string::String;
+use alloc::vec::Vec;
 use pinocchio::error::ProgramError;
 
-use crate::{errors::AgentMailProgramError, require_len, traits::InstructionData};
+use crate::{
+    errors::AgentMailProgramError,
+    instructions::{
+        parse_versioned, AgentMetadataData, INSTRUCTION_DATA_V1, INSTRUCTION_DATA_V2,
+        INSTRUCTION_DATA_V3,
+    },
+    require_len,
+    state::AgentRegistry,
+    traits::InstructionData,
+};
 
 /// Instruction data for UpdateAgent
 ///
+/// Accepts the encodings described in [`parse_versioned`]. Batch entries
+/// are always version 1.
+///
 /// # Layout
+/// * `fields` (u8, version 3 only) - Mask of the fields present below;
+///   versions 1 and 2 imply [`Self::UPDATE_ALL`]
 /// * `name_len` (u32, LE) - Length of agent name
 /// * `name` (variable) - Agent name (UTF-8)
 /// * `inbox_url_len` (u32, LE) - Length of inbox URL
 /// * `inbox_url` (variable) - Inbox URL (UTF-8)
+/// * `mime_types` (version 3 only) - Count (u8) then each MIME type as a
+///   u8 length and its ASCII bytes; replaces the accepted MIME types
+/// * `inbox_urls` (version 3 only) - Count (u8) then each URL as a u32 (LE)
+///   length and its UTF-8 bytes; replaces the additional inbox URLs
+/// * `metadata` (optional) - See [`AgentMetadataData`]; `None` leaves it unchanged
+/// * `nonce` (optional) - Tag byte (0 for none, 1 for some) followed by a
+///   u64 (LE) replay nonce; omitting it entirely also means none. Requires
+///   the metadata tag, and is never read from batch entries
 #[derive(Debug, PartialEq)]
 pub struct UpdateAgentData {
     pub name: String,
     pub inbox_url: String,
+    pub metadata: Option<AgentMetadataData>,
+    /// Which of `name`, `inbox_url`, `mime_types` and `inbox_urls` to write;
+    /// absent ones are left unchanged
+    pub fields: u8,
+    /// The full list of accepted MIME types to store
+    pub mime_types: Vec<String>,
+    /// The full list of additional inbox URLs to store
+    pub inbox_urls: Vec<String>,
+    /// Must exceed the registry's `last_nonce` when present
+    pub nonce: Option<u64>,
 }
 
-impl<'a> TryFrom<&'a [u8]> for UpdateAgentData {
-    type Error = ProgramError;
+impl UpdateAgentData {
+    /// Field mask bit selecting `name`
+    pub const UPDATE_NAME: u8 = 1 << 0;
+
+    /// Field mask bit selecting `inbox_url`
+    pub const UPDATE_INBOX_URL: u8 = 1 << 1;
+
+    /// Field mask bit selecting `mime_types`, only in version 3 data
+    pub const UPDATE_MIME_TYPES: u8 = 1 << 2;
+
+    /// Field mask bit selecting `inbox_urls`, only in version 3 data
+    pub const UPDATE_INBOX_URLS: u8 = 1 << 3;
 
+    /// Name and inbox URL, the implicit mask of version 1 and 2 data
+    pub const UPDATE_ALL: u8 = Self::UPDATE_NAME | Self::UPDATE_INBOX_URL;
+
+    /// Every bit a version 3 field mask may set
+    pub const FIELDS_MASK: u8 =
+        Self::UPDATE_ALL | Self::UPDATE_MIME_TYPES | Self::UPDATE_INBOX_URLS;
+
+    /// The new name, if this update writes one
     #[inline(always)]
-    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+    pub fn name(&self) -> Option<&str> {
+        (self.fields & Self::UPDATE_NAME != 0).then_some(self.name.as_str())
+    }
+
+    /// The new inbox URL, if this update writes one
+    #[inline(always)]
+    pub fn inbox_url(&self) -> Option<&str> {
+        (self.fields & Self::UPDATE_INBOX_URL != 0).then_some(self.inbox_url.as_str())
+    }
+
+    /// The new accepted MIME types, if this update writes them
+    #[inline(always)]
+    pub fn mime_types(&self) -> Option<&[String]> {
+        (self.fields & Self::UPDATE_MIME_TYPES != 0).then_some(self.mime_types.as_slice())
+    }
+
+    /// The new additional inbox URLs, if this update writes them
+    #[inline(always)]
+    pub fn inbox_urls(&self) -> Option<&[String]> {
+        (self.fields & Self::UPDATE_INBOX_URLS != 0).then_some(self.inbox_urls.as_slice())
+    }
+
+    /// Parse one entry from the front of `data`, returning it with the number of bytes consumed
+    #[inline(always)]
+    pub fn parse_prefix(data: &[u8]) -> Result<(Self, usize), ProgramError> {
+        Self::parse_fields(data, INSTRUCTION_DATA_V1)
+    }
+
+    /// Parse the fields following any version byte
+    ///
+    /// Versions 2 and 3 require the metadata option tag; version 1 treats it
+    /// as optional. Version 3 leads with the field mask.
+    #[inline(always)]
+    fn parse_fields(data: &[u8], version: u8) -> Result<(Self, usize), ProgramError> {
         if data.is_empty() {
             return Err(ProgramError::InvalidInstructionData);
         }
 
         let mut offset = 0;
 
+        // Read the field mask, rejecting bits no field is assigned to
+        let fields = if version >= INSTRUCTION_DATA_V3 {
+            let fields = data[offset];
+            if fields & !Self::FIELDS_MASK != 0 {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+            offset += 1;
+            fields
+        } else {
+            Self::UPDATE_ALL
+        };
+
+        let mut name = String::new();
+        if fields & Self::UPDATE_NAME != 0 {
+            let (parsed, consumed) = Self::parse_name(&data[offset..])?;
+            name = parsed;
+            offset += consumed;
+        }
+
+        let mut inbox_url = String::new();
+        if fields & Self::UPDATE_INBOX_URL != 0 {
+            let (parsed, consumed) = Self::parse_inbox_url(&data[offset..])?;
+            inbox_url = parsed;
+            offset += consumed;
+        }
+
+        let mut mime_types = Vec::new();
+        if fields & Self::UPDATE_MIME_TYPES != 0 {
+            let (parsed, consumed) = Self::parse_mime_types(&data[offset..])?;
+            mime_types = parsed;
+            offset += consumed;
+        }
+
+        let mut inbox_urls = Vec::new();
+        if fields & Self::UPDATE_INBOX_URLS != 0 {
+            let (parsed, consumed) = Self::parse_inbox_urls(&data[offset..])?;
+            inbox_urls = parsed;
+            offset += consumed;
+        }
+
+        // Read metadata commitment
+        if version >= INSTRUCTION_DATA_V2 {
+            require_len!(data, offset + 1);
+        }
+        let (metadata, consumed) = AgentMetadataData::parse_option(&data[offset..])?;
+        offset += consumed;
+
+        Ok((
+            Self {
+                name,
+                inbox_url,
+                metadata,
+                fields,
+                mime_types,
+                inbox_urls,
+                nonce: None,
+            },
+            offset,
+        ))
+    }
+
+    /// Parse the fields of a single update, followed by the optional nonce
+    #[inline(always)]
+    fn parse_with_nonce(data: &[u8], version: u8) -> Result<(Self, usize), ProgramError> {
+        let (mut update_data, mut offset) = Self::parse_fields(data, version)?;
+
+        match data.get(offset) {
+            None => {}
+            Some(0) => offset += 1,
+            Some(1) => {
+                offset += 1;
+                require_len!(data, offset + 8);
+                let mut nonce = [0u8; 8];
+                nonce.copy_from_slice(&data[offset..offset + 8]);
+                update_data.nonce = Some(u64::from_le_bytes(nonce));
+                offset += 8;
+            }
+            Some(_) => return Err(ProgramError::InvalidInstructionData),
+        }
+
+        Ok((update_data, offset))
+    }
+
+    /// Parse a length-prefixed name, returning it with the number of bytes consumed
+    #[inline(always)]
+    fn parse_name(data: &[u8]) -> Result<(String, usize), ProgramError> {
+        let mut offset = 0;
+
         // Read name length
         require_len!(data, offset + 4);
         let name_len = u32::from_le_bytes([
@@ -41,14 +213,26 @@ impl<'a> TryFrom<&'a [u8]> for UpdateAgentData {
         if name_len > 64 {
             return Err(AgentMailProgramError::NameTooLong.into());
         }
+        if name_len < AgentRegistry::MIN_NAME_LEN {
+            return Err(AgentMailProgramError::NameTooShort.into());
+        }
 
         // Read name data
         require_len!(data, offset + name_len);
         let name_bytes = &data[offset..offset + name_len];
         let name = String::from_utf8(name_bytes.to_vec())
             .map_err(|_| AgentMailProgramError::InvalidUtf8)?;
+        AgentRegistry::validate_name_characters(&name)?;
         offset += name_len;
 
+        Ok((name, offset))
+    }
+
+    /// Parse a length-prefixed inbox URL, returning it with the number of bytes consumed
+    #[inline(always)]
+    fn parse_inbox_url(data: &[u8]) -> Result<(String, usize), ProgramError> {
+        let mut offset = 0;
+
         // Read inbox URL length
         require_len!(data, offset + 4);
         let url_len = u32::from_le_bytes([
@@ -69,19 +253,105 @@ impl<'a> TryFrom<&'a [u8]> for UpdateAgentData {
         let url_bytes = &data[offset..offset + url_len];
         let inbox_url = String::from_utf8(url_bytes.to_vec())
             .map_err(|_| AgentMailProgramError::InvalidUtf8)?;
+        offset += url_len;
+
+        Ok((inbox_url, offset))
+    }
+
+    /// Parse a counted list of MIME types, returning it with the number of bytes consumed
+    #[inline(always)]
+    fn parse_mime_types(data: &[u8]) -> Result<(Vec<String>, usize), ProgramError> {
+        require_len!(data, 1);
+        let count = data[0] as usize;
+        if count > AgentRegistry::MAX_MIME_TYPES {
+            return Err(AgentMailProgramError::TooManyMimeTypes.into());
+        }
+        let mut offset = 1;
+
+        let mut mime_types = Vec::with_capacity(count);
+        for _ in 0..count {
+            require_len!(data, offset + 1);
+            let len = data[offset] as usize;
+            offset += 1;
+
+            require_len!(data, offset + len);
+            let mime_type = core::str::from_utf8(&data[offset..offset + len])
+                .map_err(|_| AgentMailProgramError::InvalidMimeType)?;
+            AgentRegistry::validate_mime_type(mime_type)?;
+            mime_types.push(String::from(mime_type));
+            offset += len;
+        }
+
+        Ok((mime_types, offset))
+    }
+
+    /// Parse a counted list of additional inbox URLs, returning it with the
+    /// number of bytes consumed
+    #[inline(always)]
+    fn parse_inbox_urls(data: &[u8]) -> Result<(Vec<String>, usize), ProgramError> {
+        require_len!(data, 1);
+        let count = data[0] as usize;
+        if count > AgentRegistry::MAX_INBOX_URLS {
+            return Err(AgentMailProgramError::TooManyInboxUrls.into());
+        }
+        let mut offset = 1;
+
+        let mut inbox_urls = Vec::with_capacity(count);
+        for _ in 0..count {
+            require_len!(data, offset + 4);
+            let len = u32::from_le_bytes([
+                data[offset],
+                data[offset + 1],
+                data[offset + 2],
+                data[offset + 3],
+            ]) as usize;
+            offset += 4;
+
+            if len > AgentRegistry::MAX_ADDITIONAL_INBOX_URL_LEN {
+                return Err(AgentMailProgramError::InboxUrlTooLong.into());
+            }
+            require_len!(data, offset + len);
+            let inbox_url = String::from_utf8(data[offset..offset + len].to_vec())
+                .map_err(|_| AgentMailProgramError::InvalidUtf8)?;
+            inbox_urls.push(inbox_url);
+            offset += len;
+        }
+
+        Ok((inbox_urls, offset))
+    }
+}
 
-        Ok(Self { name, inbox_url })
+impl<'a> TryFrom<&'a [u8]> for UpdateAgentData {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        parse_versioned(
+            data,
+            |data| Self::parse_with_nonce(data, INSTRUCTION_DATA_V1),
+            |version, data| match version {
+                INSTRUCTION_DATA_V2 | INSTRUCTION_DATA_V3 => {
+                    Some(Self::parse_with_nonce(data, version))
+                }
+                _ => None,
+            },
+        )
+        .map(|(update_data, _)| update_data)
     }
 }
 
 impl<'a> InstructionData<'a> for UpdateAgentData {
     const LEN: usize = 0; // Variable length, so we override validation
+
+    /// Version 3 data with an empty field mask and no metadata
+    const MIN_LEN: usize = 1 + 1 + 1;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use alloc::borrow::ToOwned;
+    use alloc::vec;
     use alloc::vec::Vec;
 
     fn create_test_data(name: &str, url: &str) -> Vec<u8> {
@@ -146,12 +416,303 @@ mod tests {
 
     #[test]
     fn test_update_agent_data_minimum_data() {
-        let data = create_test_data("", "");
+        let data = create_test_data("ab", "");
         let result = UpdateAgentData::try_from(&data[..]);
 
         assert!(result.is_ok());
         let update_data = result.unwrap();
-        assert_eq!(update_data.name, "");
+        assert_eq!(update_data.name, "ab");
         assert_eq!(update_data.inbox_url, "");
     }
+
+    #[test]
+    fn test_update_agent_data_name_too_short() {
+        let data = create_test_data("a", "https://test.example.com/inbox");
+        let result = UpdateAgentData::try_from(&data[..]);
+        assert_eq!(result, Err(AgentMailProgramError::NameTooShort.into()));
+    }
+
+    #[test]
+    fn test_update_agent_data_v2_encoding() {
+        let mut data = vec![INSTRUCTION_DATA_V2];
+        data.extend(create_test_data("v2-agent", "https://v2.example.com/inbox"));
+        data.push(0);
+
+        let update_data = UpdateAgentData::try_from(&data[..]).unwrap();
+        assert_eq!(update_data.name, "v2-agent");
+        assert_eq!(update_data.inbox_url, "https://v2.example.com/inbox");
+        assert!(update_data.metadata.is_none());
+    }
+
+    #[test]
+    fn test_update_agent_data_nonce() {
+        let mut data = create_test_data("nonced", "https://nonce.example.com/inbox");
+        data.push(0); // no metadata
+        assert_eq!(UpdateAgentData::try_from(&data[..]).unwrap().nonce, None);
+
+        data.push(1);
+        data.extend_from_slice(&42u64.to_le_bytes());
+        let update_data = UpdateAgentData::try_from(&data[..]).unwrap();
+        assert_eq!(update_data.nonce, Some(42));
+        assert_eq!(update_data.name, "nonced");
+
+        // A truncated nonce is rejected rather than ignored
+        data.pop();
+        assert_eq!(
+            UpdateAgentData::try_from(&data[..]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_update_agent_data_prefix_ignores_nonce() {
+        let mut data = create_test_data("entry", "https://entry.example.com/inbox");
+        data.push(0); // no metadata
+        let entry_len = data.len();
+        data.push(1);
+        data.extend_from_slice(&42u64.to_le_bytes());
+
+        // Batch entries stop after the metadata tag
+        let (entry, consumed) = UpdateAgentData::parse_prefix(&data).unwrap();
+        assert_eq!(consumed, entry_len);
+        assert_eq!(entry.nonce, None);
+    }
+
+    fn create_masked_data(name: Option<&str>, url: Option<&str>) -> Vec<u8> {
+        let mut fields = 0;
+        let mut body = Vec::new();
+        if let Some(name) = name {
+            fields |= UpdateAgentData::UPDATE_NAME;
+            body.extend_from_slice(&(name.len() as u32).to_le_bytes());
+            body.extend_from_slice(name.as_bytes());
+        }
+        if let Some(url) = url {
+            fields |= UpdateAgentData::UPDATE_INBOX_URL;
+            body.extend_from_slice(&(url.len() as u32).to_le_bytes());
+            body.extend_from_slice(url.as_bytes());
+        }
+
+        let mut data = vec![INSTRUCTION_DATA_V3, fields];
+        data.extend(body);
+        data.push(0);
+        data
+    }
+
+    #[test]
+    fn test_update_agent_data_min_len() {
+        let data = create_masked_data(None, None);
+        assert_eq!(data.len(), UpdateAgentData::MIN_LEN);
+        assert!(UpdateAgentData::try_from(&data[..]).is_ok());
+
+        let result = UpdateAgentData::try_from(&data[..data.len() - 1]);
+        assert_eq!(result, Err(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn test_update_agent_data_v3_name_only() {
+        let data = create_masked_data(Some("renamed"), None);
+        let update_data = UpdateAgentData::try_from(&data[..]).unwrap();
+        assert_eq!(update_data.name(), Some("renamed"));
+        assert_eq!(update_data.inbox_url(), None);
+    }
+
+    #[test]
+    fn test_update_agent_data_v3_url_only() {
+        let data = create_masked_data(None, Some("https://moved.example.com/inbox"));
+        let update_data = UpdateAgentData::try_from(&data[..]).unwrap();
+        assert_eq!(update_data.name(), None);
+        assert_eq!(
+            update_data.inbox_url(),
+            Some("https://moved.example.com/inbox")
+        );
+    }
+
+    #[test]
+    fn test_update_agent_data_v3_both_and_neither() {
+        let data = create_masked_data(Some("both"), Some("https://both.example.com/inbox"));
+        let update_data = UpdateAgentData::try_from(&data[..]).unwrap();
+        assert_eq!(update_data.fields, UpdateAgentData::UPDATE_ALL);
+        assert_eq!(update_data.name(), Some("both"));
+        assert_eq!(
+            update_data.inbox_url(),
+            Some("https://both.example.com/inbox")
+        );
+
+        let data = create_masked_data(None, None);
+        let update_data = UpdateAgentData::try_from(&data[..]).unwrap();
+        assert_eq!(update_data.name(), None);
+        assert_eq!(update_data.inbox_url(), None);
+    }
+
+    #[test]
+    fn test_update_agent_data_v3_unknown_field_bit() {
+        let mut data = create_masked_data(Some("agent"), None);
+        data[1] |= 1 << 4;
+        assert!(UpdateAgentData::try_from(&data[..]).is_err());
+    }
+
+    fn create_mime_types_data(mime_types: &[&str]) -> Vec<u8> {
+        let mut data = vec![
+            INSTRUCTION_DATA_V3,
+            UpdateAgentData::UPDATE_MIME_TYPES,
+            mime_types.len() as u8,
+        ];
+        for mime_type in mime_types {
+            data.push(mime_type.len() as u8);
+            data.extend_from_slice(mime_type.as_bytes());
+        }
+        data.push(0);
+        data
+    }
+
+    #[test]
+    fn test_update_agent_data_v3_mime_types() {
+        let data = create_mime_types_data(&["application/json", "text/markdown"]);
+        let update_data = UpdateAgentData::try_from(&data[..]).unwrap();
+        assert_eq!(update_data.name(), None);
+        assert_eq!(update_data.inbox_url(), None);
+        assert_eq!(
+            update_data.mime_types(),
+            Some(&["application/json".to_owned(), "text/markdown".to_owned()][..])
+        );
+
+        // An empty list clears them
+        let data = create_mime_types_data(&[]);
+        let update_data = UpdateAgentData::try_from(&data[..]).unwrap();
+        assert_eq!(update_data.mime_types(), Some(&[][..]));
+
+        // Absent from the mask, they are left unchanged
+        let data = create_masked_data(Some("agent"), None);
+        let update_data = UpdateAgentData::try_from(&data[..]).unwrap();
+        assert_eq!(update_data.mime_types(), None);
+    }
+
+    #[test]
+    fn test_update_agent_data_v3_mime_types_rejected() {
+        // The version 3 error comes back, not the version 1 one the prefix
+        // would produce when read as a name length
+        let parse = |data: &[u8]| UpdateAgentData::try_from(data);
+
+        let data = create_mime_types_data(&["a/a", "b/b", "c/c", "d/d", "e/e"]);
+        assert_eq!(
+            parse(&data),
+            Err(AgentMailProgramError::TooManyMimeTypes.into())
+        );
+
+        let data = create_mime_types_data(&["json"]);
+        assert_eq!(
+            parse(&data),
+            Err(AgentMailProgramError::InvalidMimeType.into())
+        );
+
+        // Truncated inside the second entry
+        let data = create_mime_types_data(&["text/plain", "text/markdown"]);
+        assert_eq!(
+            parse(&data[..data.len() - 4]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    fn create_inbox_urls_data(inbox_urls: &[&str]) -> Vec<u8> {
+        let mut data = vec![
+            INSTRUCTION_DATA_V3,
+            UpdateAgentData::UPDATE_INBOX_URLS,
+            inbox_urls.len() as u8,
+        ];
+        for inbox_url in inbox_urls {
+            data.extend_from_slice(&(inbox_url.len() as u32).to_le_bytes());
+            data.extend_from_slice(inbox_url.as_bytes());
+        }
+        data.push(0);
+        data
+    }
+
+    #[test]
+    fn test_update_agent_data_v3_inbox_urls() {
+        let data =
+            create_inbox_urls_data(&["https://a.example.com/inbox", "https://b.example.com"]);
+        let update_data = UpdateAgentData::try_from(&data[..]).unwrap();
+        assert_eq!(update_data.name(), None);
+        assert_eq!(update_data.mime_types(), None);
+        assert_eq!(
+            update_data.inbox_urls(),
+            Some(
+                &[
+                    "https://a.example.com/inbox".to_owned(),
+                    "https://b.example.com".to_owned()
+                ][..]
+            )
+        );
+
+        // Absent from the mask, they are left unchanged
+        let data = create_masked_data(None, Some("https://example.com/inbox"));
+        let update_data = UpdateAgentData::try_from(&data[..]).unwrap();
+        assert_eq!(update_data.inbox_urls(), None);
+    }
+
+    #[test]
+    fn test_update_agent_data_v3_inbox_urls_rejected() {
+        let parse = |data: &[u8]| UpdateAgentData::try_from(data);
+
+        let data = create_inbox_urls_data(&[
+            "https://a",
+            "https://b",
+            "https://c",
+            "https://d",
+            "https://e",
+        ]);
+        assert_eq!(
+            parse(&data),
+            Err(AgentMailProgramError::TooManyInboxUrls.into())
+        );
+
+        let too_long = alloc::format!("https://{}", "a".repeat(121));
+        let data = create_inbox_urls_data(&[&too_long]);
+        assert_eq!(
+            parse(&data),
+            Err(AgentMailProgramError::InboxUrlTooLong.into())
+        );
+
+        // Truncated inside the first entry
+        let data = create_inbox_urls_data(&["https://a.example.com/inbox"]);
+        assert_eq!(
+            parse(&data[..data.len() - 4]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_update_agent_data_v1_implies_all_fields() {
+        let data = create_test_data("agent", "https://test.example.com/inbox");
+        let update_data = UpdateAgentData::try_from(&data[..]).unwrap();
+        assert_eq!(update_data.fields, UpdateAgentData::UPDATE_ALL);
+        assert_eq!(update_data.name(), Some("agent"));
+    }
+
+    #[test]
+    fn test_update_agent_data_name_with_newline() {
+        let data = create_test_data("bad\nname", "https://test.example.com/inbox");
+        let result = UpdateAgentData::try_from(&data[..]);
+        assert_eq!(
+            result,
+            Err(AgentMailProgramError::InvalidNameCharacters.into())
+        );
+    }
+
+    #[test]
+    fn test_update_agent_data_name_with_null() {
+        let data = create_test_data("bad\0name", "https://test.example.com/inbox");
+        let result = UpdateAgentData::try_from(&data[..]);
+        assert_eq!(
+            result,
+            Err(AgentMailProgramError::InvalidNameCharacters.into())
+        );
+    }
+
+    #[test]
+    fn test_update_agent_data_name_with_unicode() {
+        let data = create_test_data("nix-🤖-é", "https://test.example.com/inbox");
+        let result = UpdateAgentData::try_from(&data[..]);
+        assert_eq!(result.unwrap().name, "nix-🤖-é");
+    }
 }
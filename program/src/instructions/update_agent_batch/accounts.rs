@@ -0,0 +1,74 @@
+use pinocchio::{account::AccountView, error::ProgramError};
+
+use crate::{assert_roles, traits::InstructionAccounts};
+
+/// Accounts for the UpdateAgentBatch instruction
+///
+/// # Account Layout
+/// 0. `[]` program - Current program
+///
+/// Followed by one pair per batch entry:
+/// * `[signer]` agent_authority - Agent's authority (must match registry authority)
+/// * `[writable]` agent_registry - Agent registry PDA to be updated
+#[derive(Debug, PartialEq)]
+pub struct UpdateAgentBatchAccounts<'a> {
+    pub program: &'a AccountView,
+    pub entries: &'a [AccountView],
+}
+
+impl<'a> UpdateAgentBatchAccounts<'a> {
+    /// Number of (agent_authority, agent_registry) pairs
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.entries.len() / 2
+    }
+
+    /// Whether the batch contains no pairs
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate over `(agent_authority, agent_registry)` pairs
+    #[inline(always)]
+    pub fn pairs(&self) -> impl Iterator<Item = (&'a AccountView, &'a AccountView)> {
+        self.entries
+            .chunks_exact(2)
+            .map(|pair| (&pair[0], &pair[1]))
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for UpdateAgentBatchAccounts<'a> {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [program, entries @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Verify this is our program
+        assert_roles!(program: [current_program]);
+
+        // Entries must come in complete (authority, registry) pairs
+        if entries.is_empty() || entries.len() % 2 != 0 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        for pair in entries.chunks_exact(2) {
+            let [agent_authority, agent_registry] = pair else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            // Only the authority can update its own registry
+            assert_roles!(
+                agent_authority: [signer],
+                agent_registry: [writable],
+            );
+        }
+
+        Ok(Self { program, entries })
+    }
+}
+
+impl<'a> InstructionAccounts<'a> for UpdateAgentBatchAccounts<'a> {}
@@ -0,0 +1,196 @@
+use alloc::vec::Vec;
+use pinocchio::error::ProgramError;
+
+use crate::{
+    errors::AgentMailProgramError, instructions::UpdateAgentData, require_len,
+    state::AgentRegistry, traits::InstructionData,
+};
+
+/// Instruction data for UpdateAgentBatch
+///
+/// # Layout
+/// * `entry_count` (u32, LE) - Number of entries
+/// * `entries` (variable) - `entry_count` UpdateAgent payloads, each
+///   `name_len` (u32, LE), `name`, `inbox_url_len` (u32, LE), `inbox_url`,
+///   then the metadata option tag and payload. Only the last entry may omit
+///   the tag; every other entry must carry it.
+#[derive(Debug, PartialEq)]
+pub struct UpdateAgentBatchData {
+    pub entries: Vec<UpdateAgentData>,
+}
+
+impl UpdateAgentBatchData {
+    /// Maximum number of entries in a single batch.
+    ///
+    /// Each entry adds an authority signature and two account keys, so a
+    /// legacy transaction (1232 bytes) fits about five entries with typical
+    /// name and URL lengths. Per-entry compute is a single deserialize and
+    /// rewrite of the registry, which keeps five entries well inside the
+    /// default compute budget.
+    pub const MAX_BATCH_SIZE: usize = 5;
+
+    /// Smallest valid entry: the shortest allowed name, an empty inbox URL
+    /// and no metadata tag
+    pub const MIN_ENTRY_LEN: usize = 4 + AgentRegistry::MIN_NAME_LEN + 4;
+
+    /// Largest valid entry: name and inbox URL at their maximum lengths plus
+    /// a full metadata commitment
+    pub const MAX_ENTRY_LEN: usize = 4
+        + AgentRegistry::MAX_NAME_LEN
+        + 4
+        + AgentRegistry::MAX_INBOX_URL_LEN
+        + 1
+        + 4
+        + AgentRegistry::MAX_METADATA_URI_LEN
+        + 32;
+
+    /// Maximum total instruction data length (2469 bytes).
+    ///
+    /// No valid batch is longer, so anything beyond this is rejected with
+    /// `InvalidInstructionData` before any entry is parsed. This matters for
+    /// CPI callers, which are not bound by the transaction size limit.
+    pub const MAX_DATA_LEN: usize = 4 + Self::MAX_BATCH_SIZE * Self::MAX_ENTRY_LEN;
+}
+
+impl<'a> TryFrom<&'a [u8]> for UpdateAgentBatchData {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() > Self::MAX_DATA_LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // Read entry count
+        require_len!(data, 4);
+        let entry_count = u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as usize;
+
+        if entry_count == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if entry_count > Self::MAX_BATCH_SIZE {
+            return Err(AgentMailProgramError::BatchTooLarge.into());
+        }
+
+        // Read each entry back to back
+        let mut offset = 4;
+        let mut entries = Vec::with_capacity(entry_count);
+        for _ in 0..entry_count {
+            let (entry, consumed) = UpdateAgentData::parse_prefix(&data[offset..])?;
+            entries.push(entry);
+            offset += consumed;
+        }
+
+        Ok(Self { entries })
+    }
+}
+
+impl<'a> InstructionData<'a> for UpdateAgentBatchData {
+    const LEN: usize = 0; // Variable length, so we override validation
+
+    /// The count and a single [`Self::MIN_ENTRY_LEN`] entry
+    const MIN_LEN: usize = 4 + Self::MIN_ENTRY_LEN;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::borrow::ToOwned;
+
+    fn create_test_data(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+
+        for (name, url) in entries {
+            data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+            data.extend_from_slice(name.as_bytes());
+            data.extend_from_slice(&(url.len() as u32).to_le_bytes());
+            data.extend_from_slice(url.as_bytes());
+            data.push(0); // no metadata
+        }
+
+        data
+    }
+
+    #[test]
+    fn test_update_agent_batch_data_try_from_valid() {
+        let data = create_test_data(&[
+            ("agent-1", "https://dc2.example.com/1"),
+            ("agent-2", "https://dc2.example.com/2"),
+            ("agent-3", "https://dc2.example.com/3"),
+        ]);
+        let batch = UpdateAgentBatchData::try_from(&data[..]).unwrap();
+
+        assert_eq!(batch.entries.len(), 3);
+        assert_eq!(batch.entries[0].name, "agent-1");
+        assert_eq!(batch.entries[1].inbox_url, "https://dc2.example.com/2");
+        assert_eq!(batch.entries[2].name, "agent-3");
+    }
+
+    #[test]
+    fn test_update_agent_batch_data_max_len() {
+        let name = "n".repeat(AgentRegistry::MAX_NAME_LEN);
+        let url = "https://".to_owned() + &"u".repeat(AgentRegistry::MAX_INBOX_URL_LEN - 8);
+        let uri = "m".repeat(AgentRegistry::MAX_METADATA_URI_LEN);
+
+        let mut data = (UpdateAgentBatchData::MAX_BATCH_SIZE as u32)
+            .to_le_bytes()
+            .to_vec();
+        for _ in 0..UpdateAgentBatchData::MAX_BATCH_SIZE {
+            data.extend_from_slice(&(name.len() as u32).to_le_bytes());
+            data.extend_from_slice(name.as_bytes());
+            data.extend_from_slice(&(url.len() as u32).to_le_bytes());
+            data.extend_from_slice(url.as_bytes());
+            data.push(1);
+            data.extend_from_slice(&(uri.len() as u32).to_le_bytes());
+            data.extend_from_slice(uri.as_bytes());
+            data.extend_from_slice(&[7u8; 32]);
+        }
+
+        // The largest valid batch sits exactly at the cap
+        assert_eq!(data.len(), UpdateAgentBatchData::MAX_DATA_LEN);
+        assert!(UpdateAgentBatchData::try_from(&data[..]).is_ok());
+
+        data.push(0);
+        assert_eq!(
+            UpdateAgentBatchData::try_from(&data[..]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_update_agent_batch_data_empty() {
+        let data = create_test_data(&[]);
+        let result = UpdateAgentBatchData::try_from(&data[..]);
+        assert_eq!(result, Err(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn test_update_agent_batch_data_too_large() {
+        let entries = [("agent", "https://example.com/inbox"); 6];
+        let data = create_test_data(&entries);
+        let result = UpdateAgentBatchData::try_from(&data[..]);
+        assert_eq!(result, Err(AgentMailProgramError::BatchTooLarge.into()));
+    }
+
+    #[test]
+    fn test_update_agent_batch_data_min_len() {
+        let mut data = create_test_data(&[("ab", "")]);
+        // Version 1 entries may omit the metadata tag
+        data.pop();
+        assert_eq!(data.len(), UpdateAgentBatchData::MIN_LEN);
+        assert!(UpdateAgentBatchData::try_from(&data[..]).is_ok());
+
+        let result = UpdateAgentBatchData::try_from(&data[..data.len() - 1]);
+        assert_eq!(result, Err(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn test_update_agent_batch_data_truncated_entry() {
+        let mut data = create_test_data(&[("agent-1", "https://example.com/1")]);
+        // Drop the metadata tag and the last inbox_url byte
+        data.truncate(data.len() - 2);
+        let result = UpdateAgentBatchData::try_from(&data[..]);
+        assert_eq!(result, Err(ProgramError::InvalidInstructionData));
+    }
+}
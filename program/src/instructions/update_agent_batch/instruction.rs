@@ -0,0 +1,31 @@
+use crate::traits::Instruction;
+
+use super::{UpdateAgentBatchAccounts, UpdateAgentBatchData};
+
+/// UpdateAgentBatch instruction
+///
+/// Updates several AgentRegistry PDAs in one transaction.
+/// Each registry must be signed for by its own authority.
+pub struct UpdateAgentBatch<'a> {
+    pub accounts: UpdateAgentBatchAccounts<'a>,
+    pub data: UpdateAgentBatchData,
+}
+
+impl<'a> Instruction<'a> for UpdateAgentBatch<'a> {
+    type Accounts = UpdateAgentBatchAccounts<'a>;
+    type Data = UpdateAgentBatchData;
+
+    fn accounts(&self) -> &Self::Accounts {
+        &self.accounts
+    }
+
+    fn data(&self) -> &Self::Data {
+        &self.data
+    }
+}
+
+impl<'a> From<(UpdateAgentBatchAccounts<'a>, UpdateAgentBatchData)> for UpdateAgentBatch<'a> {
+    fn from((accounts, data): (UpdateAgentBatchAccounts<'a>, UpdateAgentBatchData)) -> Self {
+        Self { accounts, data }
+    }
+}
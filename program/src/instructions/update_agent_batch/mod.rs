@@ -0,0 +1,9 @@
+mod accounts;
+mod data;
+mod instruction;
+mod processor;
+
+pub use accounts::*;
+pub use data::*;
+pub use instruction::*;
+pub use processor::*;
@@ -0,0 +1,33 @@
+use pinocchio::{account::AccountView, error::ProgramError, Address, ProgramResult};
+
+use crate::{
+    instructions::{update_registry_account, UpdateAgentBatch},
+    traits::Instruction,
+    utils::get_current_timestamp,
+};
+
+/// Processes the UpdateAgentBatch instruction.
+///
+/// Applies an UpdateAgent to each (agent_authority, agent_registry) pair.
+/// Any failing entry fails the whole transaction, so the batch is atomic.
+pub fn process_update_agent_batch(
+    _program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let ix = UpdateAgentBatch::parse(instruction_data, accounts)?;
+
+    // Every entry needs exactly one account pair
+    if ix.data.entries.len() != ix.accounts.len() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Get current timestamp
+    let timestamp = get_current_timestamp()?;
+
+    for ((agent_authority, agent_registry), entry) in ix.accounts.pairs().zip(&ix.data.entries) {
+        update_registry_account(agent_authority, agent_registry, entry, timestamp)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use pinocchio::{account::AccountView, error::ProgramError};
+
+use crate::{assert_roles, traits::InstructionAccounts, utils::verify_distinct};
+
+/// Accounts for the WithdrawRentVault instruction
+///
+/// # Account Layout
+/// 0. `[signer]` vault_authority - Vault's authority (must match vault authority)
+/// 1. `[writable]` rent_vault - Rent vault PDA to withdraw from
+/// 2. `[writable]` destination - Receives the withdrawn lamports
+/// 3. `[]` program - Current program
+#[derive(Debug, PartialEq)]
+pub struct WithdrawRentVaultAccounts<'a> {
+    pub vault_authority: &'a AccountView,
+    pub rent_vault: &'a AccountView,
+    pub destination: &'a AccountView,
+    pub program: &'a AccountView,
+}
+
+impl<'a> TryFrom<&'a [AccountView]> for WithdrawRentVaultAccounts<'a> {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(accounts: &'a [AccountView]) -> Result<Self, Self::Error> {
+        let [vault_authority, rent_vault, destination, program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Withdrawing into the vault itself would be a no-op on a live borrow
+        verify_distinct(rent_vault, destination)?;
+
+        // Only the authority can move lamports out of its vault
+        assert_roles!(
+            vault_authority: [signer],
+            rent_vault: [writable, program_owned],
+            destination: [writable],
+            program: [current_program],
+        );
+
+        Ok(Self {
+            vault_authority,
+            rent_vault,
+            destination,
+            program,
+        })
+    }
+}
+
+impl<'a> InstructionAccounts<'a> for WithdrawRentVaultAccounts<'a> {}
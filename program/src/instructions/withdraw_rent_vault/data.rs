@@ -0,0 +1,53 @@
+use pinocchio::error::ProgramError;
+
+use crate::{require_len, traits::InstructionData};
+
+/// Instruction data for WithdrawRentVault
+///
+/// # Layout
+/// * `amount` (u64, LE) - Lamports to withdraw
+#[derive(Debug, PartialEq)]
+pub struct WithdrawRentVaultData {
+    pub amount: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for WithdrawRentVaultData {
+    type Error = ProgramError;
+
+    #[inline(always)]
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        require_len!(data, Self::LEN);
+
+        let mut amount = [0u8; 8];
+        amount.copy_from_slice(&data[..8]);
+
+        Ok(Self {
+            amount: u64::from_le_bytes(amount),
+        })
+    }
+}
+
+impl<'a> InstructionData<'a> for WithdrawRentVaultData {
+    const LEN: usize = 8;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_withdraw_rent_vault_data_valid() {
+        assert_eq!(
+            WithdrawRentVaultData::try_from(&1_000_000u64.to_le_bytes()[..]),
+            Ok(WithdrawRentVaultData { amount: 1_000_000 })
+        );
+    }
+
+    #[test]
+    fn test_withdraw_rent_vault_data_too_short() {
+        assert_eq!(
+            WithdrawRentVaultData::try_from(&[0u8; 7][..]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+}
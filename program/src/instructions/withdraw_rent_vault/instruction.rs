@@ -0,0 +1,29 @@
+use super::{WithdrawRentVaultAccounts, WithdrawRentVaultData};
+use crate::{impl_instruction, traits::Instruction};
+
+/// WithdrawRentVault instruction combining accounts and data
+pub struct WithdrawRentVault<'a> {
+    pub accounts: WithdrawRentVaultAccounts<'a>,
+    pub data: WithdrawRentVaultData,
+}
+
+impl_instruction!(
+    WithdrawRentVault,
+    WithdrawRentVaultAccounts,
+    WithdrawRentVaultData
+);
+
+impl<'a> Instruction<'a> for WithdrawRentVault<'a> {
+    type Accounts = WithdrawRentVaultAccounts<'a>;
+    type Data = WithdrawRentVaultData;
+
+    #[inline(always)]
+    fn accounts(&self) -> &Self::Accounts {
+        &self.accounts
+    }
+
+    #[inline(always)]
+    fn data(&self) -> &Self::Data {
+        &self.data
+    }
+}
@@ -0,0 +1,54 @@
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    Address, ProgramResult,
+};
+
+use crate::{
+    instructions::WithdrawRentVault,
+    state::RentVault,
+    traits::{AccountSize, Instruction},
+};
+
+/// Processes the WithdrawRentVault instruction.
+///
+/// Moves `amount` lamports from the vault to `destination`. Only the vault's
+/// authority can withdraw, and the vault keeps its own rent-exempt minimum,
+/// so it stays open for later deregistrations. Asking for more than the
+/// pooled balance fails with `InsufficientFunds`.
+pub fn process_withdraw_rent_vault(
+    program_id: &Address,
+    accounts: &[AccountView],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let ix = WithdrawRentVault::parse(instruction_data, accounts)?;
+
+    let vault_data = ix.accounts.rent_vault.try_borrow()?;
+    let rent_vault = RentVault::from_account(&vault_data, ix.accounts.rent_vault, program_id)?;
+    drop(vault_data);
+
+    // Verify that the signer is the authority for this vault
+    rent_vault.validate_authority(ix.accounts.vault_authority.address())?;
+
+    let reserved = Rent::get()?.try_minimum_balance(RentVault::LEN)?;
+    let available = ix.accounts.rent_vault.lamports().saturating_sub(reserved);
+    if ix.data.amount > available {
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    // The vault is program-owned, so its lamports move directly without a
+    // system program CPI
+    let destination_lamports = ix
+        .accounts
+        .destination
+        .lamports()
+        .checked_add(ix.data.amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    ix.accounts
+        .rent_vault
+        .set_lamports(ix.accounts.rent_vault.lamports() - ix.data.amount);
+    ix.accounts.destination.set_lamports(destination_lamports);
+
+    Ok(())
+}
@@ -2,7 +2,9 @@ use alloc::vec;
 use alloc::vec::Vec;
 use codama::CodamaAccount;
 use pinocchio::{account::AccountView, cpi::Seed, error::ProgramError, Address};
+use pinocchio_log::log;
 
+use crate::assert_field_offsets;
 use crate::assert_no_padding;
 use crate::errors::AgentMailProgramError;
 use crate::traits::{
@@ -18,40 +20,164 @@ use crate::traits::{
 /// # PDA Seeds
 /// `[b"agentmail", agent_authority.as_ref()]`
 ///
-/// # Layout (334 bytes)
+/// # Layout (struct 1256 bytes, account 1258 bytes)
 /// - bump: 1 byte
 /// - version: 1 byte  
-/// - _padding: 6 bytes (reserved for future use / alignment)
+/// - url_count: 1 byte (number of populated `inbox_urls` slots)
+/// - accepting: 1 byte (1 if the agent accepts new conversations)
+/// - deleted: 1 byte (1 if the agent soft-deregistered)
+/// - _padding: 3 bytes (reserved for future use / alignment)
 /// - authority: 32 bytes (agent's pubkey - owner)
 /// - name: 4 + 64 bytes (length-prefixed string, max 64 chars)
 /// - inbox_url: 4 + 256 bytes (length-prefixed string, max 256 chars)
 /// - created_at: 8 bytes (i64 unix timestamp)
 /// - updated_at: 8 bytes (i64 unix timestamp)
-#[derive(Clone, Debug, PartialEq, CodamaAccount)]
-#[repr(C)]
+/// - created_slot: 8 bytes (u64 slot at registration)
+/// - inbox_urls: 4 x (4 + 128) bytes (ordered additional endpoints)
+/// - metadata_uri: 4 + 128 bytes (length-prefixed off-chain metadata URI)
+/// - metadata_hash: 32 bytes (SHA-256 of the off-chain metadata JSON)
+/// - operator: 32 bytes (fleet operator pubkey, all zeroes for none)
+/// - _reserved: 4 bytes (keeps `last_nonce` 8-byte aligned)
+/// - last_nonce: 8 bytes (u64, highest update nonce accepted, 0 for none)
+/// - accepted_mime_types: 4 x 32 bytes (zero-padded ASCII, empty slots last)
+///
+/// # Inbox URLs
+/// `inbox_url` stays the primary endpoint. `inbox_urls` holds up to four
+/// additional endpoints in priority order; senders try `inbox_url` first and
+/// then each of `inbox_urls` in turn. UpdateAgent replaces the whole list.
+///
+/// # Metadata commitment
+/// `metadata_uri` points at a richer off-chain profile (IPFS, Arweave, ...)
+/// and `metadata_hash` commits to its contents so clients can verify what
+/// they fetched. An empty URI and an all-zero hash mean no metadata.
+///
+/// # Availability
+/// `accepting` is agent-controlled and toggled with SetAccepting. Senders
+/// should not open new conversations with an agent that is not accepting.
+/// It is unrelated to moderation.
+///
+/// # Soft deregistration
+/// `deleted` is set by SoftDeregisterAgent and cleared by ReactivateAgent.
+/// The account, its rent and `created_at` are kept; senders treat a deleted
+/// agent as absent. DeregisterAgent still closes the account outright.
+///
+/// # Operator
+/// `operator` groups agents run by the same party into a fleet. It is set by
+/// the agent's authority at registration or with SetOperator, and grants the
+/// operator no rights over the registry.
+///
+/// # Accepted MIME types
+/// `accepted_mime_types` lists up to four content types the agent accepts,
+/// e.g. `application/json`, compared case-insensitively. Senders pick one of
+/// them before formatting a message. An empty list advertises nothing and
+/// is not a restriction.
+///
+/// # Update nonce
+/// An UpdateAgent may carry a nonce, which must exceed `last_nonce` and then
+/// replaces it. A captured update therefore cannot be replayed once it or a
+/// later nonce landed, even against a cloned deployment.
+///
+/// # Migration
+/// Version 3 grew the account from 394 to 922 bytes, version 4 to 1090,
+/// version 6 to 1122, version 7 to 1130 and version 8 to 1258.
+/// Older registries fail the size check until MigrateRegistry grows them in
+/// place; every change so far only appended fields or used zeroed padding.
+/// Version 5 claims a padding byte for `accepting` without changing the size;
+/// version 4 registries always read as accepting until MigrateRegistry, or an
+/// UpdateAgent that migrates on the way, upgrades them in place; SetAccepting
+/// rejects them through the size check. `deleted` takes another padding byte
+/// whose zero value already means not deleted, so it needs no version bump.
+///
+/// # Alignment
+/// The struct is packed to alignment 1, since it is viewed in place at byte 2
+/// of the account data. The layout is unchanged, as `assert_no_padding!`
+/// already rules out padding, but the integer fields cannot be borrowed;
+/// read them by value or through the getters.
+#[derive(Clone, Copy, Debug, PartialEq, CodamaAccount)]
+#[repr(C, packed)]
 pub struct AgentRegistry {
     pub bump: u8,
     pub version: u8,
-    pub _padding: [u8; 6],
+    pub url_count: u8,
+    pub accepting: u8,
+    pub deleted: u8,
+    pub _padding: [u8; 3],
     pub authority: Address,
     pub name: [u8; 68],       // 4 bytes length + 64 bytes data
     pub inbox_url: [u8; 260], // 4 bytes length + 256 bytes data
     pub created_at: i64,
     pub updated_at: i64,
+    pub created_slot: u64,
+    pub inbox_urls: [[u8; 132]; 4], // each 4 bytes length + 128 bytes data
+    pub metadata_uri: [u8; 132],    // 4 bytes length + 128 bytes data
+    pub metadata_hash: [u8; 32],
+    pub operator: Address,
+    pub _reserved: [u8; 4],
+    pub last_nonce: u64,
+    pub accepted_mime_types: [[u8; 32]; 4],
 }
 
-assert_no_padding!(AgentRegistry, 1 + 1 + 6 + 32 + 68 + 260 + 8 + 8);
+assert_no_padding!(
+    AgentRegistry,
+    1 + 1 + 1 + 1 + 1 + 3 + 32 + 68 + 260 + 8 + 8 + 8 + 132 * 4 + 132 + 32 + 32 + 4 + 8 + 32 * 4
+);
+
+// Typed views start at byte 2 of the account data, so any alignment above 1
+// would make them unsound
+const _: () = assert!(core::mem::align_of::<AgentRegistry>() == 1);
+
+// Offsets within the struct; account data adds 2 for discriminator and version
+assert_field_offsets!(AgentRegistry, {
+    bump: 0,
+    version: 1,
+    url_count: 2,
+    accepting: 3,
+    deleted: 4,
+    _padding: 5,
+    authority: 8,
+    name: 40,
+    inbox_url: 108,
+    created_at: 368,
+    updated_at: 376,
+    created_slot: 384,
+    inbox_urls: 392,
+    metadata_uri: 920,
+    metadata_hash: 1052,
+    operator: 1084,
+    _reserved: 1116,
+    last_nonce: 1120,
+    accepted_mime_types: 1128,
+});
 
 impl Discriminator for AgentRegistry {
-    const DISCRIMINATOR: u8 = AgentMailAccountDiscriminators::AgentRegistryDiscriminator as u8;
+    const DISCRIMINATOR: &'static [u8] =
+        &[AgentMailAccountDiscriminators::AgentRegistryDiscriminator as u8];
 }
 
 impl Versioned for AgentRegistry {
-    const VERSION: u8 = 1;
+    const VERSION: u8 = 8;
 }
 
 impl AccountSize for AgentRegistry {
-    const DATA_LEN: usize = 1 + 1 + 6 + 32 + 68 + 260 + 8 + 8; // 384 bytes total
+    const DATA_LEN: usize = 1
+        + 1
+        + 1
+        + 1
+        + 1
+        + 3
+        + 32
+        + 68
+        + 260
+        + 8
+        + 8
+        + 8
+        + 132 * 4
+        + 132
+        + 32
+        + 32
+        + 4
+        + 8
+        + 32 * 4; // 1256 bytes total
 }
 
 impl AccountDeserialize for AgentRegistry {}
@@ -62,12 +188,27 @@ impl AccountSerialize for AgentRegistry {
         let mut data = Vec::with_capacity(Self::DATA_LEN);
         data.push(self.bump);
         data.push(self.version);
+        data.push(self.url_count);
+        data.push(self.accepting);
+        data.push(self.deleted);
         data.extend_from_slice(&self._padding);
         data.extend_from_slice(self.authority.as_ref());
         data.extend_from_slice(&self.name);
         data.extend_from_slice(&self.inbox_url);
         data.extend_from_slice(&self.created_at.to_le_bytes());
         data.extend_from_slice(&self.updated_at.to_le_bytes());
+        data.extend_from_slice(&self.created_slot.to_le_bytes());
+        for url in &self.inbox_urls {
+            data.extend_from_slice(url);
+        }
+        data.extend_from_slice(&self.metadata_uri);
+        data.extend_from_slice(&self.metadata_hash);
+        data.extend_from_slice(self.operator.as_ref());
+        data.extend_from_slice(&self._reserved);
+        data.extend_from_slice(&self.last_nonce.to_le_bytes());
+        for mime_type in &self.accepted_mime_types {
+            data.extend_from_slice(mime_type);
+        }
         data
     }
 }
@@ -91,12 +232,45 @@ impl PdaSeeds for AgentRegistry {
 }
 
 impl AgentRegistry {
+    /// Byte offset of the authority within account data, stable across versions
+    pub const AUTHORITY_OFFSET: usize = 10;
+
+    /// Byte offset of `deleted` within account data, stable across versions
+    pub const DELETED_OFFSET: usize = 6;
+
+    /// Account size of versions 4 and 5, the first to carry `accepting`
+    ///
+    /// A shorter account predates the flag whatever its version byte says.
+    pub const V5_ACCOUNT_LEN: usize = 1090;
+
     /// Maximum length for agent name (UTF-8 bytes)
     pub const MAX_NAME_LEN: usize = 64;
 
+    /// Minimum length for agent name (UTF-8 bytes), against single-character squatting
+    pub const MIN_NAME_LEN: usize = 2;
+
     /// Maximum length for inbox URL (UTF-8 bytes)
     pub const MAX_INBOX_URL_LEN: usize = 256;
 
+    /// Maximum number of additional inbox URLs
+    pub const MAX_INBOX_URLS: usize = 4;
+
+    /// Maximum length for each additional inbox URL (UTF-8 bytes)
+    pub const MAX_ADDITIONAL_INBOX_URL_LEN: usize = 128;
+
+    /// Maximum length for the metadata URI (UTF-8 bytes)
+    pub const MAX_METADATA_URI_LEN: usize = 128;
+
+    /// Maximum number of accepted MIME types
+    pub const MAX_MIME_TYPES: usize = 4;
+
+    /// Maximum length for each accepted MIME type (ASCII bytes)
+    pub const MAX_MIME_TYPE_LEN: usize = 32;
+
+    /// Seconds after a soft-deregister during which re-registering keeps the
+    /// original `created_at` (7 days)
+    pub const REREGISTER_GRACE_PERIOD: i64 = 7 * 24 * 60 * 60;
+
     /// Create a new AgentRegistry instance
     #[inline(always)]
     pub fn new(
@@ -105,16 +279,28 @@ impl AgentRegistry {
         name: &str,
         inbox_url: &str,
         timestamp: i64,
+        slot: u64,
     ) -> Result<Self, ProgramError> {
         let mut registry = Self {
             bump,
             version: Self::VERSION,
-            _padding: [0u8; 6],
+            url_count: 0,
+            accepting: 1,
+            deleted: 0,
+            _padding: [0u8; 3],
             authority,
             name: [0u8; 68],
             inbox_url: [0u8; 260],
             created_at: timestamp,
             updated_at: timestamp,
+            created_slot: slot,
+            inbox_urls: [[0u8; 132]; 4],
+            metadata_uri: [0u8; 132],
+            metadata_hash: [0u8; 32],
+            operator: Address::new_from_array([0u8; 32]),
+            _reserved: [0u8; 4],
+            last_nonce: 0,
+            accepted_mime_types: [[0u8; 32]; 4],
         };
 
         registry.set_name(name)?;
@@ -135,6 +321,71 @@ impl AgentRegistry {
         Ok(state)
     }
 
+    /// Validate that an account's data length matches `LEN`
+    ///
+    /// Logs the expected and actual sizes so legacy (too small) and corrupt
+    /// (too large) accounts can be told apart.
+    #[inline(always)]
+    pub fn validate_account_len(len: usize) -> Result<(), ProgramError> {
+        if len < Self::LEN {
+            log!(
+                "AgentRegistry too small: expected {}, got {}",
+                Self::LEN,
+                len
+            );
+            return Err(AgentMailProgramError::AccountTooSmall.into());
+        }
+        if len > Self::LEN {
+            log!(
+                "AgentRegistry too large: expected {}, got {}",
+                Self::LEN,
+                len
+            );
+            return Err(AgentMailProgramError::AccountTooLarge.into());
+        }
+        Ok(())
+    }
+
+    /// Mutable typed view over account data for in-place updates
+    ///
+    /// Requires the exact account length and the AgentRegistry discriminator.
+    /// The view starts at byte 2, after the discriminator and version, which
+    /// is sound because the struct is packed to alignment 1.
+    #[inline(always)]
+    pub fn from_account_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        Self::validate_account_len(data.len())?;
+        Self::from_bytes_mut(data)
+    }
+
+    /// Validate that `address` is the registry PDA derived from `authority`
+    ///
+    /// Proves ownership without reading account data, for registries whose
+    /// data can no longer be deserialized. With a `bump`, e.g. the one stored
+    /// in the registry, it runs a single seed derivation instead of searching
+    /// for the canonical bump, so it is much lighter than `from_account`.
+    #[inline(always)]
+    pub fn validate_pda_for_authority(
+        address: &Address,
+        authority: &Address,
+        program_id: &Address,
+        bump: Option<u8>,
+    ) -> Result<(), ProgramError> {
+        let derived = match bump {
+            Some(bump) => Address::create_program_address(
+                &[Self::PREFIX, authority.as_ref(), &[bump]],
+                program_id,
+            )
+            .map_err(|_| ProgramError::InvalidSeeds)?,
+            None => {
+                Address::find_program_address(&[Self::PREFIX, authority.as_ref()], program_id).0
+            }
+        };
+        if address != &derived {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        Ok(())
+    }
+
     /// Validate that the provided authority matches the account's authority
     #[inline(always)]
     pub fn validate_authority(&self, provided_authority: &Address) -> Result<(), ProgramError> {
@@ -144,6 +395,24 @@ impl AgentRegistry {
         Ok(())
     }
 
+    /// Validate that the stored bump matches the expected bump
+    #[inline(always)]
+    pub fn validate_bump(&self, expected_bump: u8) -> Result<(), ProgramError> {
+        if self.bump != expected_bump {
+            return Err(AgentMailProgramError::InvalidAccountData.into());
+        }
+        Ok(())
+    }
+
+    /// Validate that a name contains no ASCII control characters (0x00–0x1F, 0x7F)
+    #[inline(always)]
+    pub fn validate_name_characters(name: &str) -> Result<(), ProgramError> {
+        if name.bytes().any(|b| b.is_ascii_control()) {
+            return Err(AgentMailProgramError::InvalidNameCharacters.into());
+        }
+        Ok(())
+    }
+
     /// Update the agent's name
     #[inline(always)]
     pub fn set_name(&mut self, name: &str) -> Result<(), ProgramError> {
@@ -152,6 +421,11 @@ impl AgentRegistry {
         if name_bytes.len() > Self::MAX_NAME_LEN {
             return Err(AgentMailProgramError::NameTooLong.into());
         }
+        if name_bytes.len() < Self::MIN_NAME_LEN {
+            return Err(AgentMailProgramError::NameTooShort.into());
+        }
+
+        Self::validate_name_characters(name)?;
 
         // Clear the name field
         self.name = [0u8; 68];
@@ -167,6 +441,9 @@ impl AgentRegistry {
     }
 
     /// Update the agent's inbox URL
+    ///
+    /// With the `require-inbox-path` feature, a non-empty URL must also have a
+    /// path after the host (see [`Self::url_has_path`]).
     #[inline(always)]
     pub fn set_inbox_url(&mut self, inbox_url: &str) -> Result<(), ProgramError> {
         let url_bytes = inbox_url.as_bytes();
@@ -175,6 +452,11 @@ impl AgentRegistry {
             return Err(AgentMailProgramError::InboxUrlTooLong.into());
         }
 
+        #[cfg(feature = "require-inbox-path")]
+        if !inbox_url.is_empty() && !Self::url_has_path(inbox_url) {
+            return Err(AgentMailProgramError::InboxUrlMissingPath.into());
+        }
+
         // Clear the inbox_url field
         self.inbox_url = [0u8; 260];
 
@@ -188,6 +470,19 @@ impl AgentRegistry {
         Ok(())
     }
 
+    /// Whether `url` has a non-empty path after the host
+    ///
+    /// The host ends at the first `/`, `?` or `#` after the scheme, so
+    /// `https://host`, `https://host/` and `https://host?q` have no path.
+    #[inline(always)]
+    pub fn url_has_path(url: &str) -> bool {
+        let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+        let host_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+        let path = &rest[host_end..];
+        let path_end = path.find(['?', '#']).unwrap_or(path.len());
+        path[..path_end].len() > 1
+    }
+
     /// Get the agent's name as a string
     #[inline(always)]
     pub fn get_name(&self) -> Result<alloc::string::String, ProgramError> {
@@ -222,31 +517,1052 @@ impl AgentRegistry {
             .map_err(|_| AgentMailProgramError::InvalidUtf8.into())
     }
 
+    /// Get the agent's name for display, never failing
+    ///
+    /// Clamps an oversized length prefix to `MAX_NAME_LEN` and replaces
+    /// invalid UTF-8. Use `get_name` wherever correctness matters.
+    #[inline(always)]
+    pub fn get_name_lossy(&self) -> alloc::string::String {
+        Self::decode_lossy(&self.name, Self::MAX_NAME_LEN)
+    }
+
+    /// Get the agent's inbox URL for display, never failing
+    ///
+    /// Clamps an oversized length prefix to `MAX_INBOX_URL_LEN` and replaces
+    /// invalid UTF-8. Use `get_inbox_url` wherever correctness matters.
+    #[inline(always)]
+    pub fn get_inbox_url_lossy(&self) -> alloc::string::String {
+        Self::decode_lossy(&self.inbox_url, Self::MAX_INBOX_URL_LEN)
+    }
+
+    #[inline(always)]
+    fn decode_lossy(field: &[u8], max_len: usize) -> alloc::string::String {
+        let len = u32::from_le_bytes([field[0], field[1], field[2], field[3]]) as usize;
+        let len = len.min(max_len);
+        alloc::string::String::from_utf8_lossy(&field[4..4 + len]).into_owned()
+    }
+
+    /// Append an additional inbox URL after any existing ones
+    #[inline(always)]
+    pub fn add_inbox_url(&mut self, inbox_url: &str) -> Result<(), ProgramError> {
+        let url_bytes = inbox_url.as_bytes();
+
+        if url_bytes.len() > Self::MAX_ADDITIONAL_INBOX_URL_LEN {
+            return Err(AgentMailProgramError::InboxUrlTooLong.into());
+        }
+
+        let index = self.url_count as usize;
+        if index >= Self::MAX_INBOX_URLS {
+            return Err(AgentMailProgramError::TooManyInboxUrls.into());
+        }
+
+        let slot = &mut self.inbox_urls[index];
+        *slot = [0u8; 132];
+        slot[..4].copy_from_slice(&(url_bytes.len() as u32).to_le_bytes());
+        slot[4..4 + url_bytes.len()].copy_from_slice(url_bytes);
+        self.url_count += 1;
+
+        Ok(())
+    }
+
+    /// Replace the additional inbox URLs; nothing changes if any is rejected
+    #[inline(always)]
+    pub fn set_inbox_urls(
+        &mut self,
+        inbox_urls: &[alloc::string::String],
+    ) -> Result<(), ProgramError> {
+        if inbox_urls.len() > Self::MAX_INBOX_URLS {
+            return Err(AgentMailProgramError::TooManyInboxUrls.into());
+        }
+        if inbox_urls
+            .iter()
+            .any(|url| url.len() > Self::MAX_ADDITIONAL_INBOX_URL_LEN)
+        {
+            return Err(AgentMailProgramError::InboxUrlTooLong.into());
+        }
+
+        self.inbox_urls = [[0u8; 132]; 4];
+        self.url_count = 0;
+        for inbox_url in inbox_urls {
+            self.add_inbox_url(inbox_url)?;
+        }
+        Ok(())
+    }
+
+    /// Whether the additional inbox URLs are exactly `inbox_urls`, in order
+    #[inline(always)]
+    pub fn inbox_urls_eq(&self, inbox_urls: &[alloc::string::String]) -> bool {
+        let count = self.url_count as usize;
+        count == inbox_urls.len()
+            && self.inbox_urls[..count]
+                .iter()
+                .zip(inbox_urls)
+                .all(|(slot, url)| Self::field_eq(slot, url))
+    }
+
+    /// Get the additional inbox URLs in priority order
+    #[inline(always)]
+    pub fn get_inbox_urls(&self) -> Result<Vec<alloc::string::String>, ProgramError> {
+        let count = self.url_count as usize;
+        if count > Self::MAX_INBOX_URLS {
+            return Err(AgentMailProgramError::InvalidAccountData.into());
+        }
+
+        let mut urls = Vec::with_capacity(count);
+        for slot in &self.inbox_urls[..count] {
+            let len = u32::from_le_bytes([slot[0], slot[1], slot[2], slot[3]]) as usize;
+            if len > Self::MAX_ADDITIONAL_INBOX_URL_LEN {
+                return Err(AgentMailProgramError::InvalidInboxUrlLength.into());
+            }
+            let url = alloc::string::String::from_utf8(slot[4..4 + len].to_vec())
+                .map_err(|_| AgentMailProgramError::InvalidUtf8)?;
+            urls.push(url);
+        }
+
+        Ok(urls)
+    }
+
+    /// Set the off-chain metadata URI and the hash committing to its contents
+    ///
+    /// An empty URI with a zeroed hash clears the commitment.
+    #[inline(always)]
+    pub fn set_metadata(&mut self, uri: &str, hash: [u8; 32]) -> Result<(), ProgramError> {
+        let uri_bytes = uri.as_bytes();
+
+        if uri_bytes.len() > Self::MAX_METADATA_URI_LEN {
+            return Err(AgentMailProgramError::MetadataUriTooLong.into());
+        }
+
+        // Clear the metadata_uri field
+        self.metadata_uri = [0u8; 132];
+
+        // Set length prefix (4 bytes, little-endian)
+        let len_bytes = (uri_bytes.len() as u32).to_le_bytes();
+        self.metadata_uri[..4].copy_from_slice(&len_bytes);
+
+        // Copy URI data
+        self.metadata_uri[4..4 + uri_bytes.len()].copy_from_slice(uri_bytes);
+
+        self.metadata_hash = hash;
+
+        Ok(())
+    }
+
+    /// Get the off-chain metadata URI as a string
+    #[inline(always)]
+    pub fn get_metadata_uri(&self) -> Result<alloc::string::String, ProgramError> {
+        let len = u32::from_le_bytes([
+            self.metadata_uri[0],
+            self.metadata_uri[1],
+            self.metadata_uri[2],
+            self.metadata_uri[3],
+        ]) as usize;
+
+        if len > Self::MAX_METADATA_URI_LEN {
+            return Err(AgentMailProgramError::InvalidAccountData.into());
+        }
+
+        let uri_bytes = &self.metadata_uri[4..4 + len];
+        alloc::string::String::from_utf8(uri_bytes.to_vec())
+            .map_err(|_| AgentMailProgramError::InvalidUtf8.into())
+    }
+
+    /// Get the hash committing to the off-chain metadata
+    #[inline(always)]
+    pub fn get_metadata_hash(&self) -> [u8; 32] {
+        self.metadata_hash
+    }
+
+    /// Whether writing these values would leave the registry unchanged
+    ///
+    /// A `None` argument means that field is left as is.
+    #[inline(always)]
+    pub fn is_unchanged(
+        &self,
+        name: Option<&str>,
+        inbox_url: Option<&str>,
+        metadata: Option<(&str, [u8; 32])>,
+    ) -> bool {
+        name.is_none_or(|name| Self::field_eq(&self.name, name))
+            && inbox_url.is_none_or(|inbox_url| Self::field_eq(&self.inbox_url, inbox_url))
+            && metadata.is_none_or(|(uri, hash)| {
+                Self::field_eq(&self.metadata_uri, uri) && self.metadata_hash == hash
+            })
+    }
+
+    /// Compare a length-prefixed field against `value` without allocating
+    fn field_eq(field: &[u8], value: &str) -> bool {
+        let value = value.as_bytes();
+        field.len() >= 4 + value.len()
+            && field[..4] == (value.len() as u32).to_le_bytes()
+            && &field[4..4 + value.len()] == value
+    }
+
+    /// Whether this registry was written with an older layout version
+    #[inline(always)]
+    pub fn needs_migration(&self) -> bool {
+        !Self::is_current(self.version)
+    }
+
+    /// Whether the agent is accepting new conversations
+    ///
+    /// Registries written before version 5 have no flag and always accept.
+    #[inline(always)]
+    pub fn is_accepting(&self) -> bool {
+        self.version < 5 || self.accepting != 0
+    }
+
+    /// Set whether the agent is accepting new conversations
+    ///
+    /// Also stamps the current version, since the version 4 and 5 layouts only
+    /// differ in the meaning of this byte. SetAccepting never sees a version 4
+    /// account, whose size check fails; MigrateRegistry upgrades those.
+    #[inline(always)]
+    pub fn set_accepting(&mut self, accepting: bool) {
+        self.accepting = accepting as u8;
+        self.version = Self::VERSION;
+    }
+
+    /// Whether the agent has soft-deregistered
+    #[inline(always)]
+    pub fn is_deleted(&self) -> bool {
+        self.deleted != 0
+    }
+
+    /// Set or clear the soft-deregistration flag
+    #[inline(always)]
+    pub fn set_deleted(&mut self, deleted: bool) {
+        self.deleted = deleted as u8;
+    }
+
+    /// Get the fleet operator, or `None` if the agent has none
+    #[inline(always)]
+    pub fn get_operator(&self) -> Option<&Address> {
+        (self.operator.as_ref() != [0u8; 32]).then_some(&self.operator)
+    }
+
+    /// Set the fleet operator; all zeroes clears it
+    #[inline(always)]
+    pub fn set_operator(&mut self, operator: &Address) {
+        self.operator = *operator;
+    }
+
+    /// Get the highest update nonce accepted so far, 0 if none
+    #[inline(always)]
+    pub fn get_last_nonce(&self) -> u64 {
+        self.last_nonce
+    }
+
+    /// Record `nonce` as the last accepted update nonce
+    ///
+    /// # Errors
+    /// `StaleNonce` unless `nonce` is strictly greater than the last one.
+    #[inline(always)]
+    pub fn advance_nonce(&mut self, nonce: u64) -> Result<(), ProgramError> {
+        if nonce <= self.last_nonce {
+            return Err(AgentMailProgramError::StaleNonce.into());
+        }
+        self.last_nonce = nonce;
+        Ok(())
+    }
+
+    /// Validate a MIME type: 1 to 32 printable ASCII bytes containing a `/`
+    #[inline(always)]
+    pub fn validate_mime_type(mime_type: &str) -> Result<(), ProgramError> {
+        let bytes = mime_type.as_bytes();
+        if bytes.is_empty()
+            || bytes.len() > Self::MAX_MIME_TYPE_LEN
+            || !bytes.iter().all(u8::is_ascii_graphic)
+            || !bytes.contains(&b'/')
+        {
+            return Err(AgentMailProgramError::InvalidMimeType.into());
+        }
+        Ok(())
+    }
+
+    /// A stored MIME type without its zero padding
+    #[inline(always)]
+    fn mime_type_bytes(slot: &[u8; 32]) -> &[u8] {
+        let len = slot.iter().position(|&b| b == 0).unwrap_or(slot.len());
+        &slot[..len]
+    }
+
+    /// Number of populated `accepted_mime_types` slots
+    #[inline(always)]
+    fn mime_type_count(&self) -> usize {
+        self.accepted_mime_types
+            .iter()
+            .take_while(|slot| slot[0] != 0)
+            .count()
+    }
+
+    /// Whether the agent lists `mime_type`, ignoring ASCII case
+    #[inline(always)]
+    pub fn accepts_mime_type(&self, mime_type: &str) -> bool {
+        self.accepted_mime_types[..self.mime_type_count()]
+            .iter()
+            .any(|slot| Self::mime_type_bytes(slot).eq_ignore_ascii_case(mime_type.as_bytes()))
+    }
+
+    /// Append an accepted MIME type; one already listed is left as is
+    #[inline(always)]
+    pub fn add_mime_type(&mut self, mime_type: &str) -> Result<(), ProgramError> {
+        Self::validate_mime_type(mime_type)?;
+        if self.accepts_mime_type(mime_type) {
+            return Ok(());
+        }
+
+        let index = self.mime_type_count();
+        if index >= Self::MAX_MIME_TYPES {
+            return Err(AgentMailProgramError::TooManyMimeTypes.into());
+        }
+
+        let slot = &mut self.accepted_mime_types[index];
+        *slot = [0u8; 32];
+        slot[..mime_type.len()].copy_from_slice(mime_type.as_bytes());
+
+        Ok(())
+    }
+
+    /// Remove an accepted MIME type, keeping the rest in order
+    ///
+    /// Returns whether it was listed.
+    #[inline(always)]
+    pub fn remove_mime_type(&mut self, mime_type: &str) -> bool {
+        let count = self.mime_type_count();
+        let Some(index) = self.accepted_mime_types[..count].iter().position(|slot| {
+            Self::mime_type_bytes(slot).eq_ignore_ascii_case(mime_type.as_bytes())
+        }) else {
+            return false;
+        };
+
+        self.accepted_mime_types
+            .copy_within(index + 1..count, index);
+        self.accepted_mime_types[count - 1] = [0u8; 32];
+        true
+    }
+
+    /// Replace the accepted MIME types; nothing changes if any is rejected
+    #[inline(always)]
+    pub fn set_mime_types(
+        &mut self,
+        mime_types: &[alloc::string::String],
+    ) -> Result<(), ProgramError> {
+        if mime_types.len() > Self::MAX_MIME_TYPES {
+            return Err(AgentMailProgramError::TooManyMimeTypes.into());
+        }
+        for mime_type in mime_types {
+            Self::validate_mime_type(mime_type)?;
+        }
+
+        self.accepted_mime_types = [[0u8; 32]; 4];
+        for mime_type in mime_types {
+            self.add_mime_type(mime_type)?;
+        }
+        Ok(())
+    }
+
+    /// Whether the accepted MIME types are exactly `mime_types`, in order
+    #[inline(always)]
+    pub fn mime_types_eq(&self, mime_types: &[alloc::string::String]) -> bool {
+        let count = self.mime_type_count();
+        count == mime_types.len()
+            && self.accepted_mime_types[..count]
+                .iter()
+                .zip(mime_types)
+                .all(|(slot, mime_type)| Self::mime_type_bytes(slot) == mime_type.as_bytes())
+    }
+
+    /// Get the accepted MIME types in the order they were added
+    #[inline(always)]
+    pub fn get_mime_types(&self) -> Result<Vec<alloc::string::String>, ProgramError> {
+        self.accepted_mime_types[..self.mime_type_count()]
+            .iter()
+            .map(|slot| {
+                alloc::string::String::from_utf8(Self::mime_type_bytes(slot).to_vec())
+                    .map_err(|_| AgentMailProgramError::InvalidUtf8.into())
+            })
+            .collect()
+    }
+
+    /// Get the slot at which the registry was created
+    #[inline(always)]
+    pub fn get_created_slot(&self) -> u64 {
+        self.created_slot
+    }
+
+    /// Get the registration timestamp
+    #[inline(always)]
+    pub fn get_created_at(&self) -> i64 {
+        self.created_at
+    }
+
+    /// Get the last update timestamp
+    #[inline(always)]
+    pub fn get_updated_at(&self) -> i64 {
+        self.updated_at
+    }
+
     /// Update the updated_at timestamp
+    ///
+    /// Validators may report a clock slightly behind the previous slot's, so
+    /// a timestamp earlier than the stored one is clamped rather than
+    /// rejected: `updated_at` never moves backward and never precedes
+    /// `created_at`. Equal timestamps are accepted as-is.
     #[inline(always)]
     pub fn touch(&mut self, timestamp: i64) {
-        self.updated_at = timestamp;
+        self.updated_at = timestamp.max(self.updated_at).max(self.created_at);
+    }
+
+    /// Carry history over from `previous`, the soft-deregistered registry this
+    /// one re-registers in place of
+    ///
+    /// `previous.updated_at` is when the agent left. Within
+    /// [`Self::REREGISTER_GRACE_PERIOD`] of it, `created_at` and `created_slot`
+    /// are kept, so a quick return (e.g. key rotation) keeps its tenure.
+    /// `last_nonce` is always kept, so a re-registration never reopens
+    /// nonces the agent already spent. Returns whether the tenure was kept.
+    #[inline(always)]
+    pub fn preserve_history(&mut self, previous: &AgentRegistry, timestamp: i64) -> bool {
+        self.last_nonce = previous.last_nonce;
+
+        let within_grace =
+            timestamp.saturating_sub(previous.updated_at) <= Self::REREGISTER_GRACE_PERIOD;
+        if within_grace {
+            self.created_at = previous.created_at;
+            self.created_slot = previous.created_slot;
+        }
+        within_grace
     }
 }
 
-// Unit tests disabled in favor of comprehensive LiteSVM integration tests
-#[cfg(disabled_unit_tests)]
+// Pure state logic only; anything that needs an AccountView is covered by
+// the LiteSVM integration tests
+#[cfg(test)]
 mod tests {
     use super::*;
-    use alloc::string::ToString;
+    use alloc::borrow::ToOwned;
 
     fn create_test_registry() -> AgentRegistry {
-        let authority = Address::new_from_array([1u8; 32]);
         AgentRegistry::new(
             255,
-            authority,
+            Address::new_from_array([1u8; 32]),
             "nix",
             "https://nix.example.com/inbox",
-            1707523200, // 2026-02-10 timestamp
+            1707523200,
+            1,
         )
         .unwrap()
     }
 
+    #[test]
+    fn test_from_account_mut_matches_byte_path() {
+        let registry = create_test_registry();
+        let mut view_bytes = registry.to_bytes();
+        let mut byte_path = registry.to_bytes();
+
+        let view = AgentRegistry::from_account_mut(&mut view_bytes).unwrap();
+        view.set_name("renamed").unwrap();
+        view.set_inbox_url("https://renamed.example.com/inbox")
+            .unwrap();
+        view.touch(1707523300);
+
+        let mut updated = registry;
+        updated.set_name("renamed").unwrap();
+        updated
+            .set_inbox_url("https://renamed.example.com/inbox")
+            .unwrap();
+        updated.touch(1707523300);
+        updated.write_to_slice(&mut byte_path).unwrap();
+
+        assert_eq!(view_bytes, byte_path);
+    }
+
+    #[test]
+    fn test_validate_pda_for_authority() {
+        let authority = Address::new_from_array([1u8; 32]);
+        let other = Address::new_from_array([2u8; 32]);
+        let (pda, bump) =
+            Address::find_program_address(&[AgentRegistry::PREFIX, authority.as_ref()], &crate::ID);
+
+        for bump in [None, Some(bump)] {
+            assert!(
+                AgentRegistry::validate_pda_for_authority(&pda, &authority, &crate::ID, bump)
+                    .is_ok()
+            );
+            assert_eq!(
+                AgentRegistry::validate_pda_for_authority(&pda, &other, &crate::ID, bump),
+                Err(ProgramError::InvalidSeeds)
+            );
+        }
+    }
+
+    #[test]
+    fn test_agent_registry_name_min_len() {
+        let mut registry = create_test_registry();
+
+        assert_eq!(
+            registry.set_name("a"),
+            Err(AgentMailProgramError::NameTooShort.into())
+        );
+        assert!(registry.set_name("ab").is_ok());
+        assert_eq!(registry.get_name().unwrap(), "ab");
+    }
+
+    #[test]
+    fn test_url_has_path() {
+        assert!(AgentRegistry::url_has_path("https://host/inbox"));
+        assert!(AgentRegistry::url_has_path("https://host:8443/inbox?v=1"));
+        assert!(!AgentRegistry::url_has_path("https://host"));
+        assert!(!AgentRegistry::url_has_path("https://host/"));
+        assert!(!AgentRegistry::url_has_path("https://host?inbox=1"));
+        assert!(!AgentRegistry::url_has_path("https://host#/inbox"));
+    }
+
+    #[test]
+    fn test_set_inbox_url_path_check() {
+        let mut registry = create_test_registry();
+
+        registry.set_inbox_url("https://host/inbox").unwrap();
+        assert_eq!(registry.get_inbox_url().unwrap(), "https://host/inbox");
+
+        let result = registry.set_inbox_url("https://host");
+        if cfg!(feature = "require-inbox-path") {
+            assert_eq!(
+                result,
+                Err(AgentMailProgramError::InboxUrlMissingPath.into())
+            );
+            assert_eq!(registry.get_inbox_url().unwrap(), "https://host/inbox");
+        } else {
+            assert_eq!(result, Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_touch_clamps_backward_clock() {
+        let mut registry = create_test_registry();
+
+        // A clock slightly behind created_at is clamped, not rejected
+        registry.touch(registry.get_created_at() - 2);
+        assert_eq!(registry.get_updated_at(), registry.get_created_at());
+
+        // Equal timestamps are accepted
+        registry.touch(registry.get_created_at());
+        assert_eq!(registry.get_updated_at(), registry.get_created_at());
+
+        // A later touch still advances, and a backward one cannot undo it
+        registry.touch(1707523300);
+        registry.touch(1707523299);
+        assert_eq!(registry.get_updated_at(), 1707523300);
+    }
+
+    #[test]
+    fn test_is_unchanged() {
+        let mut registry = create_test_registry();
+        registry.set_name("agent").unwrap();
+        registry.set_inbox_url("https://example.com/inbox").unwrap();
+        registry.set_metadata("ipfs://meta", [7u8; 32]).unwrap();
+
+        assert!(registry.is_unchanged(Some("agent"), Some("https://example.com/inbox"), None));
+        assert!(registry.is_unchanged(
+            Some("agent"),
+            Some("https://example.com/inbox"),
+            Some(("ipfs://meta", [7u8; 32]))
+        ));
+        assert!(!registry.is_unchanged(Some("agent2"), Some("https://example.com/inbox"), None));
+        assert!(!registry.is_unchanged(Some("agen"), Some("https://example.com/inbox"), None));
+        assert!(!registry.is_unchanged(Some("agent"), Some("https://example.com/other"), None));
+        assert!(!registry.is_unchanged(
+            Some("agent"),
+            Some("https://example.com/inbox"),
+            Some(("ipfs://meta", [8u8; 32]))
+        ));
+        assert!(registry.is_unchanged(None, None, None));
+        assert!(!registry.is_unchanged(None, Some("https://example.com/other"), None));
+    }
+
+    #[test]
+    fn test_needs_migration() {
+        let mut registry = create_test_registry();
+        assert!(!registry.needs_migration());
+        assert!(AgentRegistry::is_current(AgentRegistry::VERSION));
+
+        registry.version = 1;
+        assert!(registry.needs_migration());
+        assert!(!AgentRegistry::is_current(1));
+    }
+
+    #[test]
+    fn test_accepting_defaults_to_true_and_toggles() {
+        let mut registry = create_test_registry();
+        assert!(registry.is_accepting());
+
+        registry.set_accepting(false);
+        assert!(!registry.is_accepting());
+        assert_eq!(registry.to_bytes_inner()[3], 0);
+
+        registry.set_accepting(true);
+        assert!(registry.is_accepting());
+        assert_eq!(registry.to_bytes_inner()[3], 1);
+    }
+
+    #[test]
+    fn test_accepting_legacy_version_reads_true() {
+        let mut registry = create_test_registry();
+        registry.version = 4;
+        registry.accepting = 0;
+        assert!(registry.is_accepting());
+
+        registry.set_accepting(false);
+        assert_eq!(registry.version, AgentRegistry::VERSION);
+        assert!(!registry.is_accepting());
+    }
+
+    #[test]
+    fn test_deleted_defaults_to_false_and_toggles() {
+        let mut registry = create_test_registry();
+        assert!(!registry.is_deleted());
+
+        registry.set_deleted(true);
+        assert!(registry.is_deleted());
+        assert_eq!(registry.to_bytes()[AgentRegistry::DELETED_OFFSET], 1);
+
+        registry.set_deleted(false);
+        assert!(!registry.is_deleted());
+        assert_eq!(registry.to_bytes()[AgentRegistry::DELETED_OFFSET], 0);
+    }
+
+    #[test]
+    fn test_advance_nonce_strictly_increasing() {
+        let mut registry = create_test_registry();
+        assert_eq!(registry.get_last_nonce(), 0);
+
+        registry.advance_nonce(5).unwrap();
+        assert_eq!(&registry.to_bytes_inner()[1120..1128], &5u64.to_le_bytes());
+
+        let stale = Err(AgentMailProgramError::StaleNonce.into());
+        assert_eq!(registry.advance_nonce(5), stale);
+        assert_eq!(registry.advance_nonce(4), stale);
+        assert_eq!(registry.get_last_nonce(), 5);
+
+        registry.advance_nonce(6).unwrap();
+        assert_eq!(registry.get_last_nonce(), 6);
+    }
+
+    #[test]
+    fn test_preserve_history_within_grace_period() {
+        let mut previous = create_test_registry();
+        previous.advance_nonce(7).unwrap();
+        previous.set_deleted(true);
+        previous.touch(1707600000);
+
+        let left_at = previous.get_updated_at();
+        let rejoin_at = left_at + AgentRegistry::REREGISTER_GRACE_PERIOD;
+        let mut registry = AgentRegistry::new(
+            255,
+            previous.authority,
+            "nix",
+            "https://nix.example.com/inbox",
+            rejoin_at,
+            9,
+        )
+        .unwrap();
+
+        assert!(registry.preserve_history(&previous, rejoin_at));
+        assert_eq!(registry.get_created_at(), previous.get_created_at());
+        assert_eq!(registry.get_created_slot(), previous.get_created_slot());
+        assert_eq!(registry.get_last_nonce(), 7);
+    }
+
+    #[test]
+    fn test_preserve_history_after_grace_period() {
+        let mut previous = create_test_registry();
+        previous.advance_nonce(7).unwrap();
+        previous.set_deleted(true);
+        previous.touch(1707600000);
+
+        let rejoin_at = previous.get_updated_at() + AgentRegistry::REREGISTER_GRACE_PERIOD + 1;
+        let mut registry = AgentRegistry::new(
+            255,
+            previous.authority,
+            "nix",
+            "https://nix.example.com/inbox",
+            rejoin_at,
+            9,
+        )
+        .unwrap();
+
+        assert!(!registry.preserve_history(&previous, rejoin_at));
+        assert_eq!(registry.get_created_at(), rejoin_at);
+        assert_eq!(registry.get_created_slot(), 9);
+        // Spent nonces stay spent even when tenure restarts
+        assert_eq!(registry.get_last_nonce(), 7);
+    }
+
+    #[test]
+    fn test_operator_set_and_clear() {
+        let mut registry = create_test_registry();
+        assert_eq!(registry.get_operator(), None);
+
+        let operator = Address::new_from_array([9u8; 32]);
+        registry.set_operator(&operator);
+        assert_eq!(registry.get_operator(), Some(&operator));
+        assert_eq!(&registry.to_bytes_inner()[1084..1116], &[9u8; 32]);
+
+        registry.set_operator(&Address::new_from_array([0u8; 32]));
+        assert_eq!(registry.get_operator(), None);
+    }
+
+    #[test]
+    fn test_metadata_set_and_get() {
+        let mut registry = create_test_registry();
+        let hash = [7u8; 32];
+
+        registry
+            .set_metadata(
+                "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+                hash,
+            )
+            .unwrap();
+        assert_eq!(
+            registry.get_metadata_uri().unwrap(),
+            "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+        );
+        assert_eq!(registry.get_metadata_hash(), hash);
+
+        // Survives a serialization round trip
+        let mut bytes = registry.to_bytes();
+        let view = AgentRegistry::from_account_mut(&mut bytes).unwrap();
+        assert_eq!(view.get_metadata_hash(), hash);
+    }
+
+    #[test]
+    fn test_metadata_empty_by_default_and_clearable() {
+        let mut registry = create_test_registry();
+        assert_eq!(registry.get_metadata_uri().unwrap(), "");
+        assert_eq!(registry.get_metadata_hash(), [0u8; 32]);
+
+        registry.set_metadata("ar://profile", [1u8; 32]).unwrap();
+        registry.set_metadata("", [0u8; 32]).unwrap();
+        assert_eq!(registry.get_metadata_uri().unwrap(), "");
+        assert_eq!(registry.get_metadata_hash(), [0u8; 32]);
+    }
+
+    #[test]
+    fn test_metadata_uri_too_long() {
+        let mut registry = create_test_registry();
+        let uri = "a".repeat(AgentRegistry::MAX_METADATA_URI_LEN + 1);
+        assert_eq!(
+            registry.set_metadata(&uri, [1u8; 32]),
+            Err(AgentMailProgramError::MetadataUriTooLong.into())
+        );
+        assert_eq!(registry.get_metadata_hash(), [0u8; 32]);
+    }
+
+    #[test]
+    fn test_lossy_getters_clamp_oversized_length() {
+        let mut registry = create_test_registry();
+        registry.name[..4].copy_from_slice(&200u32.to_le_bytes());
+        registry.inbox_url[..4].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        assert_eq!(
+            registry.get_name(),
+            Err(AgentMailProgramError::InvalidNameLength.into())
+        );
+        let name = registry.get_name_lossy();
+        assert!(name.starts_with("nix"));
+        assert_eq!(name.len(), AgentRegistry::MAX_NAME_LEN);
+
+        let url = registry.get_inbox_url_lossy();
+        assert!(url.starts_with("https://nix.example.com/inbox"));
+        assert_eq!(url.len(), AgentRegistry::MAX_INBOX_URL_LEN);
+    }
+
+    #[test]
+    fn test_lossy_getters_replace_invalid_utf8() {
+        let mut registry = create_test_registry();
+        registry.name[4] = 0xFF;
+
+        assert_eq!(
+            registry.get_name(),
+            Err(AgentMailProgramError::InvalidUtf8.into())
+        );
+        assert_eq!(registry.get_name_lossy(), "\u{FFFD}ix");
+        assert_eq!(
+            registry.get_inbox_url_lossy(),
+            "https://nix.example.com/inbox"
+        );
+    }
+
+    #[test]
+    fn test_add_inbox_urls_in_order() {
+        let mut registry = create_test_registry();
+        assert!(registry.get_inbox_urls().unwrap().is_empty());
+
+        registry
+            .add_inbox_url("https://a.example.com/inbox")
+            .unwrap();
+        registry
+            .add_inbox_url("https://b.example.com/inbox")
+            .unwrap();
+
+        assert_eq!(
+            registry.get_inbox_urls().unwrap(),
+            vec!["https://a.example.com/inbox", "https://b.example.com/inbox"]
+        );
+
+        // Survives a serialization round trip
+        let mut bytes = registry.to_bytes();
+        let view = AgentRegistry::from_account_mut(&mut bytes).unwrap();
+        assert_eq!(view.get_inbox_urls().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_add_inbox_url_past_limit() {
+        let mut registry = create_test_registry();
+        for i in 0..AgentRegistry::MAX_INBOX_URLS {
+            registry
+                .add_inbox_url(&alloc::format!("https://{i}.example.com/inbox"))
+                .unwrap();
+        }
+
+        assert_eq!(
+            registry.add_inbox_url("https://extra.example.com/inbox"),
+            Err(AgentMailProgramError::TooManyInboxUrls.into())
+        );
+        assert_eq!(
+            registry.get_inbox_urls().unwrap().len(),
+            AgentRegistry::MAX_INBOX_URLS
+        );
+    }
+
+    #[test]
+    fn test_set_inbox_urls_replaces_list() {
+        let mut registry = create_test_registry();
+        let urls = [
+            "https://a.example.com/inbox".to_owned(),
+            "https://b.example.com/inbox".to_owned(),
+        ];
+        registry.set_inbox_urls(&urls).unwrap();
+        assert!(registry.inbox_urls_eq(&urls));
+        assert!(!registry.inbox_urls_eq(&urls[..1]));
+
+        // A shorter list clears the trailing slots
+        registry.set_inbox_urls(&urls[1..]).unwrap();
+        assert_eq!(
+            registry.get_inbox_urls().unwrap(),
+            vec!["https://b.example.com/inbox"]
+        );
+        assert_eq!(&registry.inbox_urls[1..], &[[0u8; 132]; 3]);
+
+        // A rejected list leaves the stored one alone
+        let too_long = alloc::format!("https://{}", "a".repeat(121));
+        assert_eq!(
+            registry.set_inbox_urls(&[too_long]),
+            Err(AgentMailProgramError::InboxUrlTooLong.into())
+        );
+        let too_many = [
+            urls[0].clone(),
+            urls[0].clone(),
+            urls[0].clone(),
+            urls[0].clone(),
+            urls[0].clone(),
+        ];
+        assert_eq!(
+            registry.set_inbox_urls(&too_many),
+            Err(AgentMailProgramError::TooManyInboxUrls.into())
+        );
+        assert!(registry.inbox_urls_eq(&urls[1..]));
+
+        // An empty list clears them
+        registry.set_inbox_urls(&[]).unwrap();
+        assert!(registry.get_inbox_urls().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_add_inbox_url_too_long() {
+        let mut registry = create_test_registry();
+        let url = alloc::format!("https://{}", "a".repeat(121));
+        assert_eq!(url.len(), 129);
+        assert_eq!(
+            registry.add_inbox_url(&url),
+            Err(AgentMailProgramError::InboxUrlTooLong.into())
+        );
+        assert_eq!(registry.url_count, 0);
+    }
+
+    #[test]
+    fn test_add_and_remove_mime_types() {
+        let mut registry = create_test_registry();
+        assert!(registry.get_mime_types().unwrap().is_empty());
+
+        registry.add_mime_type("application/json").unwrap();
+        registry.add_mime_type("text/markdown").unwrap();
+        registry.add_mime_type("text/plain").unwrap();
+        // Already listed, compared case-insensitively
+        registry.add_mime_type("Text/Markdown").unwrap();
+        assert_eq!(
+            registry.get_mime_types().unwrap(),
+            ["application/json", "text/markdown", "text/plain"]
+        );
+        assert!(registry.accepts_mime_type("APPLICATION/JSON"));
+
+        // Removing from the middle keeps the rest in order
+        assert!(registry.remove_mime_type("text/markdown"));
+        assert!(!registry.remove_mime_type("text/markdown"));
+        assert_eq!(
+            registry.get_mime_types().unwrap(),
+            ["application/json", "text/plain"]
+        );
+        assert!(!registry.accepts_mime_type("text/markdown"));
+        assert_eq!(&registry.to_bytes_inner()[1128 + 64..1256], &[0u8; 64]);
+
+        assert!(registry.remove_mime_type("application/json"));
+        assert!(registry.remove_mime_type("text/plain"));
+        assert!(registry.get_mime_types().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_add_mime_type_past_limit() {
+        let mut registry = create_test_registry();
+        for subtype in ["json", "xml", "pdf", "zip"] {
+            registry
+                .add_mime_type(&alloc::format!("application/{subtype}"))
+                .unwrap();
+        }
+        assert_eq!(
+            registry.add_mime_type("text/plain"),
+            Err(AgentMailProgramError::TooManyMimeTypes.into())
+        );
+        assert_eq!(
+            registry.get_mime_types().unwrap().len(),
+            AgentRegistry::MAX_MIME_TYPES
+        );
+
+        // Freeing a slot makes room again
+        assert!(registry.remove_mime_type("application/xml"));
+        registry.add_mime_type("text/plain").unwrap();
+        assert_eq!(
+            registry.get_mime_types().unwrap(),
+            [
+                "application/json",
+                "application/pdf",
+                "application/zip",
+                "text/plain"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_add_mime_type_invalid() {
+        let mut registry = create_test_registry();
+        let too_long = alloc::format!("application/{}", "x".repeat(21));
+        assert_eq!(too_long.len(), 33);
+        for mime_type in ["", "json", "text/ plain", "text/plain\0", too_long.as_str()] {
+            assert_eq!(
+                registry.add_mime_type(mime_type),
+                Err(AgentMailProgramError::InvalidMimeType.into()),
+                "{mime_type:?}"
+            );
+        }
+        assert!(registry.get_mime_types().unwrap().is_empty());
+
+        let longest = alloc::format!("application/{}", "x".repeat(20));
+        registry.add_mime_type(&longest).unwrap();
+        assert_eq!(registry.get_mime_types().unwrap(), [longest]);
+    }
+
+    #[test]
+    fn test_set_mime_types_all_or_nothing() {
+        let mut registry = create_test_registry();
+        registry.add_mime_type("text/plain").unwrap();
+
+        let invalid = ["application/json".to_owned(), "json".to_owned()];
+        assert_eq!(
+            registry.set_mime_types(&invalid),
+            Err(AgentMailProgramError::InvalidMimeType.into())
+        );
+        let too_many = ["a/a", "b/b", "c/c", "d/d", "e/e"].map(str::to_owned);
+        assert_eq!(
+            registry.set_mime_types(&too_many),
+            Err(AgentMailProgramError::TooManyMimeTypes.into())
+        );
+        assert_eq!(registry.get_mime_types().unwrap(), ["text/plain"]);
+
+        let replacement = ["application/json".to_owned(), "text/markdown".to_owned()];
+        registry.set_mime_types(&replacement).unwrap();
+        assert!(registry.mime_types_eq(&replacement));
+        registry.set_mime_types(&[]).unwrap();
+        assert!(registry.get_mime_types().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_from_account_mut_too_small() {
+        let mut bytes = create_test_registry().to_bytes();
+        bytes.pop();
+        assert_eq!(bytes.len(), AgentRegistry::LEN - 1);
+        assert_eq!(
+            AgentRegistry::from_account_mut(&mut bytes),
+            Err(AgentMailProgramError::AccountTooSmall.into())
+        );
+    }
+
+    #[test]
+    fn test_from_account_mut_too_large() {
+        let mut bytes = create_test_registry().to_bytes();
+        bytes.push(0);
+        assert_eq!(bytes.len(), AgentRegistry::LEN + 1);
+        assert_eq!(
+            AgentRegistry::from_account_mut(&mut bytes),
+            Err(AgentMailProgramError::AccountTooLarge.into())
+        );
+    }
+
+    #[test]
+    fn test_from_account_mut_wrong_discriminator() {
+        let mut bytes = create_test_registry().to_bytes();
+        bytes[0] = 0;
+        assert_eq!(
+            AgentRegistry::from_account_mut(&mut bytes),
+            Err(AgentMailProgramError::InvalidDiscriminator.into())
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_too_small() {
+        let bytes = create_test_registry().to_bytes();
+        assert_eq!(
+            AgentRegistry::from_bytes(&bytes[..AgentRegistry::LEN - 1]),
+            Err(AgentMailProgramError::AccountTooSmall.into())
+        );
+        assert_eq!(
+            AgentRegistry::from_bytes(&[]),
+            Err(AgentMailProgramError::AccountTooSmall.into())
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_wrong_discriminator() {
+        let mut bytes = create_test_registry().to_bytes();
+        bytes[0] = 2;
+        assert_eq!(
+            AgentRegistry::from_bytes(&bytes),
+            Err(AgentMailProgramError::InvalidDiscriminator.into())
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_invalid_utf8_surfaces_on_read() {
+        let mut bytes = create_test_registry().to_bytes();
+        // First name byte, after discriminator, version and the 8 + 32 byte header
+        bytes[2 + 8 + 32 + 4] = 0xFF;
+        let registry = AgentRegistry::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            registry.get_name(),
+            Err(AgentMailProgramError::InvalidUtf8.into())
+        );
+    }
+
     #[test]
     fn test_agent_registry_new() {
         let authority = Address::new_from_array([1u8; 32]);
@@ -256,49 +1572,45 @@ mod tests {
             "test-agent",
             "https://test.example.com/inbox",
             1707523200,
+            7,
         )
         .unwrap();
 
         assert_eq!(registry.bump, 200);
-        assert_eq!(registry.version, 1);
+        assert_eq!(registry.version, AgentRegistry::VERSION);
         assert_eq!(registry.authority, authority);
-        assert_eq!(registry.created_at, 1707523200);
-        assert_eq!(registry.updated_at, 1707523200);
-        assert_eq!(registry.get_name().unwrap(), "test-agent".to_string());
+        assert_eq!(registry.get_created_at(), 1707523200);
+        assert_eq!(registry.get_updated_at(), 1707523200);
+        assert_eq!(registry.get_created_slot(), 7);
+        assert_eq!(registry.get_name().unwrap(), "test-agent");
         assert_eq!(
             registry.get_inbox_url().unwrap(),
-            "https://test.example.com/inbox".to_string()
+            "https://test.example.com/inbox"
         );
     }
 
     #[test]
-    fn test_agent_registry_validate_authority_success() {
+    fn test_agent_registry_validate_authority() {
         let registry = create_test_registry();
-        let valid_authority = Address::new_from_array([1u8; 32]);
-
-        assert!(registry.validate_authority(&valid_authority).is_ok());
-    }
 
-    #[test]
-    fn test_agent_registry_validate_authority_invalid() {
-        let registry = create_test_registry();
-        let invalid_authority = Address::new_from_array([99u8; 32]);
-
-        let result = registry.validate_authority(&invalid_authority);
-        assert_eq!(result, Err(AgentMailProgramError::InvalidAuthority.into()));
+        assert!(registry
+            .validate_authority(&Address::new_from_array([1u8; 32]))
+            .is_ok());
+        assert_eq!(
+            registry.validate_authority(&Address::new_from_array([99u8; 32])),
+            Err(AgentMailProgramError::InvalidAuthority.into())
+        );
     }
 
     #[test]
     fn test_agent_registry_name_too_long() {
-        let authority = Address::new_from_array([1u8; 32]);
-        let long_name = "a".repeat(65); // Exceeds MAX_NAME_LEN
-
         let result = AgentRegistry::new(
             200,
-            authority,
-            &long_name,
+            Address::new_from_array([1u8; 32]),
+            &"a".repeat(AgentRegistry::MAX_NAME_LEN + 1),
             "https://test.example.com/inbox",
             1707523200,
+            1,
         );
 
         assert_eq!(result, Err(AgentMailProgramError::NameTooLong.into()));
@@ -306,36 +1618,18 @@ mod tests {
 
     #[test]
     fn test_agent_registry_inbox_url_too_long() {
-        let authority = Address::new_from_array([1u8; 32]);
-        let long_url = "https://".to_string() + &"a".repeat(250); // Exceeds MAX_INBOX_URL_LEN
-
-        let result = AgentRegistry::new(200, authority, "test", &long_url, 1707523200);
-
-        assert_eq!(result, Err(AgentMailProgramError::InboxUrlTooLong.into()));
-    }
-
-    #[test]
-    fn test_agent_registry_update_fields() {
-        let mut registry = create_test_registry();
-        let original_updated_at = registry.updated_at;
+        let long_url = alloc::format!("https://{}", "a".repeat(AgentRegistry::MAX_INBOX_URL_LEN));
 
-        // Update name
-        registry.set_name("new-name").unwrap();
-        assert_eq!(registry.get_name().unwrap(), "new-name".to_string());
-
-        // Update inbox URL
-        registry
-            .set_inbox_url("https://new.example.com/inbox")
-            .unwrap();
-        assert_eq!(
-            registry.get_inbox_url().unwrap(),
-            "https://new.example.com/inbox".to_string()
+        let result = AgentRegistry::new(
+            200,
+            Address::new_from_array([1u8; 32]),
+            "test",
+            &long_url,
+            1707523200,
+            1,
         );
 
-        // Touch timestamp
-        registry.touch(1707523300);
-        assert_eq!(registry.updated_at, 1707523300);
-        assert!(registry.updated_at > original_updated_at);
+        assert_eq!(result, Err(AgentMailProgramError::InboxUrlTooLong.into()));
     }
 
     #[test]
@@ -345,42 +1639,24 @@ mod tests {
 
         assert_eq!(bytes.len(), AgentRegistry::DATA_LEN);
         assert_eq!(bytes[0], 255); // bump
-        assert_eq!(bytes[1], 1); // version
-        assert_eq!(&bytes[2..8], &[0u8; 6]); // padding
+        assert_eq!(bytes[1], AgentRegistry::VERSION);
+        assert_eq!(bytes[2], 0); // url_count
+        assert_eq!(bytes[3], 1); // accepting
+        assert_eq!(bytes[4], 0); // deleted
+        assert_eq!(&bytes[5..8], &[0u8; 3]); // padding
         assert_eq!(&bytes[8..40], &[1u8; 32]); // authority
     }
 
     #[test]
-    fn test_agent_registry_to_bytes() {
+    fn test_agent_registry_to_bytes_round_trip() {
         let registry = create_test_registry();
         let bytes = registry.to_bytes();
 
         assert_eq!(bytes.len(), AgentRegistry::LEN);
-        assert_eq!(bytes[0], AgentRegistry::DISCRIMINATOR);
+        assert_eq!(&bytes[..1], AgentRegistry::DISCRIMINATOR);
         assert_eq!(bytes[1], AgentRegistry::VERSION);
         assert_eq!(bytes[2], 255); // bump
-    }
-
-    #[test]
-    fn test_agent_registry_from_bytes() {
-        let registry = create_test_registry();
-        let bytes = registry.to_bytes();
-
-        let deserialized = AgentRegistry::from_bytes(&bytes).unwrap();
-
-        assert_eq!(deserialized.bump, registry.bump);
-        assert_eq!(deserialized.version, registry.version);
-        assert_eq!(deserialized.authority, registry.authority);
-        assert_eq!(deserialized.created_at, registry.created_at);
-        assert_eq!(deserialized.updated_at, registry.updated_at);
-        assert_eq!(
-            deserialized.get_name().unwrap(),
-            registry.get_name().unwrap()
-        );
-        assert_eq!(
-            deserialized.get_inbox_url().unwrap(),
-            registry.get_inbox_url().unwrap()
-        );
+        assert_eq!(AgentRegistry::from_bytes(&bytes), Ok(&registry));
     }
 
     #[test]
@@ -1,3 +1,5 @@
 pub mod agent_registry;
+pub mod rent_vault;
 
 pub use agent_registry::*;
+pub use rent_vault::*;
@@ -0,0 +1,186 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use codama::CodamaAccount;
+use pinocchio::{account::AccountView, cpi::Seed, error::ProgramError, Address};
+
+use crate::assert_field_offsets;
+use crate::assert_no_padding;
+use crate::errors::AgentMailProgramError;
+use crate::traits::{
+    AccountDeserialize, AccountSerialize, AccountSize, AgentMailAccountDiscriminators,
+    Discriminator, PdaSeeds, Versioned,
+};
+use crate::utils::verify_current_program_account;
+
+/// Rent vault account state
+///
+/// Pools the rent reclaimed from registries that DeregisterAgent closes into
+/// it, for protocols that fund registrations for their agents. Any key can
+/// create its own vault; only that key can withdraw from it.
+///
+/// # PDA Seeds
+/// `[b"rent_vault", vault_authority.as_ref()]`
+///
+/// # Layout (struct 34 bytes, account 36 bytes)
+/// - bump: 1 byte
+/// - version: 1 byte
+/// - authority: 32 bytes (Address, the only key that can withdraw)
+///
+/// # Alignment
+/// Packed to alignment 1 like AgentRegistry, since it is viewed in place
+/// after the discriminator and version.
+#[derive(Clone, Copy, Debug, PartialEq, CodamaAccount)]
+#[repr(C, packed)]
+pub struct RentVault {
+    pub bump: u8,
+    pub version: u8,
+    pub authority: Address,
+}
+
+assert_no_padding!(RentVault, 1 + 1 + 32);
+
+const _: () = assert!(core::mem::align_of::<RentVault>() == 1);
+
+// Offsets within the struct; account data adds 2 for discriminator and version
+assert_field_offsets!(RentVault, {
+    bump: 0,
+    version: 1,
+    authority: 2,
+});
+
+impl Discriminator for RentVault {
+    const DISCRIMINATOR: &'static [u8] =
+        &[AgentMailAccountDiscriminators::RentVaultDiscriminator as u8];
+}
+
+impl Versioned for RentVault {
+    const VERSION: u8 = 1;
+}
+
+impl AccountSize for RentVault {
+    const DATA_LEN: usize = 1 + 1 + 32; // 34 bytes total
+}
+
+impl AccountDeserialize for RentVault {}
+
+impl AccountSerialize for RentVault {
+    #[inline(always)]
+    fn to_bytes_inner(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::DATA_LEN);
+        data.push(self.bump);
+        data.push(self.version);
+        data.extend_from_slice(self.authority.as_ref());
+        data
+    }
+}
+
+impl PdaSeeds for RentVault {
+    const PREFIX: &'static [u8] = b"rent_vault";
+
+    #[inline(always)]
+    fn seeds(&self) -> Vec<&[u8]> {
+        vec![Self::PREFIX, self.authority.as_ref()]
+    }
+
+    #[inline(always)]
+    fn seeds_with_bump<'a>(&'a self, bump: &'a [u8; 1]) -> Vec<Seed<'a>> {
+        vec![
+            Seed::from(Self::PREFIX),
+            Seed::from(self.authority.as_ref()),
+            Seed::from(bump.as_slice()),
+        ]
+    }
+}
+
+impl RentVault {
+    /// Create a new RentVault instance
+    #[inline(always)]
+    pub fn new(bump: u8, authority: Address) -> Self {
+        Self {
+            bump,
+            version: Self::VERSION,
+            authority,
+        }
+    }
+
+    /// Validate that `account` is a rent vault owned by this program
+    ///
+    /// Checks the owner and discriminator, then re-derives the PDA from the
+    /// stored authority and bump, so a program-owned account of another
+    /// type, or vault data copied to another address, is rejected. Returns
+    /// the vault so the caller can check its authority.
+    #[inline(always)]
+    pub fn from_account(
+        data: &[u8],
+        account: &AccountView,
+        program_id: &Address,
+    ) -> Result<Self, ProgramError> {
+        verify_current_program_account(account)?;
+        let vault = *Self::from_bytes(data)?;
+        if data.len() != Self::LEN {
+            return Err(AgentMailProgramError::InvalidAccountData.into());
+        }
+
+        let derived = Address::create_program_address(
+            &[Self::PREFIX, vault.authority.as_ref(), &[vault.bump]],
+            program_id,
+        )
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+        if account.address() != &derived {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        Ok(vault)
+    }
+
+    /// Validate that the provided authority matches the vault's authority
+    #[inline(always)]
+    pub fn validate_authority(&self, provided_authority: &Address) -> Result<(), ProgramError> {
+        if self.authority != *provided_authority {
+            return Err(AgentMailProgramError::InvalidAuthority.into());
+        }
+        Ok(())
+    }
+}
+
+// Pure state logic only; anything that needs an AccountView is covered by
+// the LiteSVM integration tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rent_vault_round_trip() {
+        let vault = RentVault::new(254, Address::new_from_array([7u8; 32]));
+        let bytes = vault.to_bytes();
+
+        assert_eq!(bytes.len(), RentVault::LEN);
+        assert_eq!(
+            bytes[0],
+            AgentMailAccountDiscriminators::RentVaultDiscriminator as u8
+        );
+        assert_eq!(RentVault::from_bytes(&bytes).unwrap(), &vault);
+    }
+
+    #[test]
+    fn test_rent_vault_rejects_registry_discriminator() {
+        let mut bytes = RentVault::new(254, Address::new_from_array([7u8; 32])).to_bytes();
+        bytes[0] = AgentMailAccountDiscriminators::AgentRegistryDiscriminator as u8;
+
+        assert_eq!(
+            RentVault::from_bytes(&bytes),
+            Err(AgentMailProgramError::InvalidDiscriminator.into())
+        );
+    }
+
+    #[test]
+    fn test_rent_vault_validate_authority() {
+        let authority = Address::new_from_array([7u8; 32]);
+        let vault = RentVault::new(254, authority);
+
+        assert!(vault.validate_authority(&authority).is_ok());
+        assert_eq!(
+            vault.validate_authority(&Address::new_from_array([8u8; 32])),
+            Err(AgentMailProgramError::InvalidAuthority.into())
+        );
+    }
+}
@@ -1,16 +1,29 @@
 use alloc::vec::Vec;
 use pinocchio::error::ProgramError;
 
-use crate::{require_len, validate_discriminator};
+use crate::errors::AgentMailProgramError;
 
 /// Discriminator for account types
+///
+/// AgentMail accounts use a single byte. Programs that keep these accounts
+/// beside Anchor accounts can use an 8-byte discriminator instead, so the
+/// prefixes cannot collide; lengths and offsets follow `DISCRIMINATOR.len()`.
 pub trait Discriminator {
-    const DISCRIMINATOR: u8;
+    const DISCRIMINATOR: &'static [u8];
+
+    /// Length of the discriminator and version prefix
+    const HEADER_LEN: usize = Self::DISCRIMINATOR.len() + 1;
 }
 
 /// Version marker for account types
 pub trait Versioned {
     const VERSION: u8;
+
+    /// Whether a stored version byte matches the current layout
+    #[inline(always)]
+    fn is_current(version_byte: u8) -> bool {
+        version_byte == Self::VERSION
+    }
 }
 
 /// Account size constants
@@ -19,62 +32,109 @@ pub trait AccountSize: Discriminator + Versioned + Sized {
     const DATA_LEN: usize;
 
     /// Total size including discriminator and version
-    const LEN: usize = 1 + 1 + Self::DATA_LEN;
+    const LEN: usize = Self::HEADER_LEN + Self::DATA_LEN;
 }
 
 /// Zero-copy account deserialization
 pub trait AccountDeserialize: AccountSize {
+    /// Validate length and discriminator, with a distinct error for each
+    #[inline(always)]
+    fn validate_header(data: &[u8]) -> Result<(), ProgramError> {
+        if data.len() < Self::LEN {
+            return Err(AgentMailProgramError::AccountTooSmall.into());
+        }
+        if !data.starts_with(Self::DISCRIMINATOR) {
+            return Err(AgentMailProgramError::InvalidDiscriminator.into());
+        }
+        Ok(())
+    }
+
     /// Zero-copy read from byte slice (validates discriminator, skips version)
     #[inline(always)]
     fn from_bytes(data: &[u8]) -> Result<&Self, ProgramError> {
-        require_len!(data, Self::LEN);
-        validate_discriminator!(data, Self::DISCRIMINATOR);
+        Self::validate_header(data)?;
 
-        // Skip discriminator (byte 0) and version (byte 1)
-        unsafe { Self::from_bytes_unchecked(&data[2..]) }
+        // Skip discriminator and version
+        unsafe { Self::from_bytes_unchecked(&data[Self::HEADER_LEN..]) }
     }
 
     /// Zero-copy read without discriminator validation
     ///
+    /// Account data follows a header, so views are rarely aligned for
+    /// anything wider than a byte; misaligned data is rejected rather than
+    /// read, and account structs are packed to alignment 1.
+    ///
     /// # Safety
-    /// Caller must ensure data is valid, properly sized, and aligned.
+    /// Caller must ensure data is valid for `Self`.
     /// Struct must be `#[repr(C)]` with no padding.
     #[inline(always)]
     unsafe fn from_bytes_unchecked(data: &[u8]) -> Result<&Self, ProgramError> {
         if data.len() < Self::DATA_LEN {
             return Err(ProgramError::InvalidAccountData);
         }
+        if data.as_ptr().align_offset(core::mem::align_of::<Self>()) != 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
         Ok(&*(data.as_ptr() as *const Self))
     }
 
     /// Mutable zero-copy access
     #[inline(always)]
     fn from_bytes_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
-        require_len!(data, Self::LEN);
-        validate_discriminator!(data, Self::DISCRIMINATOR);
+        Self::validate_header(data)?;
 
-        // Skip discriminator (byte 0) and version (byte 1)
-        unsafe { Self::from_bytes_mut_unchecked(&mut data[2..]) }
+        // Skip discriminator and version
+        unsafe { Self::from_bytes_mut_unchecked(&mut data[Self::HEADER_LEN..]) }
     }
 
-    /// Mutable zero-copy access without validation
+    /// Mutable zero-copy access without discriminator validation
+    ///
+    /// Rejects misaligned data like `from_bytes_unchecked`.
     ///
     /// # Safety
-    /// Caller must ensure data is valid, properly sized, and aligned.
+    /// Caller must ensure data is valid for `Self`.
     /// Struct must be `#[repr(C)]` with no padding.
     #[inline(always)]
     unsafe fn from_bytes_mut_unchecked(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
         if data.len() < Self::DATA_LEN {
             return Err(ProgramError::InvalidAccountData);
         }
+        if data.as_ptr().align_offset(core::mem::align_of::<Self>()) != 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
         Ok(&mut *(data.as_mut_ptr() as *mut Self))
     }
 }
 
 /// AgentMail account discriminator values
+///
+/// The single source of truth for account type bytes; the client mirrors it
+/// as `AccountKind`.
 #[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum AgentMailAccountDiscriminators {
     AgentRegistryDiscriminator = 1,
+    RentVaultDiscriminator = 2,
+}
+
+impl AgentMailAccountDiscriminators {
+    /// Every live account discriminator
+    pub const ALL: [u8; 2] = [
+        Self::AgentRegistryDiscriminator as u8,
+        Self::RentVaultDiscriminator as u8,
+    ];
+}
+
+impl TryFrom<u8> for AgentMailAccountDiscriminators {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::AgentRegistryDiscriminator),
+            2 => Ok(Self::RentVaultDiscriminator),
+            _ => Err(AgentMailProgramError::InvalidDiscriminator.into()),
+        }
+    }
 }
 
 /// Account serialization with discriminator and version prefix
@@ -86,8 +146,8 @@ pub trait AccountSerialize: Discriminator + Versioned {
     #[inline(always)]
     fn to_bytes(&self) -> Vec<u8> {
         let inner = self.to_bytes_inner();
-        let mut data = Vec::with_capacity(1 + 1 + inner.len());
-        data.push(Self::DISCRIMINATOR);
+        let mut data = Vec::with_capacity(Self::HEADER_LEN + inner.len());
+        data.extend_from_slice(Self::DISCRIMINATOR);
         data.push(Self::VERSION);
         data.extend_from_slice(&inner);
         data
@@ -104,3 +164,130 @@ pub trait AccountSerialize: Discriminator + Versioned {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_discriminators_round_trip() {
+        for byte in AgentMailAccountDiscriminators::ALL {
+            let kind = AgentMailAccountDiscriminators::try_from(byte).unwrap();
+            assert_eq!(kind as u8, byte);
+        }
+        assert_eq!(
+            AgentMailAccountDiscriminators::try_from(0),
+            Err(AgentMailProgramError::InvalidDiscriminator.into())
+        );
+    }
+
+    /// Byte-aligned sample account, generic over its discriminator
+    #[derive(Debug, PartialEq)]
+    #[repr(C)]
+    struct Sample<const WIDE: bool> {
+        value: [u8; 4],
+    }
+
+    impl<const WIDE: bool> Discriminator for Sample<WIDE> {
+        const DISCRIMINATOR: &'static [u8] = if WIDE {
+            &[0xA1, 0xB2, 0xC3, 0xD4, 0xE5, 0xF6, 0x07, 0x18]
+        } else {
+            &[7]
+        };
+    }
+
+    impl<const WIDE: bool> Versioned for Sample<WIDE> {
+        const VERSION: u8 = 3;
+    }
+
+    impl<const WIDE: bool> AccountSize for Sample<WIDE> {
+        const DATA_LEN: usize = 4;
+    }
+
+    impl<const WIDE: bool> AccountDeserialize for Sample<WIDE> {}
+
+    impl<const WIDE: bool> AccountSerialize for Sample<WIDE> {
+        fn to_bytes_inner(&self) -> Vec<u8> {
+            self.value.to_vec()
+        }
+    }
+
+    fn assert_round_trip<const WIDE: bool>(discriminator_len: usize) {
+        let sample = Sample::<WIDE> {
+            value: [1, 2, 3, 4],
+        };
+        let mut bytes = sample.to_bytes();
+
+        assert_eq!(Sample::<WIDE>::HEADER_LEN, discriminator_len + 1);
+        assert_eq!(bytes.len(), Sample::<WIDE>::LEN);
+        assert_eq!(&bytes[..discriminator_len], Sample::<WIDE>::DISCRIMINATOR);
+        assert_eq!(bytes[discriminator_len], 3);
+        assert_eq!(Sample::<WIDE>::from_bytes(&bytes), Ok(&sample));
+
+        Sample::<WIDE>::from_bytes_mut(&mut bytes).unwrap().value = [9; 4];
+        assert_eq!(&bytes[discriminator_len + 1..], &[9; 4]);
+
+        bytes[discriminator_len - 1] ^= 0xFF;
+        assert_eq!(
+            Sample::<WIDE>::from_bytes(&bytes),
+            Err(AgentMailProgramError::InvalidDiscriminator.into())
+        );
+    }
+
+    #[test]
+    fn test_one_byte_discriminator_round_trip() {
+        assert_round_trip::<false>(1);
+    }
+
+    #[test]
+    fn test_eight_byte_discriminator_round_trip() {
+        assert_round_trip::<true>(8);
+
+        // A one-byte account never validates as the wide one
+        let narrow = Sample::<false> { value: [0; 4] }.to_bytes();
+        assert!(Sample::<true>::from_bytes(&narrow).is_err());
+    }
+
+    /// Account with a `u32` field, which needs 4-byte alignment
+    ///
+    /// Only ever viewed through account bytes, never constructed.
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct Wide {
+        value: u32,
+    }
+
+    impl Discriminator for Wide {
+        const DISCRIMINATOR: &'static [u8] = &[7];
+    }
+
+    impl Versioned for Wide {
+        const VERSION: u8 = 1;
+    }
+
+    impl AccountSize for Wide {
+        const DATA_LEN: usize = 4;
+    }
+
+    impl AccountDeserialize for Wide {}
+
+    #[test]
+    fn test_misaligned_view_rejected() {
+        #[repr(C, align(8))]
+        struct Buffer([u8; 8]);
+        let mut buffer = Buffer([7, 1, 42, 0, 0, 0, 42, 0]);
+
+        // The header leaves the data at offset 2, which a u32 cannot sit at
+        assert_eq!(
+            Wide::from_bytes(&buffer.0[..6]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            Wide::from_bytes_mut(&mut buffer.0[..6]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+
+        let aligned = unsafe { Wide::from_bytes_unchecked(&buffer.0[4..]) }.unwrap();
+        assert_eq!(aligned.value, u32::from_ne_bytes([0, 0, 42, 0]));
+    }
+}
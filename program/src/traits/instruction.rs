@@ -1,13 +1,72 @@
 use pinocchio::{account::AccountView, error::ProgramError};
 
+use crate::errors::AgentMailProgramError;
+
 /// Discriminators for the AgentMail Program instructions.
 #[repr(u8)]
 pub enum AgentMailInstructionDiscriminators {
     RegisterAgent = 3,
     UpdateAgent = 4,
     DeregisterAgent = 5,
+    UpdateAgentBatch = 6,
+    SetAccepting = 7,
+    MigrateRegistry = 8,
+    SetOperator = 9,
+    CheckRegistryBatch = 10,
+    SoftDeregisterAgent = 11,
+    ReactivateAgent = 12,
+    AllocateRegistry = 13,
+    InitializeRegistry = 14,
+    CreateRentVault = 15,
+    WithdrawRentVault = 16,
+}
+
+impl AgentMailInstructionDiscriminators {
+    /// Every live instruction discriminator
+    pub const ALL: [u8; 14] = [
+        Self::RegisterAgent as u8,
+        Self::UpdateAgent as u8,
+        Self::DeregisterAgent as u8,
+        Self::UpdateAgentBatch as u8,
+        Self::SetAccepting as u8,
+        Self::MigrateRegistry as u8,
+        Self::SetOperator as u8,
+        Self::CheckRegistryBatch as u8,
+        Self::SoftDeregisterAgent as u8,
+        Self::ReactivateAgent as u8,
+        Self::AllocateRegistry as u8,
+        Self::InitializeRegistry as u8,
+        Self::CreateRentVault as u8,
+        Self::WithdrawRentVault as u8,
+    ];
+
+    /// Discriminators of the removed counter template instructions
+    pub const REMOVED: [u8; 3] = [0, 1, 2];
 }
 
+// Fail the build if a discriminator is reused or collides with a removed one
+const _: () = {
+    let all = AgentMailInstructionDiscriminators::ALL;
+    let removed = AgentMailInstructionDiscriminators::REMOVED;
+    let mut i = 0;
+    while i < all.len() {
+        let mut j = i + 1;
+        while j < all.len() {
+            assert!(all[i] != all[j], "duplicate instruction discriminator");
+            j += 1;
+        }
+        let mut k = 0;
+        while k < removed.len() {
+            assert!(
+                all[i] != removed[k],
+                "discriminator reuses a removed instruction"
+            );
+            k += 1;
+        }
+        i += 1;
+    }
+};
+
 impl TryFrom<u8> for AgentMailInstructionDiscriminators {
     type Error = ProgramError;
 
@@ -16,6 +75,19 @@ impl TryFrom<u8> for AgentMailInstructionDiscriminators {
             3 => Ok(Self::RegisterAgent),
             4 => Ok(Self::UpdateAgent),
             5 => Ok(Self::DeregisterAgent),
+            6 => Ok(Self::UpdateAgentBatch),
+            7 => Ok(Self::SetAccepting),
+            8 => Ok(Self::MigrateRegistry),
+            9 => Ok(Self::SetOperator),
+            10 => Ok(Self::CheckRegistryBatch),
+            11 => Ok(Self::SoftDeregisterAgent),
+            12 => Ok(Self::ReactivateAgent),
+            13 => Ok(Self::AllocateRegistry),
+            14 => Ok(Self::InitializeRegistry),
+            15 => Ok(Self::CreateRentVault),
+            16 => Ok(Self::WithdrawRentVault),
+            // Leftover counter template instructions (CreateCounter, Increment, CloseCounter)
+            0..=2 => Err(AgentMailProgramError::CounterInstructionsRemoved.into()),
             _ => Err(ProgramError::InvalidInstructionData),
         }
     }
@@ -35,6 +107,10 @@ pub trait InstructionAccounts<'a>:
 pub trait InstructionData<'a>: Sized + TryFrom<&'a [u8], Error = ProgramError> {
     /// Expected length of instruction data
     const LEN: usize;
+
+    /// Length of the shortest data that parses, excluding the instruction
+    /// discriminator; variable-length data overrides the fixed `LEN`
+    const MIN_LEN: usize = Self::LEN;
 }
 
 /// Full instruction combining accounts and data
@@ -58,3 +134,144 @@ pub trait Instruction<'a>: Sized {
         Ok(Self::from((accounts, data)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::definition::AgentMailInstruction;
+    use alloc::{string::String, vec, vec::Vec};
+
+    /// Maps each IDL instruction to its processor discriminator.
+    /// Exhaustive so a new IDL variant fails to compile until it is added here.
+    fn processor_discriminator(ix: &AgentMailInstruction) -> u8 {
+        match ix {
+            AgentMailInstruction::RegisterAgent { .. } => {
+                AgentMailInstructionDiscriminators::RegisterAgent as u8
+            }
+            AgentMailInstruction::UpdateAgent { .. } => {
+                AgentMailInstructionDiscriminators::UpdateAgent as u8
+            }
+            AgentMailInstruction::DeregisterAgent { .. } => {
+                AgentMailInstructionDiscriminators::DeregisterAgent as u8
+            }
+            AgentMailInstruction::UpdateAgentBatch { .. } => {
+                AgentMailInstructionDiscriminators::UpdateAgentBatch as u8
+            }
+            AgentMailInstruction::SetAccepting { .. } => {
+                AgentMailInstructionDiscriminators::SetAccepting as u8
+            }
+            AgentMailInstruction::MigrateRegistry { .. } => {
+                AgentMailInstructionDiscriminators::MigrateRegistry as u8
+            }
+            AgentMailInstruction::SetOperator { .. } => {
+                AgentMailInstructionDiscriminators::SetOperator as u8
+            }
+            AgentMailInstruction::CheckRegistryBatch { .. } => {
+                AgentMailInstructionDiscriminators::CheckRegistryBatch as u8
+            }
+            AgentMailInstruction::SoftDeregisterAgent { .. } => {
+                AgentMailInstructionDiscriminators::SoftDeregisterAgent as u8
+            }
+            AgentMailInstruction::ReactivateAgent { .. } => {
+                AgentMailInstructionDiscriminators::ReactivateAgent as u8
+            }
+            AgentMailInstruction::AllocateRegistry { .. } => {
+                AgentMailInstructionDiscriminators::AllocateRegistry as u8
+            }
+            AgentMailInstruction::InitializeRegistry { .. } => {
+                AgentMailInstructionDiscriminators::InitializeRegistry as u8
+            }
+            AgentMailInstruction::CreateRentVault { .. } => {
+                AgentMailInstructionDiscriminators::CreateRentVault as u8
+            }
+            AgentMailInstruction::WithdrawRentVault { .. } => {
+                AgentMailInstructionDiscriminators::WithdrawRentVault as u8
+            }
+        }
+    }
+
+    /// Reads the tag byte of a `#[repr(C, u8)]` enum value
+    fn idl_discriminator(ix: &AgentMailInstruction) -> u8 {
+        unsafe { *(ix as *const AgentMailInstruction as *const u8) }
+    }
+
+    fn all_idl_instructions() -> Vec<AgentMailInstruction> {
+        vec![
+            AgentMailInstruction::RegisterAgent {
+                bump: 0,
+                name: String::new(),
+                inbox_url: String::new(),
+                metadata: None,
+                operator: None,
+                idempotent: false,
+            },
+            AgentMailInstruction::UpdateAgent {
+                name: String::new(),
+                inbox_url: String::new(),
+                metadata: None,
+                nonce: None,
+            },
+            AgentMailInstruction::DeregisterAgent {},
+            AgentMailInstruction::UpdateAgentBatch {
+                entries: Vec::new(),
+            },
+            AgentMailInstruction::SetAccepting { accepting: true },
+            AgentMailInstruction::MigrateRegistry {},
+            AgentMailInstruction::SetOperator {
+                operator: pinocchio::Address::new_from_array([0u8; 32]),
+            },
+            AgentMailInstruction::CheckRegistryBatch {
+                authorities: Vec::new(),
+            },
+            AgentMailInstruction::SoftDeregisterAgent {},
+            AgentMailInstruction::ReactivateAgent {},
+            AgentMailInstruction::AllocateRegistry { bump: 0 },
+            AgentMailInstruction::InitializeRegistry {
+                bump: 0,
+                name: String::new(),
+                inbox_url: String::new(),
+                metadata: None,
+                operator: None,
+                idempotent: false,
+            },
+            AgentMailInstruction::CreateRentVault { bump: 0 },
+            AgentMailInstruction::WithdrawRentVault { amount: 0 },
+        ]
+    }
+
+    #[test]
+    fn test_instruction_discriminators_unique() {
+        let idl: Vec<u8> = all_idl_instructions()
+            .iter()
+            .map(idl_discriminator)
+            .collect();
+
+        for (i, a) in idl.iter().enumerate() {
+            assert!(!idl[i + 1..].contains(a), "duplicate IDL discriminator {a}");
+            assert!(!AgentMailInstructionDiscriminators::REMOVED.contains(a));
+        }
+        for (i, a) in AgentMailInstructionDiscriminators::ALL.iter().enumerate() {
+            assert!(!AgentMailInstructionDiscriminators::ALL[i + 1..].contains(a));
+        }
+        assert_eq!(idl.len(), AgentMailInstructionDiscriminators::ALL.len());
+    }
+
+    #[test]
+    fn test_idl_and_processor_discriminators_match() {
+        for ix in all_idl_instructions() {
+            let discriminator = idl_discriminator(&ix);
+            assert_eq!(discriminator, processor_discriminator(&ix));
+            assert!(AgentMailInstructionDiscriminators::try_from(discriminator).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_try_from_accepts_only_live_discriminators() {
+        for byte in 0..=u8::MAX {
+            assert_eq!(
+                AgentMailInstructionDiscriminators::try_from(byte).is_ok(),
+                AgentMailInstructionDiscriminators::ALL.contains(&byte)
+            );
+        }
+    }
+}
@@ -22,6 +22,10 @@ pub trait PdaSeeds {
     }
 
     /// Validate that account matches derived PDA
+    ///
+    /// Both the canonical bump and the account address must match, so a
+    /// non-canonical bump that derives some other valid address is rejected
+    /// with `InvalidSeeds` as well.
     #[inline(always)]
     fn validate_pda(
         &self,
@@ -103,3 +103,19 @@ pub fn verify_empty(account: &AccountView) -> Result<(), ProgramError> {
     }
     Ok(())
 }
+
+/// Verify two accounts are distinct, returning an error if they alias.
+///
+/// # Arguments
+/// * `a` - The first account.
+/// * `b` - The second account.
+///
+/// # Returns
+/// * `Result<(), ProgramError>` - The result of the operation
+#[inline(always)]
+pub fn verify_distinct(a: &AccountView, b: &AccountView) -> Result<(), ProgramError> {
+    if a.address() == b.address() {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
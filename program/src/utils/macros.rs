@@ -52,6 +52,30 @@ macro_rules! assert_no_padding {
     };
 }
 
+/// Compile-time assertion that each field sits at its documented offset.
+/// Catches reordered fields that keep the size, which `assert_no_padding!` misses.
+///
+/// # Example
+/// ```ignore
+/// assert_field_offsets!(Counter, { bump: 0, authority: 1, count: 33 });
+/// ```
+#[macro_export]
+macro_rules! assert_field_offsets {
+    ($struct:ty, { $($field:ident: $offset:expr),* $(,)? }) => {
+        $(
+            const _: () = assert!(
+                core::mem::offset_of!($struct, $field) == $offset,
+                concat!(
+                    stringify!($struct),
+                    "::",
+                    stringify!($field),
+                    " offset mismatch - check field order"
+                )
+            );
+        )*
+    };
+}
+
 /// Implement boilerplate `From` and `TryFrom` traits for instruction structs.
 ///
 /// # Example
@@ -80,3 +104,50 @@ macro_rules! impl_instruction {
         }
     };
 }
+
+/// Check each account against its declared roles, returning the first failure.
+///
+/// Roles are checked in the order written, so the layout reads as the
+/// requirement list. Each role runs one `verify_*` helper:
+/// * `signer` - [`verify_signer`](crate::utils::verify_signer)
+/// * `writable` - [`verify_writable`](crate::utils::verify_writable)
+/// * `system_owned` - [`verify_system_account`](crate::utils::verify_system_account)
+/// * `program_owned` - [`verify_current_program_account`](crate::utils::verify_current_program_account)
+/// * `system_program` - [`verify_system_program`](crate::utils::verify_system_program)
+/// * `current_program` - [`verify_current_program`](crate::utils::verify_current_program)
+///
+/// # Example
+/// ```ignore
+/// assert_roles!(
+///     payer: [signer, writable],
+///     system_program: [system_program],
+///     program: [current_program],
+/// );
+/// ```
+#[macro_export]
+macro_rules! assert_roles {
+    ($($account:ident: [$($role:ident),* $(,)?]),* $(,)?) => {
+        $($($crate::assert_roles!(@role $account, $role);)*)*
+    };
+    (@role $account:ident, signer) => {
+        $crate::utils::verify_signer($account)?
+    };
+    (@role $account:ident, writable) => {
+        $crate::utils::verify_writable($account)?
+    };
+    (@role $account:ident, system_owned) => {
+        $crate::utils::verify_system_account($account)?
+    };
+    (@role $account:ident, program_owned) => {
+        $crate::utils::verify_current_program_account($account)?
+    };
+    (@role $account:ident, system_program) => {
+        $crate::utils::verify_system_program($account)?
+    };
+    (@role $account:ident, current_program) => {
+        $crate::utils::verify_current_program($account)?
+    };
+    (@role $account:ident, $role:ident) => {
+        compile_error!(concat!("unknown account role `", stringify!($role), "`"))
+    };
+}
@@ -4,7 +4,7 @@ use pinocchio::{
     sysvars::{rent::Rent, Sysvar},
     ProgramResult,
 };
-use pinocchio_system::instructions::CreateAccount;
+use pinocchio_system::instructions::{CreateAccount, Transfer};
 
 /// Create a PDA account for the given seeds.
 ///
@@ -49,3 +49,38 @@ pub fn close_pda_account(pda_account: &AccountView, recipient: &AccountView) ->
 
     Ok(())
 }
+
+/// Grow a registry account to `new_len`, for layout migrations.
+///
+/// Tops up rent exemption for the new size from `payer` and zeroes the new
+/// bytes. Shrinking is rejected with `InvalidRealloc`.
+pub fn realloc_registry(
+    account: &AccountView,
+    new_len: usize,
+    payer: &AccountView,
+) -> ProgramResult {
+    let old_len = account.data_len();
+    if new_len < old_len {
+        return Err(ProgramError::InvalidRealloc);
+    }
+
+    let rent = Rent::get()?;
+    let required_lamports = rent.try_minimum_balance(new_len)?;
+    let top_up = required_lamports.saturating_sub(account.lamports());
+    if top_up > 0 {
+        Transfer {
+            from: payer,
+            to: account,
+            lamports: top_up,
+        }
+        .invoke()?;
+    }
+
+    account.resize(new_len)?;
+
+    // Zero the grown region so new fields start from their default
+    let mut data = account.try_borrow_mut()?;
+    data[old_len..].fill(0);
+
+    Ok(())
+}
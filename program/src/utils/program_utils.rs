@@ -1,8 +1,31 @@
-use crate::ID as AGENTMAIL_PROGRAM_ID;
-use pinocchio::{account::AccountView, error::ProgramError};
+use crate::{errors::AgentMailProgramError, ID as AGENTMAIL_PROGRAM_ID};
+use pinocchio::{
+    account::AccountView,
+    error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    Address,
+};
+
+/// Addresses of the well-known sysvars, and the sysvar owner itself
+pub const SYSVAR_IDS: [Address; 12] = [
+    Address::from_str_const("Sysvar1111111111111111111111111111111111111"),
+    Address::from_str_const("SysvarC1ock11111111111111111111111111111111"),
+    Address::from_str_const("SysvarEpochRewards1111111111111111111111111"),
+    Address::from_str_const("SysvarEpochSchedu1e111111111111111111111111"),
+    Address::from_str_const("SysvarFees111111111111111111111111111111111"),
+    Address::from_str_const("Sysvar1nstructions1111111111111111111111111"),
+    Address::from_str_const("SysvarLastRestartS1ot1111111111111111111111"),
+    Address::from_str_const("SysvarRecentB1ockHashes11111111111111111111"),
+    Address::from_str_const("SysvarRent111111111111111111111111111111111"),
+    Address::from_str_const("SysvarS1otHashes111111111111111111111111111"),
+    Address::from_str_const("SysvarS1otHistory11111111111111111111111111"),
+    Address::from_str_const("SysvarStakeHistory1111111111111111111111111"),
+];
 
 /// Verify the account is a system program, returning an error if it is not.
 ///
+/// The account must both live at the system program address and be executable.
+///
 /// # Arguments
 /// * `account` - The account to verify.
 ///
@@ -10,7 +33,7 @@ use pinocchio::{account::AccountView, error::ProgramError};
 /// * `Result<(), ProgramError>` - The result of the operation
 #[inline(always)]
 pub fn verify_system_program(account: &AccountView) -> Result<(), ProgramError> {
-    if account.address() != &pinocchio_system::ID {
+    if account.address() != &pinocchio_system::ID || !account.executable() {
         return Err(ProgramError::IncorrectProgramId);
     }
 
@@ -19,6 +42,8 @@ pub fn verify_system_program(account: &AccountView) -> Result<(), ProgramError>
 
 /// Verify the account is the current program, returning an error if it is not.
 ///
+/// The account must both live at the program address and be executable.
+///
 /// # Arguments
 /// * `account` - The account to verify.
 ///
@@ -26,23 +51,107 @@ pub fn verify_system_program(account: &AccountView) -> Result<(), ProgramError>
 /// * `Result<(), ProgramError>` - The result of the operation
 #[inline(always)]
 pub fn verify_current_program(account: &AccountView) -> Result<(), ProgramError> {
-    if account.address() != &AGENTMAIL_PROGRAM_ID {
+    if account.address() != &AGENTMAIL_PROGRAM_ID || !account.executable() {
         return Err(ProgramError::IncorrectProgramId);
     }
 
     Ok(())
 }
 
-/// Get the current blockchain timestamp.
+/// Verify `authority` can own an agent registry, returning an error if not.
+///
+/// The system program, this program and the well-known sysvars can never
+/// act as an agent, so a registry for one of them would be unusable.
+///
+/// # Arguments
+/// * `authority` - The proposed agent authority.
+///
+/// # Returns
+/// * `Result<(), ProgramError>` - `InvalidAuthority` for a reserved address
+#[inline(always)]
+pub fn verify_registrable_authority(authority: &Address) -> Result<(), ProgramError> {
+    if authority == &pinocchio_system::ID
+        || authority == &AGENTMAIL_PROGRAM_ID
+        || SYSVAR_IDS.contains(authority)
+    {
+        return Err(AgentMailProgramError::InvalidAuthority.into());
+    }
+
+    Ok(())
+}
+
+/// Get the current unix timestamp from the Clock sysvar.
 ///
-/// For now, this is a placeholder that returns a fixed timestamp.
-/// In a full implementation, this would access the Clock sysvar.
+/// Like `get_current_slot`, this reads the sysvar through a syscall.
 ///
 /// # Returns
-/// * `Result<i64, ProgramError>` - Unix timestamp or error
+/// * `Result<i64, ProgramError>` - Unix timestamp or `ClockUnavailable`
 #[inline(always)]
 pub fn get_current_timestamp() -> Result<i64, ProgramError> {
-    // TODO: Access Clock sysvar for real timestamp
-    // For hackathon demo, using a fixed recent timestamp
-    Ok(1707523200) // 2026-02-10 00:00:00 UTC
+    Ok(read_clock(Clock::get)?.unix_timestamp)
+}
+
+/// Get the current slot from the Clock sysvar.
+///
+/// `Clock::get` reads the sysvar through a syscall, so instructions do not
+/// take a clock account.
+///
+/// # Returns
+/// * `Result<u64, ProgramError>` - Current slot or `ClockUnavailable`
+#[inline(always)]
+pub fn get_current_slot() -> Result<u64, ProgramError> {
+    Ok(read_clock(Clock::get)?.slot)
+}
+
+/// Read the Clock through `source`, mapping any failure to `ClockUnavailable`.
+///
+/// `source` is `Clock::get` on-chain; tests inject a failing source.
+///
+/// # Returns
+/// * `Result<Clock, ProgramError>` - The Clock or `ClockUnavailable`
+#[inline(always)]
+pub fn read_clock(
+    source: impl FnOnce() -> Result<Clock, ProgramError>,
+) -> Result<Clock, ProgramError> {
+    source().map_err(|_| AgentMailProgramError::ClockUnavailable.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_clock_failure_is_clock_unavailable() {
+        assert_eq!(
+            read_clock(|| Err(ProgramError::UnsupportedSysvar)).map(|clock| clock.slot),
+            Err(AgentMailProgramError::ClockUnavailable.into())
+        );
+    }
+
+    #[test]
+    fn test_verify_registrable_authority() {
+        let reserved = [pinocchio_system::ID, AGENTMAIL_PROGRAM_ID]
+            .into_iter()
+            .chain(SYSVAR_IDS);
+        for authority in reserved {
+            assert_eq!(
+                verify_registrable_authority(&authority),
+                Err(AgentMailProgramError::InvalidAuthority.into())
+            );
+        }
+
+        assert_eq!(
+            verify_registrable_authority(&Address::new_from_array([7u8; 32])),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_read_clock_passes_clock_through() {
+        let clock = Clock {
+            slot: 42,
+            ..Default::default()
+        };
+        assert_eq!(read_clock(|| Ok(clock)).map(|clock| clock.slot), Ok(42));
+    }
 }
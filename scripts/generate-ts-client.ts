@@ -20,6 +20,8 @@ const agentmailCodama = createAgentMailCodamaBuilder(agentmailIdl)
     .appendAccountDiscriminator()
     .appendPdaDerivers()
     .setInstructionAccountDefaultValues()
+    .setOptionalAccountStrategy()
+    .appendUpdateAgentV3()
     .updateInstructionBumps()
     .build();
 
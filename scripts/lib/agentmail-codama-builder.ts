@@ -2,7 +2,9 @@ import { Codama, createFromJson } from 'codama';
 import {
     appendAccountDiscriminator,
     appendPdaDerivers,
+    appendUpdateAgentV3,
     setInstructionAccountDefaultValues,
+    setOptionalAccountStrategy,
     updateInstructionBumps,
 } from './updates';
 
@@ -33,6 +35,16 @@ export class AgentMailCodamaBuilder {
         return this;
     }
 
+    setOptionalAccountStrategy(): this {
+        this.codama = setOptionalAccountStrategy(this.codama);
+        return this;
+    }
+
+    appendUpdateAgentV3(): this {
+        this.codama = appendUpdateAgentV3(this.codama);
+        return this;
+    }
+
     updateInstructionBumps(): this {
         this.codama = updateInstructionBumps(this.codama);
         return this;
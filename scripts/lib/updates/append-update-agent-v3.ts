@@ -0,0 +1,154 @@
+import {
+    Codama,
+    InstructionArgumentNode,
+    InstructionNode,
+    arrayTypeNode,
+    assertIsNode,
+    bottomUpTransformerVisitor,
+    fieldDiscriminatorNode,
+    instructionArgumentNode,
+    numberTypeNode,
+    numberValueNode,
+    prefixedCountNode,
+    sizePrefixTypeNode,
+    stringTypeNode,
+} from 'codama';
+
+const UPDATE_AGENT_DISCRIMINATOR = 4;
+const INSTRUCTION_DATA_V3 = 3;
+
+// Field mask bits, as in UpdateAgentData
+const UPDATE_NAME = 1 << 0;
+const UPDATE_INBOX_URL = 1 << 1;
+const UPDATE_MIME_TYPES = 1 << 2;
+const UPDATE_INBOX_URLS = 1 << 3;
+
+/**
+ * One version 3 UpdateAgent form per field the mask can select.
+ *
+ * The program reads only the fields the mask selects, which the IDL cannot
+ * express under the same discriminator, so each form fixes its mask and
+ * carries just that field.
+ */
+const UPDATE_AGENT_V3_FORMS: { name: string; fields: number; arguments: InstructionArgumentNode[] }[] = [
+    {
+        name: 'updateAgentName',
+        fields: UPDATE_NAME,
+        arguments: [
+            instructionArgumentNode({
+                name: 'name',
+                type: sizePrefixTypeNode(stringTypeNode('utf8'), numberTypeNode('u32')),
+            }),
+        ],
+    },
+    {
+        name: 'updateAgentInboxUrl',
+        fields: UPDATE_INBOX_URL,
+        arguments: [
+            instructionArgumentNode({
+                name: 'inboxUrl',
+                type: sizePrefixTypeNode(stringTypeNode('utf8'), numberTypeNode('u32')),
+            }),
+        ],
+    },
+    {
+        name: 'updateAgentMimeTypes',
+        fields: UPDATE_MIME_TYPES,
+        arguments: [
+            // A u8 count, then each MIME type as a u8 length and its ASCII bytes
+            instructionArgumentNode({
+                name: 'acceptedMimeTypes',
+                type: arrayTypeNode(
+                    sizePrefixTypeNode(stringTypeNode('utf8'), numberTypeNode('u8')),
+                    prefixedCountNode(numberTypeNode('u8')),
+                ),
+            }),
+        ],
+    },
+    {
+        name: 'updateAgentInboxUrls',
+        fields: UPDATE_INBOX_URLS,
+        arguments: [
+            // A u8 count, then each URL as a u32 length and its UTF-8 bytes
+            instructionArgumentNode({
+                name: 'inboxUrls',
+                type: arrayTypeNode(
+                    sizePrefixTypeNode(stringTypeNode('utf8'), numberTypeNode('u32')),
+                    prefixedCountNode(numberTypeNode('u8')),
+                ),
+            }),
+        ],
+    },
+];
+
+/**
+ * Adds the version 3 UpdateAgent forms, which definition.rs cannot declare
+ * since they share UpdateAgent's discriminator.
+ *
+ * Each form keeps UpdateAgent's accounts and trailing metadata and nonce
+ * arguments. They go before updateAgent so instruction identification checks
+ * their version and mask bytes first.
+ */
+export function appendUpdateAgentV3(agentmailCodama: Codama): Codama {
+    agentmailCodama.update(
+        bottomUpTransformerVisitor([
+            {
+                select: '[programNode]',
+                transform: node => {
+                    assertIsNode(node, 'programNode');
+
+                    const index = node.instructions.findIndex(instruction => instruction.name === 'updateAgent');
+                    if (index < 0) {
+                        return node;
+                    }
+                    const updateAgent = node.instructions[index];
+                    const trailing = updateAgent.arguments.filter(
+                        argument => argument.name === 'metadata' || argument.name === 'nonce',
+                    );
+
+                    const forms: InstructionNode[] = UPDATE_AGENT_V3_FORMS.map(form => ({
+                        ...updateAgent,
+                        name: form.name as InstructionNode['name'],
+                        arguments: [
+                            instructionArgumentNode({
+                                name: 'discriminator',
+                                type: numberTypeNode('u8'),
+                                defaultValue: numberValueNode(UPDATE_AGENT_DISCRIMINATOR),
+                                defaultValueStrategy: 'omitted',
+                            }),
+                            instructionArgumentNode({
+                                name: 'dataVersion',
+                                type: numberTypeNode('u8'),
+                                defaultValue: numberValueNode(INSTRUCTION_DATA_V3),
+                                defaultValueStrategy: 'omitted',
+                            }),
+                            instructionArgumentNode({
+                                name: 'fields',
+                                type: numberTypeNode('u8'),
+                                defaultValue: numberValueNode(form.fields),
+                                defaultValueStrategy: 'omitted',
+                            }),
+                            ...form.arguments,
+                            ...trailing,
+                        ],
+                        discriminators: [
+                            fieldDiscriminatorNode('discriminator', 0),
+                            fieldDiscriminatorNode('dataVersion', 1),
+                            fieldDiscriminatorNode('fields', 2),
+                        ],
+                    }));
+
+                    return {
+                        ...node,
+                        instructions: [
+                            ...node.instructions.slice(0, index),
+                            ...forms,
+                            ...node.instructions.slice(index),
+                        ],
+                    };
+                },
+            },
+        ]),
+    );
+    return agentmailCodama;
+}
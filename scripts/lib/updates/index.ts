@@ -1,4 +1,6 @@
 export { appendAccountDiscriminator } from './append-account-discriminator.js';
 export { appendPdaDerivers } from './append-pda-derivers.js';
+export { appendUpdateAgentV3 } from './append-update-agent-v3.js';
 export { setInstructionAccountDefaultValues } from './set-instruction-account-default-values.js';
+export { setOptionalAccountStrategy } from './set-optional-account-strategy.js';
 export { updateInstructionBumps } from './update-instruction-bumps.js';
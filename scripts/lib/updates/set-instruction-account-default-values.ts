@@ -25,6 +25,7 @@ export function setInstructionAccountDefaultValues(pinocchioCounterCodama: Codam
             {
                 account: 'systemProgram',
                 defaultValue: publicKeyValueNode(SYSTEM_PROGRAM_ID),
+                ignoreIfOptional: true,
             },
 
             // PDA Derivations
@@ -0,0 +1,20 @@
+import { Codama, updateInstructionsVisitor } from 'codama';
+
+/**
+ * Omits unset optional accounts instead of filling them with the program ID,
+ * since UpdateAgent and DeregisterAgent tell their forms apart by account
+ * count.
+ */
+export function setOptionalAccountStrategy(agentmailCodama: Codama): Codama {
+    agentmailCodama.update(
+        updateInstructionsVisitor({
+            updateAgent: {
+                optionalAccountStrategy: 'omitted',
+            },
+            deregisterAgent: {
+                optionalAccountStrategy: 'omitted',
+            },
+        }),
+    );
+    return agentmailCodama;
+}
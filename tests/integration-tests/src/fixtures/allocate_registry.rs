@@ -0,0 +1,21 @@
+use agentmail_client::instructions;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+pub fn allocate_registry(
+    payer: &Pubkey,
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    bump: u8,
+) -> Instruction {
+    instructions::allocate_registry(payer, agent_authority, agent_registry, bump)
+}
+
+pub fn initialize_registry(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    bump: u8,
+    name: &str,
+    inbox_url: &str,
+) -> Instruction {
+    instructions::initialize_registry(agent_authority, agent_registry, bump, name, inbox_url)
+}
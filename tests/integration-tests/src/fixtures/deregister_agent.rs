@@ -1,27 +1,63 @@
-use agentmail_client::AGENTMAIL_ID;
-use solana_sdk::{
-    instruction::{AccountMeta, Instruction},
-    pubkey::Pubkey,
+use agentmail_client::instructions;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signer::Signer};
+
+use crate::{
+    fixtures::register_agent::register_agent,
+    utils::{find_agent_registry_pda, InstructionTestFixture, TestContext, TestInstruction},
 };
 
 pub fn deregister_agent(agent_authority: &Pubkey, agent_registry: &Pubkey) -> Instruction {
     // DeregisterAgent instruction discriminator is 5
-    let data = vec![5u8];
-
-    Instruction {
-        program_id: AGENTMAIL_ID,
-        accounts: vec![
-            AccountMeta::new(*agent_authority, true),
-            AccountMeta::new(*agent_registry, false),
-            AccountMeta::new_readonly(AGENTMAIL_ID, false),
-        ],
-        data,
+    instructions::deregister(agent_authority, agent_registry)
+}
+
+/// DeregisterAgent against a freshly registered agent
+pub struct DeregisterAgentFixture;
+
+impl InstructionTestFixture for DeregisterAgentFixture {
+    const INSTRUCTION_NAME: &'static str = "DeregisterAgent";
+
+    fn build_valid(ctx: &mut TestContext) -> TestInstruction {
+        let agent_authority = ctx.create_funded_keypair();
+        let (agent_registry, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+        let register = register_agent(
+            &ctx.payer.pubkey(),
+            &agent_authority.pubkey(),
+            &agent_registry,
+            bump,
+            "departing".to_string(),
+            "https://departing.example.com/inbox".to_string(),
+        );
+        ctx.send_transaction(register, &[&agent_authority]).unwrap();
+
+        TestInstruction {
+            instruction: deregister_agent(&agent_authority.pubkey(), &agent_registry),
+            signers: vec![agent_authority],
+            name: Self::INSTRUCTION_NAME,
+        }
+    }
+
+    fn required_signers() -> &'static [usize] {
+        &[0]
+    }
+
+    fn required_writable() -> &'static [usize] {
+        &[0, 1]
+    }
+
+    fn current_program_index() -> Option<usize> {
+        Some(2)
+    }
+
+    fn data_len() -> usize {
+        1
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use agentmail_client::AGENTMAIL_ID;
 
     #[test]
     fn test_deregister_agent_data_serialization() {
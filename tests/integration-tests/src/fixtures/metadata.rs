@@ -0,0 +1,33 @@
+use solana_sdk::instruction::Instruction;
+
+/// Byte offset of `metadata_uri` within AgentRegistry account data
+pub const METADATA_URI_OFFSET: usize = 922;
+
+/// Byte offset of `metadata_hash` within AgentRegistry account data
+pub const METADATA_HASH_OFFSET: usize = 1054;
+
+/// Append a metadata commitment to a RegisterAgent or UpdateAgent instruction
+pub fn with_metadata(mut instruction: Instruction, uri: &str, hash: [u8; 32]) -> Instruction {
+    instruction.data.push(1);
+    instruction
+        .data
+        .extend_from_slice(&(uri.len() as u32).to_le_bytes());
+    instruction.data.extend_from_slice(uri.as_bytes());
+    instruction.data.extend_from_slice(&hash);
+    instruction
+}
+
+/// Read the stored metadata URI and hash from raw AgentRegistry account data
+pub fn read_metadata(data: &[u8]) -> (String, [u8; 32]) {
+    let uri_len = u32::from_le_bytes(
+        data[METADATA_URI_OFFSET..METADATA_URI_OFFSET + 4]
+            .try_into()
+            .unwrap(),
+    ) as usize;
+    let uri_start = METADATA_URI_OFFSET + 4;
+    let uri = String::from_utf8(data[uri_start..uri_start + uri_len].to_vec()).unwrap();
+    let hash = data[METADATA_HASH_OFFSET..METADATA_HASH_OFFSET + 32]
+        .try_into()
+        .unwrap();
+    (uri, hash)
+}
@@ -0,0 +1,40 @@
+use agentmail_client::instructions;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+/// Size of a version 2 AgentRegistry account, before inbox_urls existed
+pub const LEGACY_V2_ACCOUNT_LEN: usize = 394;
+
+pub fn migrate_registry(
+    payer: &Pubkey,
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+) -> Instruction {
+    instructions::migrate_registry(payer, agent_authority, agent_registry)
+}
+
+/// Build version 2 registry account data for `authority`
+pub fn legacy_v2_registry_data(
+    bump: u8,
+    authority: &Pubkey,
+    name: &str,
+    inbox_url: &str,
+) -> Vec<u8> {
+    let mut data = vec![0u8; LEGACY_V2_ACCOUNT_LEN];
+    data[0] = 1; // AgentRegistry discriminator
+    data[1] = 2; // account version
+    data[2] = bump;
+    data[3] = 2; // data version
+    data[10..42].copy_from_slice(authority.as_ref());
+
+    let name_field = 42;
+    data[name_field..name_field + 4].copy_from_slice(&(name.len() as u32).to_le_bytes());
+    data[name_field + 4..name_field + 4 + name.len()].copy_from_slice(name.as_bytes());
+
+    let inbox_url_field = name_field + 68;
+    data[inbox_url_field..inbox_url_field + 4]
+        .copy_from_slice(&(inbox_url.len() as u32).to_le_bytes());
+    data[inbox_url_field + 4..inbox_url_field + 4 + inbox_url.len()]
+        .copy_from_slice(inbox_url.as_bytes());
+
+    data
+}
@@ -1,4 +1,12 @@
 // AgentMail instruction fixtures
+pub mod allocate_registry;
 pub mod deregister_agent;
+pub mod metadata;
+pub mod migrate_registry;
 pub mod register_agent;
+pub mod rent_vault;
+pub mod set_accepting;
+pub mod set_operator;
+pub mod soft_deregister_agent;
 pub mod update_agent;
+pub mod update_agent_batch;
@@ -1,10 +1,9 @@
-use agentmail_client::AGENTMAIL_ID;
-use solana_sdk::{
-    instruction::{AccountMeta, Instruction},
-    pubkey::Pubkey,
-};
+use agentmail_client::instructions;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signer::Signer};
 
-const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::from_str_const("11111111111111111111111111111112");
+use crate::utils::{find_agent_registry_pda, InstructionTestFixture, TestContext, TestInstruction};
+
+pub const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::from_str_const("11111111111111111111111111111111");
 
 pub fn register_agent(
     payer: &Pubkey,
@@ -14,32 +13,83 @@ pub fn register_agent(
     name: String,
     inbox_url: String,
 ) -> Instruction {
-    // RegisterAgent instruction discriminator is 3
-    let mut data = vec![3u8];
-
-    // Add bump
-    data.push(bump);
-
-    // Add name (length-prefixed string)
-    let name_bytes = name.as_bytes();
-    data.extend_from_slice(&(name_bytes.len() as u32).to_le_bytes());
-    data.extend_from_slice(name_bytes);
-
-    // Add inbox_url (length-prefixed string)
-    let inbox_url_bytes = inbox_url.as_bytes();
-    data.extend_from_slice(&(inbox_url_bytes.len() as u32).to_le_bytes());
-    data.extend_from_slice(inbox_url_bytes);
-
-    Instruction {
-        program_id: AGENTMAIL_ID,
-        accounts: vec![
-            AccountMeta::new(*payer, true),
-            AccountMeta::new_readonly(*agent_authority, true),
-            AccountMeta::new(*agent_registry, false),
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
-            AccountMeta::new_readonly(AGENTMAIL_ID, false),
-        ],
-        data,
+    instructions::register(
+        payer,
+        agent_authority,
+        agent_registry,
+        bump,
+        &name,
+        &inbox_url,
+    )
+}
+
+pub fn register_agent_idempotent(
+    payer: &Pubkey,
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    bump: u8,
+    name: String,
+    inbox_url: String,
+) -> Instruction {
+    instructions::register_idempotent(
+        payer,
+        agent_authority,
+        agent_registry,
+        bump,
+        &name,
+        &inbox_url,
+    )
+}
+
+/// RegisterAgent with a payer distinct from the fee payer, so clearing the
+/// payer's signer or writable flag is not masked by the transaction
+pub struct RegisterAgentFixture;
+
+impl RegisterAgentFixture {
+    const NAME: &'static str = "roles";
+    const INBOX_URL: &'static str = "https://roles.example.com/inbox";
+}
+
+impl InstructionTestFixture for RegisterAgentFixture {
+    const INSTRUCTION_NAME: &'static str = "RegisterAgent";
+
+    fn build_valid(ctx: &mut TestContext) -> TestInstruction {
+        let payer = ctx.create_funded_keypair();
+        let agent_authority = ctx.create_funded_keypair();
+        let (agent_registry, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+        TestInstruction {
+            instruction: register_agent(
+                &payer.pubkey(),
+                &agent_authority.pubkey(),
+                &agent_registry,
+                bump,
+                Self::NAME.to_string(),
+                Self::INBOX_URL.to_string(),
+            ),
+            signers: vec![payer, agent_authority],
+            name: Self::INSTRUCTION_NAME,
+        }
+    }
+
+    fn required_signers() -> &'static [usize] {
+        &[0, 1]
+    }
+
+    fn required_writable() -> &'static [usize] {
+        &[0, 2]
+    }
+
+    fn system_program_index() -> Option<usize> {
+        Some(3)
+    }
+
+    fn current_program_index() -> Option<usize> {
+        Some(4)
+    }
+
+    fn data_len() -> usize {
+        // Discriminator, bump, then the two length-prefixed strings
+        2 + 4 + Self::NAME.len() + 4 + Self::INBOX_URL.len()
     }
 }
 
@@ -0,0 +1,127 @@
+use agentmail_client::instructions;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signer::Signer};
+
+use crate::{
+    fixtures::register_agent::register_agent,
+    utils::{
+        find_agent_registry_pda, find_rent_vault_pda, InstructionTestFixture, TestContext,
+        TestInstruction,
+    },
+};
+
+pub fn create_rent_vault(payer: &Pubkey, vault_authority: &Pubkey) -> Instruction {
+    instructions::create_rent_vault(payer, vault_authority)
+}
+
+pub fn withdraw_rent_vault(
+    vault_authority: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    instructions::withdraw_rent_vault(vault_authority, destination, amount)
+}
+
+/// DeregisterAgent sending the reclaimed rent to a rent vault
+pub fn deregister_agent_to_vault(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    rent_vault: &Pubkey,
+) -> Instruction {
+    instructions::deregister_to_vault(agent_authority, agent_registry, rent_vault)
+}
+
+/// CreateRentVault for a fresh vault authority
+pub struct CreateRentVaultFixture;
+
+impl InstructionTestFixture for CreateRentVaultFixture {
+    const INSTRUCTION_NAME: &'static str = "CreateRentVault";
+
+    fn build_valid(ctx: &mut TestContext) -> TestInstruction {
+        let payer = ctx.create_funded_keypair();
+        let vault_authority = ctx.create_funded_keypair();
+
+        TestInstruction {
+            instruction: create_rent_vault(&payer.pubkey(), &vault_authority.pubkey()),
+            signers: vec![payer, vault_authority],
+            name: Self::INSTRUCTION_NAME,
+        }
+    }
+
+    fn required_signers() -> &'static [usize] {
+        &[0, 1]
+    }
+
+    fn required_writable() -> &'static [usize] {
+        &[0, 2]
+    }
+
+    fn system_program_index() -> Option<usize> {
+        Some(3)
+    }
+
+    fn current_program_index() -> Option<usize> {
+        Some(4)
+    }
+
+    fn data_len() -> usize {
+        2
+    }
+}
+
+/// WithdrawRentVault of rent pooled from one deregistered agent
+pub struct WithdrawRentVaultFixture;
+
+impl InstructionTestFixture for WithdrawRentVaultFixture {
+    const INSTRUCTION_NAME: &'static str = "WithdrawRentVault";
+
+    fn build_valid(ctx: &mut TestContext) -> TestInstruction {
+        let vault_authority = ctx.create_funded_keypair();
+        let instruction = create_rent_vault(&ctx.payer.pubkey(), &vault_authority.pubkey());
+        ctx.send_transaction(instruction, &[&vault_authority])
+            .unwrap();
+
+        let agent_authority = ctx.create_funded_keypair();
+        let (agent_registry, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+        let register = register_agent(
+            &ctx.payer.pubkey(),
+            &agent_authority.pubkey(),
+            &agent_registry,
+            bump,
+            "pooled".to_string(),
+            "https://pooled.example.com/inbox".to_string(),
+        );
+        ctx.send_transaction(register, &[&agent_authority]).unwrap();
+
+        let (rent_vault, _) = find_rent_vault_pda(&vault_authority.pubkey());
+        let deregister =
+            deregister_agent_to_vault(&agent_authority.pubkey(), &agent_registry, &rent_vault);
+        ctx.send_transaction(deregister, &[&agent_authority])
+            .unwrap();
+
+        TestInstruction {
+            instruction: withdraw_rent_vault(
+                &vault_authority.pubkey(),
+                &vault_authority.pubkey(),
+                1,
+            ),
+            signers: vec![vault_authority],
+            name: Self::INSTRUCTION_NAME,
+        }
+    }
+
+    fn required_signers() -> &'static [usize] {
+        &[0]
+    }
+
+    fn required_writable() -> &'static [usize] {
+        &[1, 2]
+    }
+
+    fn current_program_index() -> Option<usize> {
+        Some(3)
+    }
+
+    fn data_len() -> usize {
+        9
+    }
+}
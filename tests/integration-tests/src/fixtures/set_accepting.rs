@@ -0,0 +1,25 @@
+use agentmail_client::instructions;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+/// Byte offset of `accepting` within AgentRegistry account data
+pub const ACCEPTING_OFFSET: usize = 5;
+
+/// Byte offset of `updated_at` within AgentRegistry account data
+pub const UPDATED_AT_OFFSET: usize = 378;
+
+pub fn set_accepting(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    accepting: bool,
+) -> Instruction {
+    instructions::set_accepting(agent_authority, agent_registry, accepting)
+}
+
+/// Read the `updated_at` timestamp from raw AgentRegistry account data
+pub fn read_updated_at(data: &[u8]) -> i64 {
+    i64::from_le_bytes(
+        data[UPDATED_AT_OFFSET..UPDATED_AT_OFFSET + 8]
+            .try_into()
+            .unwrap(),
+    )
+}
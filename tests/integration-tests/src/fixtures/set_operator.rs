@@ -0,0 +1,30 @@
+use agentmail_client::instructions;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+/// Byte offset of `operator` within AgentRegistry account data
+pub const OPERATOR_OFFSET: usize = 1086;
+
+pub fn set_operator(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    operator: &Pubkey,
+) -> Instruction {
+    instructions::set_operator(agent_authority, agent_registry, operator)
+}
+
+/// Append an operator to a RegisterAgent instruction with no metadata
+pub fn with_operator(mut instruction: Instruction, operator: &Pubkey) -> Instruction {
+    instruction.data.push(0); // no metadata
+    instruction.data.push(1);
+    instruction.data.extend_from_slice(operator.as_ref());
+    instruction
+}
+
+/// Read the operator from raw AgentRegistry account data
+pub fn read_operator(data: &[u8]) -> Pubkey {
+    Pubkey::new_from_array(
+        data[OPERATOR_OFFSET..OPERATOR_OFFSET + 32]
+            .try_into()
+            .unwrap(),
+    )
+}
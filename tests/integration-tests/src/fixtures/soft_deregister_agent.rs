@@ -0,0 +1,13 @@
+use agentmail_client::instructions;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+/// Byte offset of `deleted` within AgentRegistry account data
+pub const DELETED_OFFSET: usize = 6;
+
+pub fn soft_deregister_agent(agent_authority: &Pubkey, agent_registry: &Pubkey) -> Instruction {
+    instructions::soft_deregister(agent_authority, agent_registry)
+}
+
+pub fn reactivate_agent(agent_authority: &Pubkey, agent_registry: &Pubkey) -> Instruction {
+    instructions::reactivate(agent_authority, agent_registry)
+}
@@ -1,7 +1,9 @@
-use agentmail_client::AGENTMAIL_ID;
-use solana_sdk::{
-    instruction::{AccountMeta, Instruction},
-    pubkey::Pubkey,
+use agentmail_client::instructions;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signer::Signer};
+
+use crate::{
+    fixtures::register_agent::register_agent,
+    utils::{find_agent_registry_pda, InstructionTestFixture, TestContext, TestInstruction},
 };
 
 pub fn update_agent(
@@ -10,27 +12,132 @@ pub fn update_agent(
     name: String,
     inbox_url: String,
 ) -> Instruction {
-    // UpdateAgent instruction discriminator is 4
-    let mut data = vec![4u8];
-
-    // Add name (length-prefixed string)
-    let name_bytes = name.as_bytes();
-    data.extend_from_slice(&(name_bytes.len() as u32).to_le_bytes());
-    data.extend_from_slice(name_bytes);
-
-    // Add inbox_url (length-prefixed string)
-    let inbox_url_bytes = inbox_url.as_bytes();
-    data.extend_from_slice(&(inbox_url_bytes.len() as u32).to_le_bytes());
-    data.extend_from_slice(inbox_url_bytes);
-
-    Instruction {
-        program_id: AGENTMAIL_ID,
-        accounts: vec![
-            AccountMeta::new_readonly(*agent_authority, true),
-            AccountMeta::new(*agent_registry, false),
-            AccountMeta::new_readonly(AGENTMAIL_ID, false),
-        ],
-        data,
+    // Unchecked so tests can still send over-long fields to the program
+    instructions::update(agent_authority, agent_registry, &name, &inbox_url)
+}
+
+/// UpdateAgent carrying a replay nonce
+pub fn update_agent_with_nonce(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    name: &str,
+    inbox_url: &str,
+    nonce: u64,
+) -> Instruction {
+    instructions::update_with_nonce(agent_authority, agent_registry, name, inbox_url, nonce)
+}
+
+/// UpdateAgent that may grow a legacy registry, with `payer` funding the rent
+pub fn update_agent_with_payer(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    payer: Option<&Pubkey>,
+    name: String,
+    inbox_url: String,
+) -> Instruction {
+    instructions::update_with_payer(agent_authority, agent_registry, payer, &name, &inbox_url)
+}
+
+/// UpdateAgent using the version 3 field mask; `None` fields are left unchanged
+pub fn update_agent_fields(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    name: Option<&str>,
+    inbox_url: Option<&str>,
+) -> Instruction {
+    // UpdateAgent discriminator, then data version 3 and the field mask
+    let mut data = vec![4u8, 3u8, 0u8];
+
+    for (bit, value) in [(1u8 << 0, name), (1u8 << 1, inbox_url)] {
+        if let Some(value) = value {
+            data[2] |= bit;
+            data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            data.extend_from_slice(value.as_bytes());
+        }
+    }
+
+    // No metadata commitment
+    data.push(0);
+
+    let mut instruction = update_agent(
+        agent_authority,
+        agent_registry,
+        String::new(),
+        String::new(),
+    );
+    instruction.data = data;
+    instruction
+}
+
+/// UpdateAgent replacing only the accepted MIME types
+pub fn update_agent_mime_types(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    mime_types: &[&str],
+) -> Instruction {
+    instructions::update_mime_types(agent_authority, agent_registry, mime_types)
+}
+
+/// UpdateAgent replacing only the additional inbox URLs
+pub fn update_agent_inbox_urls(
+    agent_authority: &Pubkey,
+    agent_registry: &Pubkey,
+    inbox_urls: &[&str],
+) -> Instruction {
+    instructions::update_inbox_urls(agent_authority, agent_registry, inbox_urls)
+}
+
+/// UpdateAgent against a freshly registered agent
+pub struct UpdateAgentFixture;
+
+impl UpdateAgentFixture {
+    const NAME: &'static str = "renamed";
+    const INBOX_URL: &'static str = "https://renamed.example.com/inbox";
+}
+
+impl InstructionTestFixture for UpdateAgentFixture {
+    const INSTRUCTION_NAME: &'static str = "UpdateAgent";
+
+    fn build_valid(ctx: &mut TestContext) -> TestInstruction {
+        let agent_authority = ctx.create_funded_keypair();
+        let (agent_registry, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+        let register = register_agent(
+            &ctx.payer.pubkey(),
+            &agent_authority.pubkey(),
+            &agent_registry,
+            bump,
+            "original".to_string(),
+            "https://original.example.com/inbox".to_string(),
+        );
+        ctx.send_transaction(register, &[&agent_authority]).unwrap();
+
+        TestInstruction {
+            instruction: update_agent(
+                &agent_authority.pubkey(),
+                &agent_registry,
+                Self::NAME.to_string(),
+                Self::INBOX_URL.to_string(),
+            ),
+            signers: vec![agent_authority],
+            name: Self::INSTRUCTION_NAME,
+        }
+    }
+
+    fn required_signers() -> &'static [usize] {
+        &[0]
+    }
+
+    fn required_writable() -> &'static [usize] {
+        &[1]
+    }
+
+    fn current_program_index() -> Option<usize> {
+        Some(2)
+    }
+
+    fn data_len() -> usize {
+        // Discriminator, then the two length-prefixed strings
+        1 + 4 + Self::NAME.len() + 4 + Self::INBOX_URL.len()
     }
 }
 
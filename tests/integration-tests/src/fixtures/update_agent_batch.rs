@@ -0,0 +1,45 @@
+use agentmail_client::AGENTMAIL_ID;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+/// A single (agent_authority, agent_registry, name, inbox_url) batch entry
+pub struct UpdateAgentBatchEntry {
+    pub agent_authority: Pubkey,
+    pub agent_registry: Pubkey,
+    pub name: String,
+    pub inbox_url: String,
+}
+
+pub fn update_agent_batch(entries: &[UpdateAgentBatchEntry]) -> Instruction {
+    // UpdateAgentBatch instruction discriminator is 6
+    let mut data = vec![6u8];
+    data.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+
+    let mut accounts = vec![AccountMeta::new_readonly(AGENTMAIL_ID, false)];
+
+    for entry in entries {
+        // Add name (length-prefixed string)
+        let name_bytes = entry.name.as_bytes();
+        data.extend_from_slice(&(name_bytes.len() as u32).to_le_bytes());
+        data.extend_from_slice(name_bytes);
+
+        // Add inbox_url (length-prefixed string)
+        let inbox_url_bytes = entry.inbox_url.as_bytes();
+        data.extend_from_slice(&(inbox_url_bytes.len() as u32).to_le_bytes());
+        data.extend_from_slice(inbox_url_bytes);
+
+        // No metadata commitment (the tag keeps entries self-delimiting)
+        data.push(0);
+
+        accounts.push(AccountMeta::new_readonly(entry.agent_authority, true));
+        accounts.push(AccountMeta::new(entry.agent_registry, false));
+    }
+
+    Instruction {
+        program_id: AGENTMAIL_ID,
+        accounts,
+        data,
+    }
+}
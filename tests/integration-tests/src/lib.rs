@@ -3,8 +3,30 @@ pub mod utils;
 
 // AgentMail integration tests
 #[cfg(test)]
+mod test_allocate_registry;
+#[cfg(test)]
+mod test_check_registry_batch;
+#[cfg(test)]
 mod test_deregister_agent;
 #[cfg(test)]
+mod test_instruction_builders;
+#[cfg(test)]
+mod test_migrate_registry;
+#[cfg(test)]
+mod test_program_id;
+#[cfg(test)]
 mod test_register_agent;
 #[cfg(test)]
+mod test_registry_client;
+#[cfg(test)]
+mod test_rent_vault;
+#[cfg(test)]
+mod test_set_accepting;
+#[cfg(test)]
+mod test_set_operator;
+#[cfg(test)]
+mod test_soft_deregister_agent;
+#[cfg(test)]
 mod test_update_agent;
+#[cfg(test)]
+mod test_update_agent_batch;
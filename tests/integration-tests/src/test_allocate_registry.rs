@@ -0,0 +1,142 @@
+use agentmail_client::{
+    accounts::AgentRegistry, preflight::AGENT_REGISTRY_ACCOUNT_LEN, AGENTMAIL_ID,
+};
+use solana_sdk::{instruction::InstructionError, pubkey::Pubkey, signer::Signer, sysvar};
+
+use crate::{
+    fixtures::{
+        allocate_registry::{allocate_registry, initialize_registry},
+        register_agent::{register_agent, SYSTEM_PROGRAM_ID},
+    },
+    utils::{
+        assertions::{assert_account_not_exists, assert_custom_error, assert_instruction_error},
+        pda_utils::find_agent_registry_pda,
+        setup::TestContext,
+    },
+};
+
+#[test]
+fn test_allocate_then_initialize() {
+    let mut context = TestContext::new();
+    let funder = context.create_funded_keypair();
+    let agent_authority = context.create_funded_keypair();
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+
+    // The funder creates the account; the authority does not sign
+    let funder_before = context.svm.get_balance(&funder.pubkey()).unwrap();
+    let instruction = allocate_registry(
+        &funder.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+    );
+    context.send_transaction(instruction, &[&funder]).unwrap();
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(account.owner, AGENTMAIL_ID);
+    assert_eq!(account.data.len(), AGENT_REGISTRY_ACCOUNT_LEN);
+    assert!(account.data.iter().all(|&b| b == 0));
+    let funder_after = context.svm.get_balance(&funder.pubkey()).unwrap();
+    assert_eq!(funder_before - funder_after, account.lamports);
+
+    // The authority writes the data without paying anything
+    let authority_before = context.svm.get_balance(&agent_authority.pubkey()).unwrap();
+    let instruction = initialize_registry(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "two-phase",
+        "https://two-phase.example.com/inbox",
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+    assert_eq!(
+        context.svm.get_balance(&agent_authority.pubkey()).unwrap(),
+        authority_before
+    );
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    let registry = AgentRegistry::from_bytes(&account.data).unwrap();
+    assert_eq!(registry.authority, agent_authority.pubkey());
+    assert_eq!(registry.bump, bump);
+    assert_eq!(registry.name_str().unwrap(), "two-phase");
+    assert_eq!(
+        registry.inbox_url_str().unwrap(),
+        "https://two-phase.example.com/inbox"
+    );
+
+    // A second initialize finds the registry already written
+    context.warp_to_next_slot();
+    let instruction = initialize_registry(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "overwrite",
+        "https://overwrite.example.com/inbox",
+    );
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    assert_custom_error(error, 106); // RegistryAlreadyExists
+}
+
+#[test]
+fn test_initialize_registry_unallocated() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+
+    let instruction = initialize_registry(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "no-account",
+        "https://no-account.example.com/inbox",
+    );
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    assert_instruction_error(error, InstructionError::InvalidAccountOwner);
+    assert_account_not_exists(&context, &agent_registry_pda);
+}
+
+#[test]
+fn test_allocate_registry_rejects_registered_agent() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "registered".to_string(),
+        "https://registered.example.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let instruction = allocate_registry(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+    );
+    let error = context.send_transaction_expect_error(instruction, &[]);
+    assert_custom_error(error, 106); // RegistryAlreadyExists
+}
+
+#[test]
+fn test_allocate_registry_rejects_reserved_authorities() {
+    let mut context = TestContext::new();
+    let funder = context.create_funded_keypair();
+
+    let reserved: [Pubkey; 3] = [SYSTEM_PROGRAM_ID, AGENTMAIL_ID, sysvar::clock::ID];
+    for authority in reserved {
+        let (agent_registry_pda, bump) = find_agent_registry_pda(&authority);
+        let instruction =
+            allocate_registry(&funder.pubkey(), &authority, &agent_registry_pda, bump);
+        let error = context.send_transaction_expect_error(instruction, &[&funder]);
+        assert_custom_error(error, 100); // InvalidAuthority
+        assert_account_not_exists(&context, &agent_registry_pda);
+    }
+}
@@ -0,0 +1,83 @@
+use agentmail_client::instructions;
+use solana_sdk::{
+    instruction::InstructionError, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
+
+use crate::{
+    fixtures::register_agent::register_agent,
+    utils::{
+        assertions::assert_instruction_error, pda_utils::find_agent_registry_pda,
+        setup::TestContext, Address,
+    },
+};
+
+fn setup_agent_registry(context: &mut TestContext, agent_authority: &Keypair) -> Address {
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "recipient".to_string(),
+        "https://recipient.example.com/inbox".to_string(),
+    );
+
+    context
+        .send_transaction(instruction, &[agent_authority])
+        .unwrap();
+    agent_registry_pda
+}
+
+fn check_mask(context: &mut TestContext, registries: &[Pubkey], authorities: &[Pubkey]) -> u32 {
+    let instruction = instructions::check_registry_batch(registries, authorities);
+    let return_data = context
+        .send_transaction_return_data(instruction, &[])
+        .unwrap();
+    u32::from_le_bytes(return_data.try_into().unwrap())
+}
+
+#[test]
+fn test_check_registry_batch_all_valid() {
+    let mut context = TestContext::new();
+    let authorities: Vec<Keypair> = (0..3).map(|_| context.create_funded_keypair()).collect();
+    let registries: Vec<Pubkey> = authorities
+        .iter()
+        .map(|authority| setup_agent_registry(&mut context, authority))
+        .collect();
+    let authorities: Vec<Pubkey> = authorities.iter().map(Keypair::pubkey).collect();
+
+    assert_eq!(check_mask(&mut context, &registries, &authorities), 0b111);
+}
+
+#[test]
+fn test_check_registry_batch_some_invalid() {
+    let mut context = TestContext::new();
+    let registered = context.create_funded_keypair();
+    let other = context.create_funded_keypair();
+    let unregistered = Pubkey::new_unique();
+
+    let registered_pda = setup_agent_registry(&mut context, &registered);
+    let other_pda = setup_agent_registry(&mut context, &other);
+    let (unregistered_pda, _) = find_agent_registry_pda(&unregistered);
+
+    let registries = [registered_pda, other_pda, unregistered_pda];
+    // The second entry names the wrong authority; the third was never registered
+    let authorities = [registered.pubkey(), registered.pubkey(), unregistered];
+
+    assert_eq!(check_mask(&mut context, &registries, &authorities), 0b001);
+}
+
+#[test]
+fn test_check_registry_batch_count_mismatch() {
+    let mut context = TestContext::new();
+    let registered = context.create_funded_keypair();
+    let registered_pda = setup_agent_registry(&mut context, &registered);
+
+    let instruction = instructions::check_registry_batch(
+        &[registered_pda],
+        &[registered.pubkey(), Pubkey::new_unique()],
+    );
+    let error = context.send_transaction_expect_error(instruction, &[]);
+    assert_instruction_error(error, InstructionError::InvalidArgument);
+}
@@ -1,8 +1,14 @@
 use solana_sdk::{signature::Keypair, signer::Signer, transaction::TransactionError};
 
 use crate::{
-    fixtures::{deregister_agent::deregister_agent, register_agent::register_agent},
-    utils::{pda_utils::find_agent_registry_pda, setup::TestContext, Address},
+    fixtures::{
+        deregister_agent::{deregister_agent, DeregisterAgentFixture},
+        register_agent::register_agent,
+    },
+    utils::{
+        pda_utils::find_agent_registry_pda, setup::TestContext, test_helpers::test_declared_roles,
+        Address,
+    },
 };
 
 fn setup_agent_registry(context: &mut TestContext, agent_authority: &Keypair) -> (Address, u8) {
@@ -133,7 +139,7 @@ fn test_deregister_agent_account_data_zeroed() {
 
     // Verify account exists and has data
     let account_before = context.get_account(&agent_registry_pda).unwrap();
-    assert_eq!(account_before.data.len(), 384);
+    assert_eq!(account_before.data.len(), 1258);
     assert!(!account_before.data.iter().all(|&b| b == 0)); // Should have non-zero data
 
     let instruction = deregister_agent(&agent_authority.pubkey(), &agent_registry_pda);
@@ -187,3 +193,72 @@ fn test_deregister_agent_can_re_register() {
     let account = context.get_account(&agent_registry_pda);
     assert!(account.is_some(), "Re-registered account should exist");
 }
+
+#[test]
+fn test_deregister_agent_corrupt_data_via_pda_match() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+
+    // Corrupt the discriminator and the stored authority
+    let mut data = context.get_account(&agent_registry_pda).unwrap().data;
+    data[0] = 0xFF;
+    data[10..42].fill(0xAB);
+    context.set_account_data(&agent_registry_pda, data);
+
+    let instruction = deregister_agent(&agent_authority.pubkey(), &agent_registry_pda);
+    let result = context.send_transaction(instruction, &[&agent_authority]);
+    assert!(
+        result.is_ok(),
+        "Authority should reclaim a corrupt registry via PDA derivation"
+    );
+
+    let account = context.get_account(&agent_registry_pda);
+    assert!(account.is_none(), "Agent registry account should be closed");
+}
+
+#[test]
+fn test_deregister_agent_corrupt_data_wrong_signer() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+    let other = context.create_funded_keypair();
+
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+
+    let mut data = context.get_account(&agent_registry_pda).unwrap().data;
+    data[0] = 0xFF;
+    context.set_account_data(&agent_registry_pda, data);
+
+    // The fallback must still reject a signer the PDA was not derived from
+    let instruction = deregister_agent(&other.pubkey(), &agent_registry_pda);
+    let error = context.send_transaction_expect_error(instruction, &[&other]);
+    assert!(matches!(error, TransactionError::InstructionError(_, _)));
+}
+
+#[test]
+fn test_deregister_agent_garbage_data() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+
+    // Replace the whole registry with bytes no instruction could have written
+    let len = context.get_account(&agent_registry_pda).unwrap().data.len();
+    let garbage = (0..len).map(|i| (i as u8).wrapping_mul(31)).collect();
+    context.set_account_data(&agent_registry_pda, garbage);
+
+    let instruction = deregister_agent(&agent_authority.pubkey(), &agent_registry_pda);
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .expect("Authority should reclaim a fully corrupted registry");
+
+    let account = context.get_account(&agent_registry_pda);
+    assert!(account.is_none(), "Agent registry account should be closed");
+}
+
+#[test]
+fn test_deregister_agent_account_roles() {
+    // Every role declared for the authority, registry and program account
+    test_declared_roles::<DeregisterAgentFixture>();
+}
@@ -0,0 +1,225 @@
+use agentmail_client::{
+    instructions::{self, DeregisterAgent, RegisterAgent, UpdateAgent},
+    registry::{find_agent_registry_pda, find_rent_vault_pda},
+    AGENTMAIL_ID,
+};
+use solana_sdk::pubkey::Pubkey;
+
+#[test]
+fn test_register_builder() {
+    let payer = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let registry = Pubkey::new_unique();
+
+    let instruction = instructions::register(
+        &payer,
+        &authority,
+        &registry,
+        254,
+        "nix",
+        "https://nix.example.com/inbox",
+    );
+
+    assert_eq!(instruction.program_id, AGENTMAIL_ID);
+    assert_eq!(
+        instruction.accounts,
+        RegisterAgent::account_metas(&payer, &authority, &registry)
+    );
+
+    let mut expected = vec![3u8, 254];
+    expected.extend_from_slice(&3u32.to_le_bytes());
+    expected.extend_from_slice(b"nix");
+    expected.extend_from_slice(&29u32.to_le_bytes());
+    expected.extend_from_slice(b"https://nix.example.com/inbox");
+    assert_eq!(instruction.data, expected);
+}
+
+#[test]
+fn test_register_derived_builder_uses_canonical_bump() {
+    let payer = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let (registry, bump) = find_agent_registry_pda(&authority);
+
+    let instruction =
+        instructions::register_derived(&payer, &authority, "nix", "https://nix.example.com/inbox");
+
+    assert_eq!(
+        instruction,
+        instructions::register(
+            &payer,
+            &authority,
+            &registry,
+            bump,
+            "nix",
+            "https://nix.example.com/inbox",
+        )
+    );
+    assert_eq!(instruction.accounts[2].pubkey, registry);
+    assert_eq!(instruction.data[1], bump);
+}
+
+#[test]
+fn test_update_builder() {
+    let authority = Pubkey::new_unique();
+    let registry = Pubkey::new_unique();
+
+    let instruction = instructions::update(&authority, &registry, "renamed", "");
+
+    assert_eq!(instruction.program_id, AGENTMAIL_ID);
+    assert_eq!(
+        instruction.accounts,
+        UpdateAgent::account_metas(&authority, &registry)
+    );
+
+    let mut expected = vec![4u8];
+    expected.extend_from_slice(&7u32.to_le_bytes());
+    expected.extend_from_slice(b"renamed");
+    expected.extend_from_slice(&0u32.to_le_bytes());
+    assert_eq!(instruction.data, expected);
+}
+
+#[test]
+fn test_update_single_field_builders() {
+    let authority = Pubkey::new_unique();
+    let registry = Pubkey::new_unique();
+
+    // Data version 3, the field mask, the one field, then no metadata
+    let instruction = instructions::update_name(&authority, &registry, "renamed");
+    assert_eq!(
+        instruction.accounts,
+        UpdateAgent::account_metas(&authority, &registry)
+    );
+    let mut expected = vec![4u8, 3, 1];
+    expected.extend_from_slice(&7u32.to_le_bytes());
+    expected.extend_from_slice(b"renamed");
+    expected.push(0);
+    assert_eq!(instruction.data, expected);
+
+    let instruction = instructions::update_inbox_url(&authority, &registry, "https://a.io");
+    let mut expected = vec![4u8, 3, 2];
+    expected.extend_from_slice(&12u32.to_le_bytes());
+    expected.extend_from_slice(b"https://a.io");
+    expected.push(0);
+    assert_eq!(instruction.data, expected);
+}
+
+#[test]
+fn test_deregister_builder() {
+    let authority = Pubkey::new_unique();
+    let registry = Pubkey::new_unique();
+
+    let instruction = instructions::deregister(&authority, &registry);
+    assert_eq!(instruction.program_id, AGENTMAIL_ID);
+    assert_eq!(
+        instruction.accounts,
+        DeregisterAgent::account_metas(&authority, &registry)
+    );
+    assert_eq!(instruction.data, vec![5u8]);
+}
+
+#[test]
+fn test_deregister_to_vault_builder() {
+    let authority = Pubkey::new_unique();
+    let registry = Pubkey::new_unique();
+    let vault = Pubkey::new_unique();
+
+    let instruction = instructions::deregister_to_vault(&authority, &registry, &vault);
+    assert_eq!(instruction.program_id, AGENTMAIL_ID);
+    assert_eq!(
+        instruction.accounts,
+        DeregisterAgent::account_metas_to_vault(&authority, &registry, &vault)
+    );
+    assert_eq!(instruction.accounts[3].pubkey, vault);
+    assert!(instruction.accounts[3].is_writable && !instruction.accounts[3].is_signer);
+    assert_eq!(instruction.data, vec![5u8]);
+}
+
+#[test]
+fn test_rent_vault_builders() {
+    let payer = Pubkey::new_unique();
+    let vault_authority = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    let (vault, bump) = find_rent_vault_pda(&vault_authority);
+
+    let instruction = instructions::create_rent_vault(&payer, &vault_authority);
+    assert_eq!(instruction.program_id, AGENTMAIL_ID);
+    assert_eq!(instruction.accounts[2].pubkey, vault);
+    assert_eq!(instruction.data, vec![15u8, bump]);
+
+    let instruction = instructions::withdraw_rent_vault(&vault_authority, &destination, 42);
+    assert_eq!(instruction.accounts[0].pubkey, vault_authority);
+    assert!(instruction.accounts[0].is_signer);
+    assert_eq!(instruction.accounts[1].pubkey, vault);
+    assert_eq!(instruction.accounts[2].pubkey, destination);
+    assert_eq!(instruction.data[0], 16);
+    assert_eq!(&instruction.data[1..], &42u64.to_le_bytes());
+}
+
+#[test]
+fn test_set_accepting_builder() {
+    let authority = Pubkey::new_unique();
+    let registry = Pubkey::new_unique();
+
+    let instruction = instructions::set_accepting(&authority, &registry, false);
+
+    assert_eq!(instruction.program_id, AGENTMAIL_ID);
+    assert_eq!(
+        instruction.accounts,
+        UpdateAgent::account_metas(&authority, &registry)
+    );
+    assert_eq!(instruction.data, vec![7u8, 0]);
+}
+
+#[test]
+fn test_set_operator_builder() {
+    let authority = Pubkey::new_unique();
+    let registry = Pubkey::new_unique();
+    let operator = Pubkey::new_unique();
+
+    let instruction = instructions::set_operator(&authority, &registry, &operator);
+
+    assert_eq!(instruction.program_id, AGENTMAIL_ID);
+    assert_eq!(
+        instruction.accounts,
+        UpdateAgent::account_metas(&authority, &registry)
+    );
+    assert_eq!(instruction.data[0], 9);
+    assert_eq!(&instruction.data[1..], operator.as_ref());
+}
+
+#[test]
+fn test_migrate_registry_builder() {
+    let payer = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let registry = Pubkey::new_unique();
+
+    let instruction = instructions::migrate_registry(&payer, &authority, &registry);
+
+    assert_eq!(instruction.program_id, AGENTMAIL_ID);
+    assert_eq!(
+        instruction.accounts,
+        RegisterAgent::account_metas(&payer, &authority, &registry)
+    );
+    assert_eq!(instruction.data, vec![8u8]);
+}
+
+#[test]
+fn test_check_registry_batch_builder() {
+    let registries = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let authorities = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+    let instruction = instructions::check_registry_batch(&registries, &authorities);
+
+    assert_eq!(instruction.program_id, AGENTMAIL_ID);
+    assert_eq!(instruction.accounts.len(), 3);
+    assert_eq!(instruction.accounts[0].pubkey, AGENTMAIL_ID);
+    for (meta, registry) in instruction.accounts[1..].iter().zip(&registries) {
+        assert_eq!(meta.pubkey, *registry);
+        assert!(!meta.is_signer && !meta.is_writable);
+    }
+
+    assert_eq!(instruction.data[0], 10);
+    assert_eq!(&instruction.data[1..5], &2u32.to_le_bytes());
+    assert_eq!(&instruction.data[5..37], authorities[0].as_ref());
+    assert_eq!(&instruction.data[37..], authorities[1].as_ref());
+}
@@ -0,0 +1,213 @@
+use agentmail_client::AGENTMAIL_ID;
+use solana_sdk::{account::Account, instruction::InstructionError, signer::Signer};
+
+use crate::{
+    fixtures::{
+        migrate_registry::{legacy_v2_registry_data, migrate_registry, LEGACY_V2_ACCOUNT_LEN},
+        register_agent::register_agent,
+        set_accepting::ACCEPTING_OFFSET,
+    },
+    utils::{
+        assertions::{assert_custom_error, assert_instruction_error},
+        pda_utils::find_agent_registry_pda,
+        setup::TestContext,
+    },
+};
+
+const CURRENT_ACCOUNT_LEN: usize = 1258;
+
+#[test]
+fn test_migrate_registry_grows_legacy_account() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+    let legacy_data = legacy_v2_registry_data(
+        bump,
+        &agent_authority.pubkey(),
+        "veteran",
+        "https://old.example.com/inbox",
+    );
+    let legacy_rent = context
+        .svm
+        .minimum_balance_for_rent_exemption(LEGACY_V2_ACCOUNT_LEN);
+    context
+        .svm
+        .set_account(
+            agent_registry_pda,
+            Account {
+                lamports: legacy_rent,
+                data: legacy_data.clone(),
+                owner: AGENTMAIL_ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+    let payer_before = context.svm.get_balance(&context.payer.pubkey()).unwrap();
+    let instruction = migrate_registry(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+    let payer_after = context.svm.get_balance(&context.payer.pubkey()).unwrap();
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    let current_rent = context
+        .svm
+        .minimum_balance_for_rent_exemption(CURRENT_ACCOUNT_LEN);
+    assert_eq!(account.data.len(), CURRENT_ACCOUNT_LEN);
+    assert_eq!(account.lamports, current_rent);
+
+    // The payer covers the rent difference on top of the transaction fee
+    let top_up = current_rent - legacy_rent;
+    assert!(payer_before - payer_after >= top_up);
+
+    // Existing fields are untouched apart from the version stamps
+    assert_eq!(account.data[0], 1);
+    assert_eq!(account.data[1], 8);
+    assert_eq!(account.data[3], 8);
+    assert_eq!(&account.data[4..LEGACY_V2_ACCOUNT_LEN], &legacy_data[4..]);
+
+    // Legacy registries were implicitly accepting; everything else is zeroed
+    assert_eq!(account.data[ACCEPTING_OFFSET], 1);
+    assert!(account.data[LEGACY_V2_ACCOUNT_LEN..]
+        .iter()
+        .all(|byte| *byte == 0));
+}
+
+#[test]
+fn test_migrate_registry_wrong_authority() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+    let attacker = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+    let legacy_rent = context
+        .svm
+        .minimum_balance_for_rent_exemption(LEGACY_V2_ACCOUNT_LEN);
+    context
+        .svm
+        .set_account(
+            agent_registry_pda,
+            Account {
+                lamports: legacy_rent,
+                data: legacy_v2_registry_data(bump, &agent_authority.pubkey(), "veteran", ""),
+                owner: AGENTMAIL_ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+    let instruction = migrate_registry(
+        &context.payer.pubkey(),
+        &attacker.pubkey(),
+        &agent_registry_pda,
+    );
+    let error = context.send_transaction_expect_error(instruction, &[&attacker]);
+    assert_custom_error(error, 100); // InvalidAuthority
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(account.data.len(), LEGACY_V2_ACCOUNT_LEN);
+}
+
+#[test]
+fn test_migrate_registry_not_program_owned() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    // Nothing registered: the PDA is still a system account
+    let (agent_registry_pda, _bump) = find_agent_registry_pda(&agent_authority.pubkey());
+    let instruction = migrate_registry(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+    );
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    assert_instruction_error(error, InstructionError::InvalidAccountOwner);
+}
+
+#[test]
+fn test_migrate_registry_repairs_stale_header_version() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "interrupted".to_string(),
+        "https://example.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    // Current size and fields, but the header still claims an old version
+    let mut data = context.get_account(&agent_registry_pda).unwrap().data;
+    let expected = data.clone();
+    data[1] = 2;
+    context.set_account_data(&agent_registry_pda, data);
+
+    let instruction = migrate_registry(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(
+        account.data, expected,
+        "only the version byte should change"
+    );
+}
+
+#[test]
+fn test_migrate_registry_repair_keeps_accepting_choice() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "closed".to_string(),
+        "https://example.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    // Current size with accepting turned off, but the inner version is stale
+    let mut data = context.get_account(&agent_registry_pda).unwrap().data;
+    data[ACCEPTING_OFFSET] = 0;
+    let expected = data.clone();
+    data[3] = 4;
+    context.set_account_data(&agent_registry_pda, data);
+
+    let instruction = migrate_registry(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    // The size shows the flag is real, so the repair must not re-enable it
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(account.data, expected);
+    assert_eq!(account.data[ACCEPTING_OFFSET], 0);
+}
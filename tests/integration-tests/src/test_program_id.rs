@@ -0,0 +1,18 @@
+use agentmail_client::AGENTMAIL_ID;
+use solana_sdk::{signature::read_keypair_file, signer::Signer};
+
+const PROGRAM_KEYPAIR: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../keys/agentmail-program.json"
+);
+
+#[test]
+fn test_program_id_matches_client() {
+    assert_eq!(agentmail::ID.as_ref(), AGENTMAIL_ID.as_ref());
+}
+
+#[test]
+fn test_program_keypair_matches_client() {
+    let keypair = read_keypair_file(PROGRAM_KEYPAIR).expect("Should read program keypair");
+    assert_eq!(keypair.pubkey(), AGENTMAIL_ID);
+}
@@ -1,9 +1,23 @@
-use solana_sdk::{signer::Signer, transaction::TransactionError};
+use agentmail_client::AGENTMAIL_ID;
+use solana_sdk::{
+    instruction::InstructionError, pubkey::Pubkey, signer::Signer, transaction::TransactionError,
+};
 
 use crate::{
-    fixtures::register_agent::register_agent,
+    fixtures::{
+        metadata::{read_metadata, with_metadata},
+        register_agent::{
+            register_agent, register_agent_idempotent, RegisterAgentFixture, SYSTEM_PROGRAM_ID,
+        },
+    },
     utils::{
-        pda_utils::find_agent_registry_pda, setup::TestContext, test_helpers::AgentRegistryAccount,
+        assertions::{
+            assert_account_not_exists, assert_custom_error, assert_instruction_error,
+            assert_owned_by_program,
+        },
+        pda_utils::{find_agent_registry_pda, find_non_canonical_agent_registry_pda},
+        setup::TestContext,
+        test_helpers::{test_declared_roles, AgentRegistryAccount},
     },
 };
 
@@ -30,12 +44,12 @@ fn test_register_agent_success() {
     assert!(result.is_ok(), "RegisterAgent transaction should succeed");
 
     // Verify the account was created and has correct data
+    assert_owned_by_program(&context, &agent_registry_pda);
     let account = context.get_account(&agent_registry_pda);
-    assert!(account.is_some(), "Agent registry account should exist");
 
     let registry = AgentRegistryAccount::try_from_account_data(&account.unwrap().data).unwrap();
     assert_eq!(registry.bump, bump);
-    assert_eq!(registry.version, 1);
+    assert_eq!(registry.version, 8);
     assert_eq!(registry.authority, agent_authority.pubkey());
     assert_eq!(registry.name, name);
     assert_eq!(registry.inbox_url, inbox_url);
@@ -84,6 +98,85 @@ fn test_register_agent_already_exists() {
     assert!(matches!(error, TransactionError::InstructionError(_, _)));
 }
 
+#[test]
+fn test_register_agent_idempotent_retry() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+    let retry = |context: &mut TestContext| {
+        let instruction = register_agent_idempotent(
+            &context.payer.pubkey(),
+            &agent_authority.pubkey(),
+            &agent_registry_pda,
+            bump,
+            "retried".to_string(),
+            "https://retried.example.com/inbox".to_string(),
+        );
+        context.send_transaction(instruction, &[&agent_authority])
+    };
+
+    retry(&mut context).unwrap();
+    let registered = context.get_account(&agent_registry_pda).unwrap();
+
+    // The first attempt landed; an identical retry is a no-op success
+    context.warp_to_next_slot();
+    retry(&mut context).unwrap();
+    assert_eq!(
+        context.get_account(&agent_registry_pda).unwrap(),
+        registered
+    );
+
+    // Without the flag the same retry is still an error
+    context.warp_to_next_slot();
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "retried".to_string(),
+        "https://retried.example.com/inbox".to_string(),
+    );
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    assert_custom_error(error, 106); // RegistryAlreadyExists
+}
+
+#[test]
+fn test_register_agent_idempotent_conflicting_retry() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+    let instruction = register_agent_idempotent(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "original".to_string(),
+        "https://original.example.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+    let registered = context.get_account(&agent_registry_pda).unwrap();
+
+    // A different inbox URL is a conflicting registration, not a retry
+    let instruction = register_agent_idempotent(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "original".to_string(),
+        "https://elsewhere.example.com/inbox".to_string(),
+    );
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    assert_custom_error(error, 106); // RegistryAlreadyExists
+    assert_eq!(
+        context.get_account(&agent_registry_pda).unwrap(),
+        registered
+    );
+}
+
 #[test]
 fn test_register_agent_invalid_authority() {
     let mut context = TestContext::new();
@@ -195,3 +288,224 @@ fn test_register_agent_invalid_url_format() {
     let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
     assert!(matches!(error, TransactionError::InstructionError(_, _)));
 }
+
+#[test]
+fn test_register_agent_payer_is_registry() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+    let aliased = context.create_funded_keypair();
+
+    let (_agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+
+    // The same account is passed as both payer and registry
+    let instruction = register_agent(
+        &aliased.pubkey(),
+        &agent_authority.pubkey(),
+        &aliased.pubkey(),
+        bump,
+        "aliased".to_string(),
+        "https://aliased.com/inbox".to_string(),
+    );
+
+    let error = context.send_transaction_expect_error(instruction, &[&aliased, &agent_authority]);
+    assert_instruction_error(error, InstructionError::InvalidArgument);
+}
+
+#[test]
+fn test_register_agent_payer_is_authority() {
+    let mut context = TestContext::new();
+    let agent_authority = context.payer.pubkey();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority);
+
+    // One key fills both roles and signs once
+    let instruction = register_agent(
+        &agent_authority,
+        &agent_authority,
+        &agent_registry_pda,
+        bump,
+        "self-funded".to_string(),
+        "https://self-funded.example.com/inbox".to_string(),
+    );
+
+    let balance_before = context.svm.get_balance(&agent_authority).unwrap();
+    context.send_transaction(instruction, &[]).unwrap();
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    let registry = AgentRegistryAccount::try_from_account_data(&account.data).unwrap();
+    assert_eq!(registry.authority, agent_authority);
+
+    // The authority paid the rent itself
+    let balance_after = context.svm.get_balance(&agent_authority).unwrap();
+    assert!(balance_before - balance_after >= account.lamports);
+}
+
+#[test]
+fn test_register_agent_non_signing_pda_authority() {
+    let mut context = TestContext::new();
+
+    // Program-derived, so off the curve and only able to sign via CPI
+    let (pda_authority, _) = Pubkey::find_program_address(&[b"pda-authority"], &AGENTMAIL_ID);
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&pda_authority);
+
+    let mut instruction = register_agent(
+        &context.payer.pubkey(),
+        &pda_authority,
+        &agent_registry_pda,
+        bump,
+        "unsigned-pda".to_string(),
+        "https://unsigned-pda.example.com/inbox".to_string(),
+    );
+    instruction.accounts[1].is_signer = false;
+
+    let error = context.send_transaction_expect_error(instruction, &[]);
+    assert_instruction_error(error, InstructionError::MissingRequiredSignature);
+    assert_account_not_exists(&context, &agent_registry_pda);
+}
+
+#[test]
+fn test_register_agent_mismatched_bump() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+    let (other_pda, other_bump) = find_non_canonical_agent_registry_pda(&agent_authority.pubkey());
+
+    // A non-canonical bump derives a valid address that is not the registry
+    for (account, bump) in [
+        (other_pda, other_bump),
+        (agent_registry_pda, other_bump),
+        (other_pda, bump),
+    ] {
+        let instruction = register_agent(
+            &context.payer.pubkey(),
+            &agent_authority.pubkey(),
+            &account,
+            bump,
+            "nix".to_string(),
+            "https://nix.example.com/inbox".to_string(),
+        );
+
+        let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+        assert_instruction_error(error, InstructionError::InvalidSeeds);
+        assert!(context.get_account(&account).is_none());
+    }
+}
+
+#[test]
+fn test_register_agent_with_metadata() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+
+    let uri = "https://nix.example.com/agent.json";
+    let hash = [0xABu8; 32];
+    let instruction = with_metadata(
+        register_agent(
+            &context.payer.pubkey(),
+            &agent_authority.pubkey(),
+            &agent_registry_pda,
+            bump,
+            "nix".to_string(),
+            "https://nix.example.com/inbox".to_string(),
+        ),
+        uri,
+        hash,
+    );
+
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(read_metadata(&account.data), (uri.to_string(), hash));
+}
+
+#[test]
+fn test_register_agent_without_metadata() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "nix".to_string(),
+        "https://nix.example.com/inbox".to_string(),
+    );
+
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(read_metadata(&account.data), (String::new(), [0u8; 32]));
+}
+
+#[test]
+fn test_register_agent_non_executable_system_program() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    // Replace the system program with a non-executable account at its address
+    let mut system_program = context.get_account(&SYSTEM_PROGRAM_ID).unwrap();
+    system_program.executable = false;
+    context
+        .svm
+        .set_account(SYSTEM_PROGRAM_ID, system_program)
+        .unwrap();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "spoofed".to_string(),
+        "https://spoofed.example.com/inbox".to_string(),
+    );
+
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    assert_instruction_error(error, InstructionError::IncorrectProgramId);
+}
+
+#[test]
+fn test_register_agent_name_min_len() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "a".to_string(),
+        "https://nix.example.com/inbox".to_string(),
+    );
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    assert_custom_error(error, 119);
+
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "ab".to_string(),
+        "https://nix.example.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .expect("Two-byte name should be accepted");
+}
+
+#[test]
+fn test_register_agent_account_roles() {
+    // Every role declared for the payer, authority, registry and both program accounts
+    test_declared_roles::<RegisterAgentFixture>();
+}
@@ -0,0 +1,1045 @@
+use agentmail::traits::AgentMailAccountDiscriminators;
+use agentmail_client::accounts::AgentRegistry;
+use agentmail_client::encode::encode_update_agent;
+use agentmail_client::fees::{fee_message, total_cost_estimate};
+use agentmail_client::preflight::{
+    check_register_args, check_register_state, PreflightError, AGENT_REGISTRY_ACCOUNT_LEN,
+};
+use agentmail_client::registry::{
+    account_type, authority_memcmp_filter, decode_maybe_registry, derive_registry_pdas,
+    fetch_registries, is_registry_data, register_signers, registry_filters, sort_by_authority,
+    AccountKind, AGENT_REGISTRY_AUTHORITY_OFFSET, AGENT_REGISTRY_DELETED_OFFSET,
+};
+use agentmail_client::shared::DecodedAccount;
+use agentmail_client::watch::forward_agent_updates;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_account_decoder_client_types::{UiAccount, UiAccountData, UiAccountEncoding};
+use solana_client::rpc_filter::RpcFilterType;
+use solana_client::rpc_response::{Response, RpcResponseContext};
+use solana_sdk::signer::Signer;
+
+use crate::{
+    fixtures::{
+        deregister_agent::deregister_agent,
+        register_agent::{register_agent, SYSTEM_PROGRAM_ID},
+        update_agent::update_agent,
+    },
+    utils::{pda_utils::find_agent_registry_pda, setup::TestContext},
+};
+
+#[test]
+fn test_decode_registries_mixed_authorities() {
+    let mut context = TestContext::new();
+    let registered = context.create_funded_keypair();
+    let unregistered = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&registered.pubkey());
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &registered.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "nix".to_string(),
+        "https://nix.example.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&registered])
+        .unwrap();
+
+    // Client-side derivation must agree with the test helper
+    let authorities = [registered.pubkey(), unregistered.pubkey()];
+    let registries: Vec<_> = authorities
+        .iter()
+        .map(|authority| {
+            let (pda, _) = agentmail_client::find_agent_registry_pda(authority);
+            assert_eq!(pda, find_agent_registry_pda(authority).0);
+            let account = context.get_account(&pda);
+            decode_maybe_registry(account.as_ref().map(|account| account.data.as_slice()))
+                .expect("Should decode registry")
+        })
+        .collect();
+
+    assert_eq!(registries.len(), 2);
+    let registry = registries[0]
+        .as_ref()
+        .expect("Registered agent should be Some");
+    assert_eq!(registry.discriminator, 1);
+    assert_eq!(registry.bump, bump);
+    assert!(registries[1].is_none(), "Unregistered agent should be None");
+}
+
+#[test]
+fn test_fetch_registries_mixed_authorities() {
+    use solana_client::{rpc_client::RpcClient, rpc_request::RpcRequest};
+    use std::collections::HashMap;
+
+    let mut context = TestContext::new();
+    let registered = context.create_funded_keypair();
+    let unregistered = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&registered.pubkey());
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &registered.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "nix".to_string(),
+        "https://nix.example.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&registered])
+        .unwrap();
+
+    // Serve getMultipleAccounts from the LiteSVM state, in request order
+    let authorities = [unregistered.pubkey(), registered.pubkey()];
+    let accounts: Vec<Option<UiAccount>> = derive_registry_pdas(&authorities)
+        .iter()
+        .map(|(pda, _)| {
+            context
+                .get_account(pda)
+                .map(|account| account_notification(0, &account).value)
+        })
+        .collect();
+    let response = Response {
+        context: RpcResponseContext {
+            slot: 0,
+            api_version: None,
+        },
+        value: accounts,
+    };
+    let mocks = HashMap::from([(
+        RpcRequest::GetMultipleAccounts,
+        serde_json::to_value(response).unwrap(),
+    )]);
+    let rpc = RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks);
+
+    let registries = fetch_registries(&rpc, &authorities).unwrap();
+    assert_eq!(registries.len(), 2);
+    assert!(registries[0].is_none(), "Unregistered agent should be None");
+    let registry = registries[1]
+        .as_ref()
+        .expect("Registered agent should be Some");
+    assert_eq!(registry.authority, registered.pubkey());
+    assert_eq!(registry.name_str().unwrap(), "nix");
+}
+
+#[test]
+fn test_fetch_registries_ignores_foreign_accounts() {
+    use solana_client::{rpc_client::RpcClient, rpc_request::RpcRequest};
+    use std::collections::HashMap;
+
+    let mut context = TestContext::new();
+    let registered = context.create_funded_keypair();
+    let squatted = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&registered.pubkey());
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &registered.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "nix".to_string(),
+        "https://nix.example.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&registered])
+        .unwrap();
+
+    // An empty system account at one PDA, and valid registry bytes owned by
+    // the system program at the other
+    let empty = solana_account::Account {
+        lamports: 1_000_000,
+        owner: SYSTEM_PROGRAM_ID,
+        ..Default::default()
+    };
+    let mut foreign = context.get_account(&agent_registry_pda).unwrap();
+    foreign.owner = SYSTEM_PROGRAM_ID;
+
+    let authorities = [squatted.pubkey(), registered.pubkey()];
+    let response = Response {
+        context: RpcResponseContext {
+            slot: 0,
+            api_version: None,
+        },
+        value: vec![
+            Some(account_notification(0, &empty).value),
+            Some(account_notification(0, &foreign).value),
+        ],
+    };
+    let mocks = HashMap::from([(
+        RpcRequest::GetMultipleAccounts,
+        serde_json::to_value(response).unwrap(),
+    )]);
+    let rpc = RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks);
+
+    let registries = fetch_registries(&rpc, &authorities).unwrap();
+    assert_eq!(registries, vec![None, None]);
+}
+
+#[test]
+fn test_register_signers_same_key() {
+    let mut context = TestContext::new();
+    let authority = context.create_funded_keypair();
+    let same_key = authority.insecure_clone();
+
+    let signers = register_signers(&authority, &same_key);
+    assert_eq!(signers.len(), 1);
+    assert_eq!(signers[0].pubkey(), authority.pubkey());
+}
+
+#[test]
+fn test_register_signers_distinct_keys() {
+    let mut context = TestContext::new();
+    let authority = context.create_funded_keypair();
+
+    let signers = register_signers(&context.payer, &authority);
+    assert_eq!(signers.len(), 2);
+    assert_eq!(signers[0].pubkey(), context.payer.pubkey());
+    assert_eq!(signers[1].pubkey(), authority.pubkey());
+}
+
+#[test]
+fn test_authority_memcmp_filter_matches_layout() {
+    let mut context = TestContext::new();
+    let authority = context.create_funded_keypair();
+    let other = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&authority.pubkey());
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "indexed".to_string(),
+        "https://indexed.example.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&authority])
+        .unwrap();
+
+    let data = context.get_account(&agent_registry_pda).unwrap().data;
+    assert_eq!(
+        &data[AGENT_REGISTRY_AUTHORITY_OFFSET..AGENT_REGISTRY_AUTHORITY_OFFSET + 32],
+        authority.pubkey().as_ref()
+    );
+
+    let RpcFilterType::Memcmp(memcmp) = authority_memcmp_filter(&authority.pubkey()) else {
+        panic!("Expected a memcmp filter");
+    };
+    assert_eq!(memcmp.offset(), AGENT_REGISTRY_AUTHORITY_OFFSET);
+    assert!(memcmp.bytes_match(&data));
+
+    let RpcFilterType::Memcmp(other_memcmp) = authority_memcmp_filter(&other.pubkey()) else {
+        panic!("Expected a memcmp filter");
+    };
+    assert!(!other_memcmp.bytes_match(&data));
+}
+
+#[test]
+fn test_preflight_register_already_registered() {
+    let mut context = TestContext::new();
+    let authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&authority.pubkey());
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "taken".to_string(),
+        "https://taken.example.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&authority])
+        .unwrap();
+
+    let registry = context.get_account(&agent_registry_pda);
+    assert_eq!(
+        registry.as_ref().unwrap().data.len(),
+        AGENT_REGISTRY_ACCOUNT_LEN
+    );
+
+    let rent_minimum = context
+        .svm
+        .minimum_balance_for_rent_exemption(AGENT_REGISTRY_ACCOUNT_LEN);
+    let payer_balance = context.svm.get_balance(&context.payer.pubkey()).unwrap();
+
+    assert_eq!(
+        check_register_state(
+            &agent_registry_pda,
+            registry.is_some(),
+            payer_balance,
+            rent_minimum
+        ),
+        Err(PreflightError::AlreadyRegistered(agent_registry_pda))
+    );
+    assert!(check_register_state(&agent_registry_pda, false, payer_balance, rent_minimum).is_ok());
+}
+
+#[test]
+fn test_preflight_register_name_too_long() {
+    let name = "a".repeat(65);
+    assert_eq!(
+        check_register_args(&name, "https://example.com/inbox"),
+        Err(PreflightError::NameTooLong { len: 65 })
+    );
+    assert!(check_register_args(&"a".repeat(64), "https://example.com/inbox").is_ok());
+}
+
+#[test]
+fn test_encode_update_agent_matches_hand_packed() {
+    let authority = solana_sdk::pubkey::Pubkey::new_unique();
+    let (agent_registry_pda, _) = find_agent_registry_pda(&authority);
+
+    let mut expected = vec![4u8];
+    expected.extend_from_slice(&13u32.to_le_bytes());
+    expected.extend_from_slice(b"updated_agent");
+    expected.extend_from_slice(&24u32.to_le_bytes());
+    expected.extend_from_slice(b"https://newurl.com/inbox");
+
+    let encoded = encode_update_agent("updated_agent", "https://newurl.com/inbox").unwrap();
+    assert_eq!(encoded, expected);
+
+    let instruction = update_agent(
+        &authority,
+        &agent_registry_pda,
+        "updated_agent".to_string(),
+        "https://newurl.com/inbox".to_string(),
+    );
+    assert_eq!(instruction.data, expected);
+
+    assert_eq!(
+        encode_update_agent(&"a".repeat(65), "https://newurl.com/inbox"),
+        Err(PreflightError::NameTooLong { len: 65 })
+    );
+}
+
+fn registry_with_fields(name: [u8; 68], inbox_url: [u8; 260]) -> AgentRegistry {
+    AgentRegistry {
+        discriminator: 1,
+        version: 3,
+        bump: 255,
+        data_version: 5,
+        url_count: 0,
+        accepting: 1,
+        deleted: 0,
+        padding: [0; 3],
+        authority: Default::default(),
+        name,
+        inbox_url,
+        created_at: 0,
+        updated_at: 0,
+        created_slot: 0,
+        inbox_urls: [[0; 132]; 4],
+        metadata_uri: [0; 132],
+        metadata_hash: [0; 32],
+        operator: Default::default(),
+        reserved: [0; 4],
+        last_nonce: 0,
+        accepted_mime_types: [[0; 32]; 4],
+    }
+}
+
+fn prefixed<const N: usize>(len: u32, data: &[u8]) -> [u8; N] {
+    let mut field = [0u8; N];
+    field[..4].copy_from_slice(&len.to_le_bytes());
+    field[4..4 + data.len()].copy_from_slice(data);
+    field
+}
+
+#[test]
+fn test_name_str_full_length_zeros() {
+    // A length of 64 over an all-zero payload is valid UTF-8
+    let registry = registry_with_fields(prefixed(64, &[]), prefixed(0, &[]));
+    assert_eq!(registry.name_str().unwrap(), "\0".repeat(64));
+    assert_eq!(registry.inbox_url_str().unwrap(), "");
+}
+
+#[test]
+fn test_name_str_overlong_prefix() {
+    let registry = registry_with_fields(prefixed(65, &[]), prefixed(u32::MAX, &[]));
+    assert!(registry.name_str().is_err());
+    assert!(registry.inbox_url_str().is_err());
+}
+
+#[test]
+fn test_name_str_invalid_utf8() {
+    let registry = registry_with_fields(prefixed(2, &[0xC3, 0x28]), prefixed(4, b"http"));
+    assert!(registry.name_str().is_err());
+    assert_eq!(registry.inbox_url_str().unwrap(), "http");
+}
+
+#[test]
+fn test_fee_message_register_matches_paid_fee() {
+    let mut context = TestContext::new();
+    let authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&authority.pubkey());
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "priced".to_string(),
+        "https://priced.example.com/inbox".to_string(),
+    );
+
+    let message = fee_message(&instruction, &context.svm.latest_blockhash()).unwrap();
+    assert_eq!(message.account_keys[0], context.payer.pubkey());
+    assert_eq!(message.header.num_required_signatures, 2);
+
+    let balance_before = context.svm.get_balance(&context.payer.pubkey()).unwrap();
+    context
+        .send_transaction(instruction, &[&authority])
+        .unwrap();
+    let balance_after = context.svm.get_balance(&context.payer.pubkey()).unwrap();
+
+    let rent = context
+        .svm
+        .minimum_balance_for_rent_exemption(AGENT_REGISTRY_ACCOUNT_LEN);
+    let paid_fee = balance_before - balance_after - rent;
+    assert!(paid_fee > 0, "Register should cost a non-zero fee");
+    assert_eq!(
+        paid_fee % u64::from(message.header.num_required_signatures),
+        0
+    );
+}
+
+#[test]
+fn test_sort_by_authority_is_order_independent() {
+    let mut context = TestContext::new();
+
+    let mut fetched = Vec::new();
+    for index in 0..4 {
+        let authority = context.create_funded_keypair();
+        let (agent_registry_pda, bump) = find_agent_registry_pda(&authority.pubkey());
+        let instruction = register_agent(
+            &context.payer.pubkey(),
+            &authority.pubkey(),
+            &agent_registry_pda,
+            bump,
+            format!("agent-{index}"),
+            format!("https://agent-{index}.example.com/inbox"),
+        );
+        context
+            .send_transaction(instruction, &[&authority])
+            .unwrap();
+
+        let account = context.get_account(&agent_registry_pda).unwrap();
+        fetched.push(DecodedAccount {
+            address: agent_registry_pda,
+            data: AgentRegistry::from_bytes(&account.data).unwrap(),
+            account,
+        });
+    }
+
+    // Simulate two RPC nodes returning the same accounts in different orders
+    let mut first = fetched.clone();
+    let mut second = fetched;
+    second.reverse();
+    second.swap(0, 2);
+
+    sort_by_authority(&mut first);
+    sort_by_authority(&mut second);
+
+    let addresses = |agents: &[DecodedAccount<AgentRegistry>]| {
+        agents.iter().map(|agent| agent.address).collect::<Vec<_>>()
+    };
+    assert_eq!(addresses(&first), addresses(&second));
+
+    let authorities: Vec<_> = first
+        .iter()
+        .map(|agent| {
+            agent.account.data
+                [AGENT_REGISTRY_AUTHORITY_OFFSET..AGENT_REGISTRY_AUTHORITY_OFFSET + 32]
+                .to_vec()
+        })
+        .collect();
+    assert!(authorities.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+/// Wraps raw account data the way an `accountSubscribe` notification does
+fn account_notification(slot: u64, account: &solana_account::Account) -> Response<UiAccount> {
+    Response {
+        context: RpcResponseContext {
+            slot,
+            api_version: None,
+        },
+        value: UiAccount {
+            lamports: account.lamports,
+            data: UiAccountData::Binary(STANDARD.encode(&account.data), UiAccountEncoding::Base64),
+            owner: account.owner.to_string(),
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+            space: Some(account.data.len() as u64),
+        },
+    }
+}
+
+#[test]
+fn test_watch_agent_callback_receives_new_inbox_url() {
+    let mut context = TestContext::new();
+    let authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&authority.pubkey());
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "watched".to_string(),
+        "https://old.example.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&authority])
+        .unwrap();
+
+    let instruction = update_agent(
+        &authority.pubkey(),
+        &agent_registry_pda,
+        "watched".to_string(),
+        "https://new.example.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&authority])
+        .unwrap();
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    let updates = vec![account_notification(context.get_current_slot(), &account)];
+
+    let mut received = Vec::new();
+    forward_agent_updates(updates, |registry| {
+        received.push(registry.inbox_url_str().unwrap())
+    });
+    assert_eq!(received, vec!["https://new.example.com/inbox".to_string()]);
+}
+
+#[test]
+fn test_total_cost_estimate_large_fleet() {
+    let context = TestContext::new();
+    let rent = context
+        .svm
+        .minimum_balance_for_rent_exemption(AGENT_REGISTRY_ACCOUNT_LEN);
+
+    assert_eq!(total_cost_estimate(rent, 100_000).unwrap(), rent * 100_000);
+    assert!(total_cost_estimate(rent, u64::MAX).is_err());
+    assert_eq!(total_cost_estimate(rent, 0).unwrap(), 0);
+}
+
+#[test]
+fn test_account_kind_matches_program_discriminators() {
+    for byte in AgentMailAccountDiscriminators::ALL {
+        let kind = AccountKind::try_from(byte).expect("client is missing an account kind");
+        assert_eq!(kind as u8, byte);
+        assert_eq!(account_type(&[byte, 0, 0]), Some(kind));
+    }
+
+    // Both sides must also reject exactly the same bytes
+    for byte in 0..=u8::MAX {
+        assert_eq!(
+            AgentMailAccountDiscriminators::try_from(byte)
+                .ok()
+                .map(|d| d as u8),
+            AccountKind::try_from(byte).ok().map(|k| k as u8),
+            "discriminator {byte} disagrees between program and client"
+        );
+    }
+    assert_eq!(account_type(&[]), None);
+}
+
+#[test]
+fn test_min_data_lens_match_program() {
+    use agentmail::instructions::{
+        CheckRegistryBatchData, RegisterAgentData, SetAcceptingData, SetOperatorData,
+        UpdateAgentData,
+    };
+    use agentmail::traits::InstructionData;
+    use agentmail_client::encode::{
+        encode_register_agent_unchecked, CHECK_REGISTRY_BATCH_MIN_DATA_LEN,
+        REGISTER_AGENT_MIN_DATA_LEN, SET_ACCEPTING_MIN_DATA_LEN, SET_OPERATOR_MIN_DATA_LEN,
+        UPDATE_AGENT_MIN_DATA_LEN,
+    };
+
+    // The client lengths add the discriminator the program strips first
+    assert_eq!(REGISTER_AGENT_MIN_DATA_LEN, 1 + RegisterAgentData::MIN_LEN);
+    assert_eq!(UPDATE_AGENT_MIN_DATA_LEN, 1 + UpdateAgentData::MIN_LEN);
+    assert_eq!(SET_ACCEPTING_MIN_DATA_LEN, 1 + SetAcceptingData::MIN_LEN);
+    assert_eq!(SET_OPERATOR_MIN_DATA_LEN, 1 + SetOperatorData::MIN_LEN);
+    assert_eq!(
+        CHECK_REGISTRY_BATCH_MIN_DATA_LEN,
+        1 + CheckRegistryBatchData::MIN_LEN
+    );
+
+    let data = encode_register_agent_unchecked(255, "ab", "");
+    assert_eq!(data.len(), REGISTER_AGENT_MIN_DATA_LEN);
+    assert!(RegisterAgentData::try_from(&data[1..]).is_ok());
+    assert!(RegisterAgentData::try_from(&data[1..data.len() - 1]).is_err());
+}
+
+#[test]
+fn test_derive_registry_pdas_matches_single_derivation() {
+    let authorities: Vec<_> = (0..5)
+        .map(|_| solana_sdk::pubkey::Pubkey::new_unique())
+        .collect();
+
+    let derived = derive_registry_pdas(&authorities);
+    assert_eq!(derived.len(), authorities.len());
+    for (authority, pda) in authorities.iter().zip(&derived) {
+        assert_eq!(
+            *pda,
+            agentmail_client::registry::find_agent_registry_pda(authority)
+        );
+    }
+    assert!(derive_registry_pdas(&[]).is_empty());
+}
+
+#[test]
+fn test_is_registered_registered_and_unregistered() {
+    let mut context = TestContext::new();
+    let registered = context.create_funded_keypair();
+    let unregistered = context.create_funded_keypair();
+
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&registered.pubkey());
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &registered.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "gated".to_string(),
+        "https://gated.example.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&registered])
+        .unwrap();
+
+    // `is_registered` fetches a data slice up to the deleted flag; mirror that here
+    let check = |authority: &solana_sdk::pubkey::Pubkey| {
+        let (pda, _) = agentmail_client::find_agent_registry_pda(authority);
+        let account = context.get_account(&pda);
+        account.is_some_and(|account| {
+            let data = &account.data[..AGENT_REGISTRY_DELETED_OFFSET + 1];
+            account.owner == agentmail_client::AGENTMAIL_ID
+                && is_registry_data(Some(&data[..1]))
+                && data[AGENT_REGISTRY_DELETED_OFFSET] == 0
+        })
+    };
+    assert!(check(&registered.pubkey()));
+    assert!(!check(&unregistered.pubkey()));
+
+    assert!(!is_registry_data(None));
+    assert!(!is_registry_data(Some(&[])));
+    assert!(!is_registry_data(Some(&[0])));
+}
+
+#[test]
+fn test_account_metas_match_program_layout() {
+    use agentmail_client::instructions::{DeregisterAgent, RegisterAgent, UpdateAgent};
+    use agentmail_client::AGENTMAIL_ID;
+    use solana_sdk::instruction::AccountMeta;
+
+    use crate::fixtures::register_agent::SYSTEM_PROGRAM_ID;
+
+    let payer = solana_sdk::pubkey::Pubkey::new_unique();
+    let authority = solana_sdk::pubkey::Pubkey::new_unique();
+    let registry = solana_sdk::pubkey::Pubkey::new_unique();
+    let vault = solana_sdk::pubkey::Pubkey::new_unique();
+
+    assert_eq!(
+        RegisterAgent::account_metas(&payer, &authority, &registry),
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(registry, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(AGENTMAIL_ID, false),
+        ]
+    );
+    assert_eq!(
+        UpdateAgent::account_metas(&authority, &registry),
+        vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(registry, false),
+            AccountMeta::new_readonly(AGENTMAIL_ID, false),
+        ]
+    );
+    assert_eq!(
+        UpdateAgent::account_metas_with_payer(&authority, &registry, Some(&payer)),
+        vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(registry, false),
+            AccountMeta::new_readonly(AGENTMAIL_ID, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new(payer, true),
+        ]
+    );
+    assert_eq!(
+        DeregisterAgent::account_metas(&authority, &registry, Some(&vault)),
+        vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(registry, false),
+            AccountMeta::new_readonly(AGENTMAIL_ID, false),
+            AccountMeta::new(vault, false),
+        ]
+    );
+}
+
+#[test]
+fn test_timestamps_round_trip_program_to_client() {
+    use agentmail::state::AgentRegistry as ProgramAgentRegistry;
+    use agentmail::traits::AccountSerialize;
+
+    // Every byte distinct, so a swapped byte order cannot decode to the same value
+    let created_at: i64 = 0x0102_0304_0506_0708;
+    let updated_at: i64 = -0x0A0B_0C0D_0E0F_1011;
+    let created_slot: u64 = 0x1112_1314_1516_1718;
+
+    let mut registry = ProgramAgentRegistry::new(
+        7,
+        solana_address::Address::new_from_array([9u8; 32]),
+        "endian",
+        "https://example.com/inbox",
+        created_at,
+        created_slot,
+    )
+    .unwrap();
+    // Set directly, since touch() clamps updated_at to created_at
+    registry.updated_at = updated_at;
+    let bytes = registry.to_bytes();
+
+    // The program writes little-endian at the documented account offsets
+    assert_eq!(&bytes[370..378], &created_at.to_le_bytes());
+    assert_eq!(&bytes[378..386], &updated_at.to_le_bytes());
+    assert_eq!(&bytes[386..394], &created_slot.to_le_bytes());
+
+    let decoded = AgentRegistry::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.created_at, created_at);
+    assert_eq!(decoded.updated_at, updated_at);
+    assert_eq!(decoded.created_slot, created_slot);
+}
+
+#[test]
+fn test_registry_filters_count_tracks_register_and_deregister() {
+    let mut context = TestContext::new();
+    let authorities: Vec<_> = (0..3).map(|_| context.create_funded_keypair()).collect();
+
+    // Apply the filters `count_agents` sends to the RPC node
+    let count = |context: &TestContext| {
+        authorities
+            .iter()
+            .filter_map(|authority| {
+                context.get_account(&find_agent_registry_pda(&authority.pubkey()).0)
+            })
+            .filter(|account| {
+                registry_filters().iter().all(|filter| match filter {
+                    RpcFilterType::DataSize(len) => account.data.len() as u64 == *len,
+                    RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(&account.data),
+                    _ => unreachable!("unexpected registry filter"),
+                })
+            })
+            .count()
+    };
+    assert_eq!(count(&context), 0);
+
+    for (i, authority) in authorities.iter().enumerate() {
+        let (agent_registry_pda, bump) = find_agent_registry_pda(&authority.pubkey());
+        let instruction = register_agent(
+            &context.payer.pubkey(),
+            &authority.pubkey(),
+            &agent_registry_pda,
+            bump,
+            format!("counted-{i}"),
+            "https://counted.example.com/inbox".to_string(),
+        );
+        context.send_transaction(instruction, &[authority]).unwrap();
+        assert_eq!(count(&context), i + 1);
+    }
+
+    let (agent_registry_pda, _) = find_agent_registry_pda(&authorities[1].pubkey());
+    let instruction = deregister_agent(&authorities[1].pubkey(), &agent_registry_pda);
+    context
+        .send_transaction(instruction, &[&authorities[1]])
+        .unwrap();
+    assert_eq!(count(&context), 2);
+}
+
+#[test]
+fn test_audit_reports_only_corrupt_accounts() {
+    use agentmail_client::audit::{audit_account, AuditFinding, AuditIssue};
+    use solana_sdk::account::Account;
+
+    let mut context = TestContext::new();
+    let authorities: Vec<_> = (0..3).map(|_| context.create_funded_keypair()).collect();
+
+    let mut addresses = Vec::new();
+    for (i, authority) in authorities.iter().enumerate() {
+        let (agent_registry_pda, bump) = find_agent_registry_pda(&authority.pubkey());
+        let instruction = register_agent(
+            &context.payer.pubkey(),
+            &authority.pubkey(),
+            &agent_registry_pda,
+            bump,
+            format!("audited-{i}"),
+            "https://audited.example.com/inbox".to_string(),
+        );
+        context.send_transaction(instruction, &[authority]).unwrap();
+        addresses.push(agent_registry_pda);
+    }
+
+    // Corrupt the name length prefix of the second registry
+    let corrupt = addresses[1];
+    let name_offset = AGENT_REGISTRY_AUTHORITY_OFFSET + 32;
+    let mut account = context.get_account(&corrupt).unwrap();
+    account.data[name_offset..name_offset + 4].copy_from_slice(&1000u32.to_le_bytes());
+    context.svm.set_account(corrupt, account).unwrap();
+
+    // A stray program-owned account with no known discriminator
+    let stray = solana_sdk::pubkey::Pubkey::new_unique();
+    context
+        .svm
+        .set_account(
+            stray,
+            Account {
+                lamports: 1_000_000,
+                data: vec![0xFF; 16],
+                owner: agentmail_client::AGENTMAIL_ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+    addresses.push(stray);
+
+    // `audit_program` runs the same check over every `getProgramAccounts` result
+    let findings: Vec<AuditFinding> = addresses
+        .iter()
+        .flat_map(|address| audit_account(address, &context.get_account(address).unwrap().data))
+        .collect();
+
+    assert_eq!(
+        findings,
+        vec![
+            AuditFinding {
+                address: corrupt,
+                issue: AuditIssue::CorruptLengthPrefix {
+                    field: "name",
+                    len: 1000,
+                },
+            },
+            AuditFinding {
+                address: stray,
+                issue: AuditIssue::UnknownDiscriminator(Some(0xFF)),
+            },
+        ]
+    );
+
+    // A healthy registry copied to the wrong address fails the PDA check
+    let moved = solana_sdk::pubkey::Pubkey::new_unique();
+    let data = context.get_account(&addresses[0]).unwrap().data;
+    let (expected, _) = find_agent_registry_pda(&authorities[0].pubkey());
+    assert_eq!(
+        audit_account(&moved, &data),
+        vec![AuditFinding {
+            address: moved,
+            issue: AuditIssue::PdaMismatch {
+                expected,
+                bump: data[2],
+            },
+        }]
+    );
+}
+
+#[test]
+fn test_register_instruction_recreates_registry_elsewhere() {
+    let mut source = TestContext::new();
+    let authority = source.create_funded_keypair();
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&authority.pubkey());
+    let instruction = register_agent(
+        &source.payer.pubkey(),
+        &authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "exported".to_string(),
+        "https://export.example.com/inbox".to_string(),
+    );
+    source.send_transaction(instruction, &[&authority]).unwrap();
+    let exported =
+        AgentRegistry::from_bytes(&source.get_account(&agent_registry_pda).unwrap().data).unwrap();
+
+    // Import into a separate cluster at a later time
+    let mut target = TestContext::new();
+    target
+        .airdrop_if_required(&authority.pubkey(), 1_000_000_000)
+        .unwrap();
+    target.warp_to_timestamp(exported.created_at + 86_400);
+    let instruction = exported
+        .register_instruction(&target.payer.pubkey())
+        .unwrap();
+    assert_eq!(instruction.accounts[2].pubkey, agent_registry_pda);
+    target.send_transaction(instruction, &[&authority]).unwrap();
+
+    let imported =
+        AgentRegistry::from_bytes(&target.get_account(&agent_registry_pda).unwrap().data).unwrap();
+    assert_eq!(imported.authority, exported.authority);
+    assert_eq!(imported.bump, exported.bump);
+    assert_eq!(imported.name_str().unwrap(), "exported");
+    assert_eq!(
+        imported.inbox_url_str().unwrap(),
+        "https://export.example.com/inbox"
+    );
+    assert_eq!(imported.created_at, exported.created_at + 86_400);
+}
+
+#[test]
+fn test_verify_agent_signature() {
+    use agentmail_client::registry::verify_agent_signature;
+
+    let mut context = TestContext::new();
+    let authority = context.create_funded_keypair();
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&authority.pubkey());
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "signer".to_string(),
+        "https://signer.example.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&authority])
+        .unwrap();
+    let registry =
+        AgentRegistry::from_bytes(&context.get_account(&agent_registry_pda).unwrap().data).unwrap();
+
+    let message = br#"{"from":"agent","subject":"hello"}"#;
+    let signature = authority.sign_message(message);
+    assert!(verify_agent_signature(&registry, message, &signature));
+
+    // Tampered message
+    let tampered = br#"{"from":"agent","subject":"hellO"}"#;
+    assert!(!verify_agent_signature(&registry, tampered, &signature));
+
+    // Tampered signature
+    let mut bytes: [u8; 64] = signature.into();
+    bytes[0] ^= 1;
+    assert!(!verify_agent_signature(&registry, message, &bytes.into()));
+
+    // Signed by a key other than the registry's authority
+    let other = context.create_funded_keypair();
+    assert!(!verify_agent_signature(
+        &registry,
+        message,
+        &other.sign_message(message)
+    ));
+}
+
+#[test]
+fn test_address_pubkey_round_trip() {
+    use agentmail_client::address::{to_address, to_pubkey};
+    use solana_sdk::pubkey::Pubkey;
+
+    for bytes in [[0u8; 32], [0xFF; 32], core::array::from_fn(|i| i as u8)] {
+        let pubkey = Pubkey::new_from_array(bytes);
+        let address = to_address(pubkey);
+        assert_eq!(address.to_bytes(), bytes);
+        assert_eq!(to_pubkey(address), pubkey);
+    }
+
+    let address = solana_address::Address::new_unique();
+    assert_eq!(to_address(to_pubkey(address)), address);
+}
+
+#[test]
+fn test_client_pda_verifies_on_program_side() {
+    use agentmail::state::AgentRegistry as ProgramAgentRegistry;
+    use agentmail_client::address::to_address;
+    use agentmail_client::registry::find_agent_registry_pda as client_find_pda;
+
+    let authority = solana_sdk::pubkey::Pubkey::new_unique();
+    let (pda, bump) = client_find_pda(&authority);
+    assert!(ProgramAgentRegistry::validate_pda_for_authority(
+        &to_address(pda),
+        &to_address(authority),
+        &to_address(agentmail_client::AGENTMAIL_ID),
+        Some(bump),
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_error_codes_cover_every_program_error() {
+    use agentmail::errors::AgentMailProgramError::*;
+    use agentmail_client::error_codes::{error_message, ERROR_CODES, ERROR_CODE_OFFSET};
+    use solana_sdk::program_error::ProgramError;
+
+    let variants = [
+        InvalidAuthority,
+        NameTooLong,
+        InboxUrlTooLong,
+        InvalidNameLength,
+        InvalidInboxUrlLength,
+        InvalidUtf8,
+        RegistryAlreadyExists,
+        RegistryDoesNotExist,
+        InvalidAccountSize,
+        InvalidAccountData,
+        InvalidNameCharacters,
+        CounterInstructionsRemoved,
+        BatchTooLarge,
+        AccountTooSmall,
+        AccountTooLarge,
+        TooManyInboxUrls,
+        InvalidDiscriminator,
+        MetadataUriTooLong,
+        NoChanges,
+        NameTooShort,
+        InboxUrlMissingPath,
+        ClockUnavailable,
+        StaleNonce,
+        TooManyMimeTypes,
+        InvalidMimeType,
+    ];
+    // Fails to compile when a variant is added, so the list above stays complete
+    for variant in &variants {
+        match variant {
+            InvalidAuthority
+            | NameTooLong
+            | InboxUrlTooLong
+            | InvalidNameLength
+            | InvalidInboxUrlLength
+            | InvalidUtf8
+            | RegistryAlreadyExists
+            | RegistryDoesNotExist
+            | InvalidAccountSize
+            | InvalidAccountData
+            | InvalidNameCharacters
+            | CounterInstructionsRemoved
+            | BatchTooLarge
+            | AccountTooSmall
+            | AccountTooLarge
+            | TooManyInboxUrls
+            | InvalidDiscriminator
+            | MetadataUriTooLong
+            | NoChanges
+            | NameTooShort
+            | InboxUrlMissingPath
+            | ClockUnavailable
+            | StaleNonce
+            | TooManyMimeTypes
+            | InvalidMimeType => {}
+        }
+    }
+
+    assert_eq!(ERROR_CODES.len(), variants.len());
+    for (index, variant) in variants.into_iter().enumerate() {
+        let (code, message) = ERROR_CODES[index];
+        assert_eq!(code, ERROR_CODE_OFFSET + index as u32);
+        assert_eq!(message, variant.to_string());
+        assert_eq!(ProgramError::from(variant), ProgramError::Custom(code));
+        assert_eq!(error_message(code), Some(message));
+    }
+    assert_eq!(error_message(ERROR_CODE_OFFSET - 1), None);
+    assert_eq!(
+        error_message(ERROR_CODE_OFFSET + ERROR_CODES.len() as u32),
+        None
+    );
+}
@@ -0,0 +1,223 @@
+use agentmail_client::{
+    registry::{RENT_VAULT_ACCOUNT_LEN, RENT_VAULT_AUTHORITY_OFFSET, RENT_VAULT_DISCRIMINATOR},
+    AGENTMAIL_ID,
+};
+use solana_sdk::{
+    account::Account, instruction::InstructionError, pubkey::Pubkey, signature::Keypair,
+    signer::Signer,
+};
+
+use crate::{
+    fixtures::{
+        register_agent::register_agent,
+        rent_vault::{
+            create_rent_vault, deregister_agent_to_vault, withdraw_rent_vault,
+            CreateRentVaultFixture, WithdrawRentVaultFixture,
+        },
+    },
+    utils::{
+        assertions::{assert_account_not_exists, assert_custom_error, assert_instruction_error},
+        pda_utils::{find_agent_registry_pda, find_rent_vault_pda},
+        setup::TestContext,
+        test_helpers::test_declared_roles,
+        Address,
+    },
+};
+
+fn setup_rent_vault(context: &mut TestContext, vault_authority: &Keypair) -> Address {
+    let instruction = create_rent_vault(&context.payer.pubkey(), &vault_authority.pubkey());
+    context
+        .send_transaction(instruction, &[vault_authority])
+        .unwrap();
+    find_rent_vault_pda(&vault_authority.pubkey()).0
+}
+
+fn setup_agent_registry(context: &mut TestContext, agent_authority: &Keypair) -> Address {
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "pooled-agent".to_string(),
+        "https://pooled.example.com/inbox".to_string(),
+    );
+
+    context
+        .send_transaction(instruction, &[agent_authority])
+        .unwrap();
+    agent_registry_pda
+}
+
+/// Plants a program-owned account holding `data` at `address`
+fn setup_program_owned_account(context: &mut TestContext, address: Address, data: Vec<u8>) {
+    let lamports = context.svm.minimum_balance_for_rent_exemption(data.len());
+    context
+        .svm
+        .set_account(
+            address,
+            Account {
+                lamports,
+                data,
+                owner: AGENTMAIL_ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_create_rent_vault() {
+    let mut context = TestContext::new();
+    let vault_authority = context.create_funded_keypair();
+
+    let rent_vault = setup_rent_vault(&mut context, &vault_authority);
+
+    let account = context.get_account(&rent_vault).unwrap();
+    assert_eq!(account.owner, AGENTMAIL_ID);
+    assert_eq!(account.data.len(), RENT_VAULT_ACCOUNT_LEN);
+    assert_eq!(account.data[0], RENT_VAULT_DISCRIMINATOR);
+    assert_eq!(
+        &account.data[RENT_VAULT_AUTHORITY_OFFSET..],
+        vault_authority.pubkey().as_ref()
+    );
+
+    // A second create finds the vault already program-owned
+    context.warp_to_next_slot();
+    let instruction = create_rent_vault(&context.payer.pubkey(), &vault_authority.pubkey());
+    let error = context.send_transaction_expect_error(instruction, &[&vault_authority]);
+    assert_instruction_error(error, InstructionError::InvalidAccountOwner);
+}
+
+#[test]
+fn test_deregister_agent_to_rent_vault() {
+    let mut context = TestContext::new();
+    let vault_authority = context.create_funded_keypair();
+    let agent_authority = context.create_funded_keypair();
+
+    let rent_vault = setup_rent_vault(&mut context, &vault_authority);
+    let agent_registry_pda = setup_agent_registry(&mut context, &agent_authority);
+
+    let registry_lamports = context.get_account(&agent_registry_pda).unwrap().lamports;
+    let vault_before = context.get_account(&rent_vault).unwrap().lamports;
+    let authority_before = context.svm.get_balance(&agent_authority.pubkey()).unwrap();
+
+    let instruction =
+        deregister_agent_to_vault(&agent_authority.pubkey(), &agent_registry_pda, &rent_vault);
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    assert_account_not_exists(&context, &agent_registry_pda);
+    let vault_after = context.get_account(&rent_vault).unwrap().lamports;
+    assert_eq!(vault_after, vault_before + registry_lamports);
+    assert_eq!(
+        context.svm.get_balance(&agent_authority.pubkey()).unwrap(),
+        authority_before
+    );
+}
+
+#[test]
+fn test_deregister_agent_to_non_vault_account() {
+    let mut context = TestContext::new();
+    let vault_authority = context.create_funded_keypair();
+    let agent_authority = context.create_funded_keypair();
+    let other_authority = context.create_funded_keypair();
+
+    let rent_vault = setup_rent_vault(&mut context, &vault_authority);
+    let agent_registry_pda = setup_agent_registry(&mut context, &agent_authority);
+
+    // Another agent's registry is program-owned, but not a vault
+    let other_registry = setup_agent_registry(&mut context, &other_authority);
+    let instruction = deregister_agent_to_vault(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        &other_registry,
+    );
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    assert_custom_error(error, 116); // InvalidDiscriminator
+
+    // Vault data copied to another address fails the derivation check
+    let impostor = Address::new_unique();
+    let vault_data = context.get_account(&rent_vault).unwrap().data;
+    setup_program_owned_account(&mut context, impostor, vault_data);
+    let instruction =
+        deregister_agent_to_vault(&agent_authority.pubkey(), &agent_registry_pda, &impostor);
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    assert_instruction_error(error, InstructionError::InvalidSeeds);
+
+    assert!(context.get_account(&agent_registry_pda).is_some());
+}
+
+#[test]
+fn test_withdraw_rent_vault_keeps_rent_exempt_minimum() {
+    let mut context = TestContext::new();
+    let vault_authority = context.create_funded_keypair();
+    let agent_authority = context.create_funded_keypair();
+    let destination = Pubkey::new_unique();
+
+    let rent_vault = setup_rent_vault(&mut context, &vault_authority);
+    let agent_registry_pda = setup_agent_registry(&mut context, &agent_authority);
+    let registry_lamports = context.get_account(&agent_registry_pda).unwrap().lamports;
+
+    let instruction =
+        deregister_agent_to_vault(&agent_authority.pubkey(), &agent_registry_pda, &rent_vault);
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    // Anything above the vault's own rent is withdrawable, and no more
+    let instruction = withdraw_rent_vault(
+        &vault_authority.pubkey(),
+        &destination,
+        registry_lamports + 1,
+    );
+    let error = context.send_transaction_expect_error(instruction, &[&vault_authority]);
+    assert_instruction_error(error, InstructionError::InsufficientFunds);
+
+    let instruction =
+        withdraw_rent_vault(&vault_authority.pubkey(), &destination, registry_lamports);
+    context
+        .send_transaction(instruction, &[&vault_authority])
+        .unwrap();
+
+    assert_eq!(
+        context.svm.get_balance(&destination).unwrap(),
+        registry_lamports
+    );
+    assert_eq!(
+        context.get_account(&rent_vault).unwrap().lamports,
+        context
+            .svm
+            .minimum_balance_for_rent_exemption(RENT_VAULT_ACCOUNT_LEN)
+    );
+}
+
+#[test]
+fn test_withdraw_rent_vault_wrong_authority() {
+    let mut context = TestContext::new();
+    let vault_authority = context.create_funded_keypair();
+    let attacker = context.create_funded_keypair();
+
+    let rent_vault = setup_rent_vault(&mut context, &vault_authority);
+
+    // The attacker signs, but names the victim's vault
+    let mut instruction = withdraw_rent_vault(&vault_authority.pubkey(), &attacker.pubkey(), 1);
+    instruction.accounts[0].pubkey = attacker.pubkey();
+    assert_eq!(instruction.accounts[1].pubkey, rent_vault);
+
+    let error = context.send_transaction_expect_error(instruction, &[&attacker]);
+    assert_custom_error(error, 100); // InvalidAuthority
+}
+
+#[test]
+fn test_create_rent_vault_account_roles() {
+    test_declared_roles::<CreateRentVaultFixture>();
+}
+
+#[test]
+fn test_withdraw_rent_vault_account_roles() {
+    test_declared_roles::<WithdrawRentVaultFixture>();
+}
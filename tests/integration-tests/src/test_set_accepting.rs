@@ -0,0 +1,109 @@
+use solana_sdk::{instruction::InstructionError, signature::Keypair, signer::Signer};
+
+use crate::{
+    fixtures::{
+        register_agent::register_agent,
+        set_accepting::{read_updated_at, set_accepting, ACCEPTING_OFFSET, UPDATED_AT_OFFSET},
+    },
+    utils::{
+        assertions::{assert_custom_error, assert_instruction_error},
+        pda_utils::find_agent_registry_pda,
+        setup::TestContext,
+        Address,
+    },
+};
+
+fn setup_agent_registry(context: &mut TestContext, agent_authority: &Keypair) -> Address {
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "available".to_string(),
+        "https://available.example.com/inbox".to_string(),
+    );
+
+    context
+        .send_transaction(instruction, &[agent_authority])
+        .unwrap();
+    agent_registry_pda
+}
+
+#[test]
+fn test_register_agent_defaults_to_accepting() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let agent_registry_pda = setup_agent_registry(&mut context, &agent_authority);
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(account.data[ACCEPTING_OFFSET], 1);
+}
+
+#[test]
+fn test_set_accepting_toggles_and_touches() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let agent_registry_pda = setup_agent_registry(&mut context, &agent_authority);
+
+    // Zero updated_at so the touch is observable
+    let mut account = context.get_account(&agent_registry_pda).unwrap();
+    account.data[UPDATED_AT_OFFSET..UPDATED_AT_OFFSET + 8].fill(0);
+    context
+        .svm
+        .set_account(agent_registry_pda, account)
+        .unwrap();
+
+    let instruction = set_accepting(&agent_authority.pubkey(), &agent_registry_pda, false);
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(account.data[ACCEPTING_OFFSET], 0);
+    assert!(
+        read_updated_at(&account.data) > 0,
+        "updated_at should be touched"
+    );
+
+    context.warp_to_next_slot();
+    let instruction = set_accepting(&agent_authority.pubkey(), &agent_registry_pda, true);
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(account.data[ACCEPTING_OFFSET], 1);
+}
+
+#[test]
+fn test_set_accepting_wrong_authority() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+    let attacker = context.create_funded_keypair();
+
+    let agent_registry_pda = setup_agent_registry(&mut context, &agent_authority);
+
+    let instruction = set_accepting(&attacker.pubkey(), &agent_registry_pda, false);
+    let error = context.send_transaction_expect_error(instruction, &[&attacker]);
+    assert_custom_error(error, 100); // InvalidAuthority
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(account.data[ACCEPTING_OFFSET], 1);
+}
+
+#[test]
+fn test_set_accepting_invalid_flag() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let agent_registry_pda = setup_agent_registry(&mut context, &agent_authority);
+
+    let mut instruction = set_accepting(&agent_authority.pubkey(), &agent_registry_pda, true);
+    instruction.data[1] = 2;
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    assert_instruction_error(error, InstructionError::InvalidInstructionData);
+}
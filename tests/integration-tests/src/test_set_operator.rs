@@ -0,0 +1,151 @@
+use agentmail_client::registry::{operator_memcmp_filter, AGENT_REGISTRY_OPERATOR_OFFSET};
+use solana_client::rpc_filter::RpcFilterType;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+use crate::{
+    fixtures::{
+        register_agent::register_agent,
+        set_accepting::{read_updated_at, UPDATED_AT_OFFSET},
+        set_operator::{read_operator, set_operator, with_operator, OPERATOR_OFFSET},
+    },
+    utils::{
+        assertions::assert_custom_error, pda_utils::find_agent_registry_pda, setup::TestContext,
+        Address,
+    },
+};
+
+fn setup_agent_registry(
+    context: &mut TestContext,
+    agent_authority: &Keypair,
+    operator: Option<&Pubkey>,
+) -> Address {
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+
+    let mut instruction = register_agent(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "fleet-agent".to_string(),
+        "https://fleet.example.com/inbox".to_string(),
+    );
+    if let Some(operator) = operator {
+        instruction = with_operator(instruction, operator);
+    }
+
+    context
+        .send_transaction(instruction, &[agent_authority])
+        .unwrap();
+    agent_registry_pda
+}
+
+#[test]
+fn test_register_agent_without_operator() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let agent_registry_pda = setup_agent_registry(&mut context, &agent_authority, None);
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(read_operator(&account.data), Pubkey::default());
+}
+
+#[test]
+fn test_register_agent_with_operator() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+    let operator = Pubkey::new_unique();
+
+    let agent_registry_pda = setup_agent_registry(&mut context, &agent_authority, Some(&operator));
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(read_operator(&account.data), operator);
+}
+
+#[test]
+fn test_set_operator_sets_clears_and_touches() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+    let operator = Pubkey::new_unique();
+
+    let agent_registry_pda = setup_agent_registry(&mut context, &agent_authority, None);
+
+    // Zero updated_at so the touch is observable
+    let mut account = context.get_account(&agent_registry_pda).unwrap();
+    account.data[UPDATED_AT_OFFSET..UPDATED_AT_OFFSET + 8].fill(0);
+    context
+        .svm
+        .set_account(agent_registry_pda, account)
+        .unwrap();
+
+    let instruction = set_operator(&agent_authority.pubkey(), &agent_registry_pda, &operator);
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(read_operator(&account.data), operator);
+    assert!(
+        read_updated_at(&account.data) > 0,
+        "updated_at should be touched"
+    );
+
+    context.warp_to_next_slot();
+    let instruction = set_operator(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        &Pubkey::default(),
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(read_operator(&account.data), Pubkey::default());
+}
+
+#[test]
+fn test_set_operator_wrong_authority() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+    let attacker = context.create_funded_keypair();
+
+    let agent_registry_pda = setup_agent_registry(&mut context, &agent_authority, None);
+
+    let instruction = set_operator(&attacker.pubkey(), &agent_registry_pda, &attacker.pubkey());
+    let error = context.send_transaction_expect_error(instruction, &[&attacker]);
+    assert_custom_error(error, 100); // InvalidAuthority
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(read_operator(&account.data), Pubkey::default());
+}
+
+#[test]
+fn test_operator_memcmp_filter_matches_fleet() {
+    let mut context = TestContext::new();
+    let operator = Pubkey::new_unique();
+    let in_fleet = context.create_funded_keypair();
+    let joined_later = context.create_funded_keypair();
+    let outsider = context.create_funded_keypair();
+
+    let in_fleet_pda = setup_agent_registry(&mut context, &in_fleet, Some(&operator));
+    let joined_later_pda = setup_agent_registry(&mut context, &joined_later, None);
+    let outsider_pda = setup_agent_registry(&mut context, &outsider, None);
+
+    let instruction = set_operator(&joined_later.pubkey(), &joined_later_pda, &operator);
+    context
+        .send_transaction(instruction, &[&joined_later])
+        .unwrap();
+
+    assert_eq!(AGENT_REGISTRY_OPERATOR_OFFSET, OPERATOR_OFFSET);
+    let RpcFilterType::Memcmp(memcmp) = operator_memcmp_filter(&operator) else {
+        panic!("Expected a memcmp filter");
+    };
+    assert_eq!(memcmp.offset(), AGENT_REGISTRY_OPERATOR_OFFSET);
+
+    let matches: Vec<Address> = [in_fleet_pda, joined_later_pda, outsider_pda]
+        .into_iter()
+        .filter(|pda| memcmp.bytes_match(&context.get_account(pda).unwrap().data))
+        .collect();
+    assert_eq!(matches, vec![in_fleet_pda, joined_later_pda]);
+}
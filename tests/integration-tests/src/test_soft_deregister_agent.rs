@@ -0,0 +1,216 @@
+use agentmail::state::AgentRegistry;
+use agentmail_client::{instructions, registry::registry_filters};
+use solana_client::rpc_filter::RpcFilterType;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+use crate::{
+    fixtures::{
+        deregister_agent::deregister_agent,
+        register_agent::register_agent,
+        soft_deregister_agent::{reactivate_agent, soft_deregister_agent, DELETED_OFFSET},
+    },
+    utils::{
+        assertions::{assert_account_not_exists, assert_custom_error},
+        pda_utils::find_agent_registry_pda,
+        setup::TestContext,
+        Address,
+    },
+};
+
+/// Byte offset of `created_at` within AgentRegistry account data
+const CREATED_AT_OFFSET: usize = 370;
+fn setup_agent_registry(context: &mut TestContext, agent_authority: &Keypair) -> Address {
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "returning-agent".to_string(),
+        "https://returning.example.com/inbox".to_string(),
+    );
+
+    context
+        .send_transaction(instruction, &[agent_authority])
+        .unwrap();
+    agent_registry_pda
+}
+
+/// Whether the agent is visible to directory listings and CPI recipient checks
+fn is_visible(context: &mut TestContext, registry: &Pubkey, authority: &Pubkey) -> bool {
+    let account = context.get_account(registry).unwrap();
+    let listed = registry_filters().iter().all(|filter| match filter {
+        RpcFilterType::DataSize(len) => account.data.len() as u64 == *len,
+        RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(&account.data),
+        _ => unreachable!("unexpected registry filter"),
+    });
+
+    let instruction = instructions::check_registry_batch(&[*registry], &[*authority]);
+    let return_data = context
+        .send_transaction_return_data(instruction, &[])
+        .unwrap();
+    let checked = u32::from_le_bytes(return_data.try_into().unwrap()) == 1;
+
+    assert_eq!(listed, checked, "filters and CheckRegistryBatch disagree");
+    listed
+}
+
+#[test]
+fn test_soft_deregister_hides_and_reactivate_restores() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+    let authority = agent_authority.pubkey();
+
+    let agent_registry_pda = setup_agent_registry(&mut context, &agent_authority);
+    let registered = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(registered.data[DELETED_OFFSET], 0);
+    assert!(is_visible(&mut context, &agent_registry_pda, &authority));
+
+    let instruction = soft_deregister_agent(&authority, &agent_registry_pda);
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    // The account, its rent and created_at survive
+    let deleted = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(deleted.data[DELETED_OFFSET], 1);
+    assert_eq!(deleted.lamports, registered.lamports);
+    assert_eq!(
+        deleted.data[CREATED_AT_OFFSET..CREATED_AT_OFFSET + 8],
+        registered.data[CREATED_AT_OFFSET..CREATED_AT_OFFSET + 8]
+    );
+    context.warp_to_next_slot();
+    assert!(!is_visible(&mut context, &agent_registry_pda, &authority));
+
+    let instruction = reactivate_agent(&authority, &agent_registry_pda);
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let reactivated = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(reactivated.data[DELETED_OFFSET], 0);
+    context.warp_to_next_slot();
+    assert!(is_visible(&mut context, &agent_registry_pda, &authority));
+}
+
+#[test]
+fn test_soft_deregister_and_reactivate_reject_no_change() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+    let authority = agent_authority.pubkey();
+
+    let agent_registry_pda = setup_agent_registry(&mut context, &agent_authority);
+
+    let instruction = reactivate_agent(&authority, &agent_registry_pda);
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    assert_custom_error(error, 118); // NoChanges
+
+    let instruction = soft_deregister_agent(&authority, &agent_registry_pda);
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    context.warp_to_next_slot();
+    let instruction = soft_deregister_agent(&authority, &agent_registry_pda);
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    assert_custom_error(error, 118); // NoChanges
+}
+
+#[test]
+fn test_soft_deregister_wrong_authority() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+    let attacker = context.create_funded_keypair();
+
+    let agent_registry_pda = setup_agent_registry(&mut context, &agent_authority);
+
+    let instruction = soft_deregister_agent(&attacker.pubkey(), &agent_registry_pda);
+    let error = context.send_transaction_expect_error(instruction, &[&attacker]);
+    assert_custom_error(error, 100); // InvalidAuthority
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(account.data[DELETED_OFFSET], 0);
+}
+
+#[test]
+fn test_deregister_closes_soft_deregistered_agent() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+    let authority = agent_authority.pubkey();
+
+    let agent_registry_pda = setup_agent_registry(&mut context, &agent_authority);
+
+    let instruction = soft_deregister_agent(&authority, &agent_registry_pda);
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let instruction = deregister_agent(&authority, &agent_registry_pda);
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+    assert_account_not_exists(&context, &agent_registry_pda);
+}
+
+/// Soft-deregisters a fresh agent at `left_at`, then re-registers it at
+/// `returned_at`, returning `(created_at before, created_at after)`
+fn reregister_after(
+    context: &mut TestContext,
+    left_at: i64,
+    returned_at: i64,
+) -> (Vec<u8>, Vec<u8>) {
+    let agent_authority = context.create_funded_keypair();
+    let authority = agent_authority.pubkey();
+
+    context.warp_to_timestamp(left_at - 100);
+    let agent_registry_pda = setup_agent_registry(context, &agent_authority);
+    let registered = context.get_account(&agent_registry_pda).unwrap();
+
+    context.warp_to_timestamp(left_at);
+    let instruction = soft_deregister_agent(&authority, &agent_registry_pda);
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    context.warp_to_timestamp(returned_at);
+    let (_, bump) = find_agent_registry_pda(&authority);
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &authority,
+        &agent_registry_pda,
+        bump,
+        "rotated-agent".to_string(),
+        "https://rotated.example.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    // The registry is live again and carries the new registration
+    let reregistered = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(reregistered.data[DELETED_OFFSET], 0);
+    assert_eq!(reregistered.lamports, registered.lamports);
+    assert!(is_visible(context, &agent_registry_pda, &authority));
+
+    (
+        registered.data[CREATED_AT_OFFSET..CREATED_AT_OFFSET + 8].to_vec(),
+        reregistered.data[CREATED_AT_OFFSET..CREATED_AT_OFFSET + 8].to_vec(),
+    )
+}
+
+#[test]
+fn test_reregister_keeps_created_at_only_within_grace_period() {
+    let mut context = TestContext::new();
+    let left_at = 1_707_523_200;
+
+    // A quick return (e.g. key rotation) keeps the original tenure
+    let (created, recreated) = reregister_after(&mut context, left_at, left_at + 3600);
+    assert_eq!(recreated, created);
+
+    // A return after the grace window starts a fresh one
+    let returned_at = left_at + AgentRegistry::REREGISTER_GRACE_PERIOD + 1;
+    let (created, recreated) = reregister_after(&mut context, left_at, returned_at);
+    assert_ne!(recreated, created);
+    assert_eq!(recreated, returned_at.to_le_bytes());
+}
@@ -1,9 +1,27 @@
-use solana_sdk::{signature::Keypair, signer::Signer, transaction::TransactionError};
+use agentmail_client::{
+    accounts::AgentRegistry, preflight::AGENT_REGISTRY_ACCOUNT_LEN, AGENTMAIL_ID,
+};
+use solana_sdk::{
+    account::Account, instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
 
 use crate::{
-    fixtures::{register_agent::register_agent, update_agent::update_agent},
+    fixtures::{
+        metadata::{read_metadata, with_metadata},
+        migrate_registry::{legacy_v2_registry_data, LEGACY_V2_ACCOUNT_LEN},
+        register_agent::register_agent,
+        set_accepting::{read_updated_at, UPDATED_AT_OFFSET},
+        update_agent::{
+            update_agent, update_agent_fields, update_agent_inbox_urls, update_agent_mime_types,
+            update_agent_with_nonce, update_agent_with_payer, UpdateAgentFixture,
+        },
+    },
     utils::{
-        pda_utils::find_agent_registry_pda, setup::TestContext, test_helpers::AgentRegistryAccount,
+        assertions::{assert_custom_error, assert_instruction_error, assert_owned_by_program},
+        pda_utils::find_agent_registry_pda,
+        setup::TestContext,
+        test_helpers::{test_declared_roles, AgentRegistryAccount},
         Address,
     },
 };
@@ -50,11 +68,8 @@ fn test_update_agent_success() {
     assert!(result.is_ok(), "UpdateAgent transaction should succeed");
 
     // Verify the account was updated
+    assert_owned_by_program(&context, &agent_registry_pda);
     let account = context.get_account(&agent_registry_pda);
-    assert!(
-        account.is_some(),
-        "Agent registry account should still exist"
-    );
 
     let registry = AgentRegistryAccount::try_from_account_data(&account.unwrap().data).unwrap();
     assert_eq!(registry.authority, agent_authority.pubkey());
@@ -224,3 +239,582 @@ fn test_update_agent_preserves_other_fields() {
     // But updated_at should have changed
     assert!(updated_registry.updated_at >= original_registry.updated_at);
 }
+
+#[test]
+fn test_update_agent_preserves_created_slot() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let registration_slot = context.get_current_slot();
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+
+    let original_account = context.get_account(&agent_registry_pda).unwrap();
+    let original_registry =
+        AgentRegistryAccount::try_from_account_data(&original_account.data).unwrap();
+    assert_eq!(original_registry.created_slot, registration_slot);
+
+    context.warp_to_next_slot();
+
+    let instruction = update_agent(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        "new_name".to_string(),
+        "https://new.com/inbox".to_string(),
+    );
+
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let updated_account = context.get_account(&agent_registry_pda).unwrap();
+    let updated_registry =
+        AgentRegistryAccount::try_from_account_data(&updated_account.data).unwrap();
+    assert_eq!(updated_registry.created_slot, registration_slot);
+    assert!(context.get_current_slot() > registration_slot);
+}
+
+#[test]
+fn test_update_agent_registry_as_authority() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+
+    // Pass the registry PDA as both authority and registry
+    let mut instruction = update_agent(
+        &agent_registry_pda,
+        &agent_registry_pda,
+        "aliased_name".to_string(),
+        "https://aliased.com/inbox".to_string(),
+    );
+    // A PDA cannot sign, so the aliasing check must reject it first
+    instruction.accounts[0].is_signer = false;
+
+    let error = context.send_transaction_expect_error(instruction, &[]);
+    assert_instruction_error(error, InstructionError::InvalidArgument);
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    let registry = AgentRegistryAccount::try_from_account_data(&account.data).unwrap();
+    assert_eq!(registry.name, "original_name");
+}
+
+#[test]
+fn test_update_agent_metadata() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+
+    let uri = "https://updated.com/agent.json";
+    let hash = [0x42u8; 32];
+    let instruction = with_metadata(
+        update_agent(
+            &agent_authority.pubkey(),
+            &agent_registry_pda,
+            "updated_name".to_string(),
+            "https://updated.com/inbox".to_string(),
+        ),
+        uri,
+        hash,
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(read_metadata(&account.data), (uri.to_string(), hash));
+
+    // Omitting metadata leaves the stored commitment untouched
+    context.warp_to_next_slot();
+    let instruction = update_agent(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        "renamed".to_string(),
+        "https://updated.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(read_metadata(&account.data), (uri.to_string(), hash));
+
+    // An empty URI and zero hash clear the commitment
+    context.warp_to_next_slot();
+    let instruction = with_metadata(
+        update_agent(
+            &agent_authority.pubkey(),
+            &agent_registry_pda,
+            "renamed".to_string(),
+            "https://updated.com/inbox".to_string(),
+        ),
+        "",
+        [0u8; 32],
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(read_metadata(&account.data), (String::new(), [0u8; 32]));
+}
+
+#[test]
+fn test_update_agent_metadata_uri_too_long() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+
+    let instruction = with_metadata(
+        update_agent(
+            &agent_authority.pubkey(),
+            &agent_registry_pda,
+            "original_name".to_string(),
+            "https://original.com/inbox".to_string(),
+        ),
+        &"a".repeat(129),
+        [1u8; 32],
+    );
+
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    assert_custom_error(error, 117);
+}
+
+#[test]
+fn test_update_agent_unchanged_is_noop() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+
+    // Zero updated_at so a write would be observable
+    let mut data = context.get_account(&agent_registry_pda).unwrap().data;
+    data[UPDATED_AT_OFFSET..UPDATED_AT_OFFSET + 8].fill(0);
+    context.set_account_data(&agent_registry_pda, data.clone());
+
+    let instruction = update_agent(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        "original_name".to_string(),
+        "https://original.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .expect("Unchanged update should succeed as a no-op");
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    assert_eq!(
+        read_updated_at(&account.data),
+        0,
+        "updated_at should not move"
+    );
+    assert_eq!(account.data, data, "registry should not be rewritten");
+}
+
+fn read_name_and_inbox_url(
+    context: &TestContext,
+    agent_registry_pda: &Address,
+) -> (String, String) {
+    let account = context.get_account(agent_registry_pda).unwrap();
+    let registry = AgentRegistry::from_bytes(&account.data).unwrap();
+    (
+        registry.name_str().unwrap(),
+        registry.inbox_url_str().unwrap(),
+    )
+}
+
+#[test]
+fn test_update_agent_name_only() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+
+    let instruction = update_agent_fields(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        Some("renamed"),
+        None,
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let (name, inbox_url) = read_name_and_inbox_url(&context, &agent_registry_pda);
+    assert_eq!(name, "renamed");
+    assert_eq!(inbox_url, "https://original.com/inbox");
+}
+
+#[test]
+fn test_update_agent_inbox_url_only() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+
+    let instruction = update_agent_fields(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        None,
+        Some("https://moved.com/inbox"),
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let (name, inbox_url) = read_name_and_inbox_url(&context, &agent_registry_pda);
+    assert_eq!(name, "original_name");
+    assert_eq!(inbox_url, "https://moved.com/inbox");
+}
+
+#[test]
+fn test_update_agent_both_fields_masked() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+
+    let instruction = update_agent_fields(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        Some("renamed"),
+        Some("https://moved.com/inbox"),
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let (name, inbox_url) = read_name_and_inbox_url(&context, &agent_registry_pda);
+    assert_eq!(name, "renamed");
+    assert_eq!(inbox_url, "https://moved.com/inbox");
+}
+
+fn read_registry(context: &TestContext, agent_registry: &Address) -> AgentRegistry {
+    let account = context.get_account(agent_registry).unwrap();
+    AgentRegistry::from_bytes(&account.data).unwrap()
+}
+
+#[test]
+fn test_update_agent_mime_types() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+    assert!(read_registry(&context, &agent_registry_pda)
+        .mime_types()
+        .unwrap()
+        .is_empty());
+
+    let mime_types = [
+        "application/json",
+        "text/markdown",
+        "text/plain",
+        "image/png",
+    ];
+    let instruction =
+        update_agent_mime_types(&agent_authority.pubkey(), &agent_registry_pda, &mime_types);
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let registry = read_registry(&context, &agent_registry_pda);
+    assert_eq!(registry.mime_types().unwrap(), mime_types);
+    assert!(registry.accepts_mime_type("Text/Markdown"));
+    assert!(!registry.accepts_mime_type("text/html"));
+    // Name and inbox URL are left unchanged
+    assert_eq!(registry.name_str().unwrap(), "original_name");
+    assert_eq!(
+        registry.inbox_url_str().unwrap(),
+        "https://original.com/inbox"
+    );
+
+    // Replacing with a shorter list clears the trailing slots
+    context.warp_to_next_slot();
+    let instruction = update_agent_mime_types(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        &["text/plain"],
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let registry = read_registry(&context, &agent_registry_pda);
+    assert_eq!(registry.mime_types().unwrap(), ["text/plain"]);
+    assert_eq!(&registry.accepted_mime_types[1..], &[[0u8; 32]; 3]);
+}
+
+#[test]
+fn test_update_agent_mime_type_invalid() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+
+    let instruction = update_agent_mime_types(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        &["application/json", "json"],
+    );
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    // The version 3 parse fails, so the data is reported as version 1
+    assert!(matches!(error, TransactionError::InstructionError(_, _)));
+    assert!(read_registry(&context, &agent_registry_pda)
+        .mime_types()
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_update_agent_inbox_urls() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+    assert!(read_registry(&context, &agent_registry_pda)
+        .inbox_urls()
+        .unwrap()
+        .is_empty());
+
+    let inbox_urls = [
+        "https://backup-a.example.com/inbox",
+        "https://backup-b.example.com/inbox",
+    ];
+    let instruction =
+        update_agent_inbox_urls(&agent_authority.pubkey(), &agent_registry_pda, &inbox_urls);
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let registry = read_registry(&context, &agent_registry_pda);
+    assert_eq!(registry.inbox_urls().unwrap(), inbox_urls);
+    assert_eq!(registry.url_count, 2);
+    // The primary inbox URL is left unchanged
+    assert_eq!(
+        registry.inbox_url_str().unwrap(),
+        "https://original.com/inbox"
+    );
+
+    // Replacing with a shorter list clears the trailing slots
+    context.warp_to_next_slot();
+    let instruction = update_agent_inbox_urls(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        &["https://backup-b.example.com/inbox"],
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority])
+        .unwrap();
+
+    let registry = read_registry(&context, &agent_registry_pda);
+    assert_eq!(
+        registry.inbox_urls().unwrap(),
+        ["https://backup-b.example.com/inbox"]
+    );
+    assert_eq!(&registry.inbox_urls[1..], &[[0u8; 132]; 3]);
+}
+
+#[test]
+fn test_update_agent_inbox_urls_past_limit() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+
+    let inbox_urls = ["https://a.example.com/inbox"; 5];
+    let instruction =
+        update_agent_inbox_urls(&agent_authority.pubkey(), &agent_registry_pda, &inbox_urls);
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    // The version 3 parse fails, so the data is reported as version 1
+    assert!(matches!(error, TransactionError::InstructionError(_, _)));
+    assert!(read_registry(&context, &agent_registry_pda)
+        .inbox_urls()
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_update_agent_zero_lamport_registry() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+
+    // Drain the registry as a partial close would
+    context.set_account_lamports(&agent_registry_pda, 0);
+
+    let instruction = update_agent(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        "new_name".to_string(),
+        "https://new.com/inbox".to_string(),
+    );
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    assert_custom_error(error, 107);
+}
+
+/// Plant a version 2 registry for `agent_authority`, returning its PDA and rent
+fn setup_legacy_registry(context: &mut TestContext, agent_authority: &Keypair) -> (Address, u64) {
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+    let legacy_rent = context
+        .svm
+        .minimum_balance_for_rent_exemption(LEGACY_V2_ACCOUNT_LEN);
+    context
+        .svm
+        .set_account(
+            agent_registry_pda,
+            Account {
+                lamports: legacy_rent,
+                data: legacy_v2_registry_data(
+                    bump,
+                    &agent_authority.pubkey(),
+                    "veteran",
+                    "https://old.example.com/inbox",
+                ),
+                owner: AGENTMAIL_ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+    (agent_registry_pda, legacy_rent)
+}
+
+#[test]
+fn test_update_agent_legacy_registry_grown_by_separate_payer() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+    let rent_payer = context.create_funded_keypair();
+    let (agent_registry_pda, legacy_rent) = setup_legacy_registry(&mut context, &agent_authority);
+
+    let authority_before = context.svm.get_balance(&agent_authority.pubkey()).unwrap();
+    let payer_before = context.svm.get_balance(&rent_payer.pubkey()).unwrap();
+
+    let instruction = update_agent_with_payer(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        Some(&rent_payer.pubkey()),
+        "upgraded".to_string(),
+        "https://new.example.com/inbox".to_string(),
+    );
+    context
+        .send_transaction(instruction, &[&agent_authority, &rent_payer])
+        .unwrap();
+
+    let account = context.get_account(&agent_registry_pda).unwrap();
+    let current_rent = context
+        .svm
+        .minimum_balance_for_rent_exemption(AGENT_REGISTRY_ACCOUNT_LEN);
+    assert_eq!(account.data.len(), AGENT_REGISTRY_ACCOUNT_LEN);
+    assert_eq!(account.lamports, current_rent);
+
+    // The separate payer covers exactly the top-up; the authority pays nothing
+    assert_eq!(
+        payer_before - context.svm.get_balance(&rent_payer.pubkey()).unwrap(),
+        current_rent - legacy_rent
+    );
+    assert_eq!(
+        context.svm.get_balance(&agent_authority.pubkey()).unwrap(),
+        authority_before
+    );
+
+    let registry = AgentRegistry::from_bytes(&account.data).unwrap();
+    assert_eq!(registry.version, 8);
+    assert_eq!(registry.name_str().unwrap(), "upgraded");
+    assert_eq!(
+        registry.inbox_url_str().unwrap(),
+        "https://new.example.com/inbox"
+    );
+}
+
+#[test]
+fn test_update_agent_legacy_registry_without_system_program() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+    let (agent_registry_pda, _) = setup_legacy_registry(&mut context, &agent_authority);
+
+    // The plain builder passes no system program, so the registry cannot grow
+    let instruction = update_agent(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        "upgraded".to_string(),
+        "https://new.example.com/inbox".to_string(),
+    );
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    assert_instruction_error(error, InstructionError::NotEnoughAccountKeys);
+}
+
+fn read_last_nonce(context: &TestContext, agent_registry_pda: &Address) -> u64 {
+    let account = context.get_account(agent_registry_pda).unwrap();
+    AgentRegistry::from_bytes(&account.data).unwrap().last_nonce
+}
+
+#[test]
+fn test_update_agent_increasing_nonce_accepted() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+    assert_eq!(read_last_nonce(&context, &agent_registry_pda), 0);
+
+    for (nonce, name) in [(1, "first"), (5, "second")] {
+        let instruction = update_agent_with_nonce(
+            &agent_authority.pubkey(),
+            &agent_registry_pda,
+            name,
+            "https://original.com/inbox",
+            nonce,
+        );
+        context
+            .send_transaction(instruction, &[&agent_authority])
+            .expect("Increasing nonce should be accepted");
+        assert_eq!(read_last_nonce(&context, &agent_registry_pda), nonce);
+    }
+
+    let (name, _) = read_name_and_inbox_url(&context, &agent_registry_pda);
+    assert_eq!(name, "second");
+}
+
+#[test]
+fn test_update_agent_replayed_nonce_rejected() {
+    let mut context = TestContext::new();
+    let agent_authority = context.create_funded_keypair();
+    let (agent_registry_pda, _bump) = setup_agent_registry(&mut context, &agent_authority);
+
+    let instruction = update_agent_with_nonce(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        "updated_name",
+        "https://updated.com/inbox",
+        7,
+    );
+    context
+        .send_transaction(instruction.clone(), &[&agent_authority])
+        .unwrap();
+
+    // Same update replayed in a later slot, so it is a distinct transaction
+    context.warp_to_next_slot();
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    assert_custom_error(error, 122);
+
+    // A lower nonce with different contents is stale as well
+    let instruction = update_agent_with_nonce(
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        "older_name",
+        "https://updated.com/inbox",
+        3,
+    );
+    let error = context.send_transaction_expect_error(instruction, &[&agent_authority]);
+    assert_custom_error(error, 122);
+
+    assert_eq!(read_last_nonce(&context, &agent_registry_pda), 7);
+    let (name, _) = read_name_and_inbox_url(&context, &agent_registry_pda);
+    assert_eq!(name, "updated_name");
+}
+
+#[test]
+fn test_update_agent_account_roles() {
+    // Every role declared for the authority, registry and program account
+    test_declared_roles::<UpdateAgentFixture>();
+}
@@ -0,0 +1,118 @@
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::TransactionError};
+
+use crate::{
+    fixtures::{
+        register_agent::register_agent,
+        update_agent_batch::{update_agent_batch, UpdateAgentBatchEntry},
+    },
+    utils::{
+        pda_utils::find_agent_registry_pda, setup::TestContext, test_helpers::AgentRegistryAccount,
+        Address,
+    },
+};
+
+fn setup_agent_registry(context: &mut TestContext, agent_authority: &Keypair) -> Address {
+    let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+
+    let instruction = register_agent(
+        &context.payer.pubkey(),
+        &agent_authority.pubkey(),
+        &agent_registry_pda,
+        bump,
+        "original_name".to_string(),
+        "https://original.com/inbox".to_string(),
+    );
+
+    context
+        .send_transaction(instruction, &[agent_authority])
+        .unwrap();
+    agent_registry_pda
+}
+
+fn read_registry(context: &TestContext, agent_registry: &Address) -> AgentRegistryAccount {
+    let account = context
+        .get_account(agent_registry)
+        .expect("Agent registry account should exist");
+    AgentRegistryAccount::try_from_account_data(&account.data).unwrap()
+}
+
+#[test]
+fn test_update_agent_batch_three_agents() {
+    let mut context = TestContext::new();
+    let authorities: Vec<Keypair> = (0..3).map(|_| context.create_funded_keypair()).collect();
+    let registries: Vec<Address> = authorities
+        .iter()
+        .map(|authority| setup_agent_registry(&mut context, authority))
+        .collect();
+
+    context.warp_to_next_slot();
+
+    let entries: Vec<UpdateAgentBatchEntry> = authorities
+        .iter()
+        .zip(&registries)
+        .enumerate()
+        .map(|(i, (authority, registry))| UpdateAgentBatchEntry {
+            agent_authority: authority.pubkey(),
+            agent_registry: *registry,
+            name: format!("fleet-agent-{}", i),
+            inbox_url: format!("https://dc2.example.com/inbox/{}", i),
+        })
+        .collect();
+
+    let signers: Vec<&Keypair> = authorities.iter().collect();
+    let result = context.send_transaction(update_agent_batch(&entries), &signers);
+    assert!(
+        result.is_ok(),
+        "UpdateAgentBatch transaction should succeed"
+    );
+
+    for (i, (authority, registry)) in authorities.iter().zip(&registries).enumerate() {
+        let registry = read_registry(&context, registry);
+        assert_eq!(registry.authority, authority.pubkey());
+        assert_eq!(registry.name, format!("fleet-agent-{}", i));
+        assert_eq!(
+            registry.inbox_url,
+            format!("https://dc2.example.com/inbox/{}", i)
+        );
+        assert!(registry.updated_at > registry.created_at);
+    }
+}
+
+#[test]
+fn test_update_agent_batch_wrong_authority_is_atomic() {
+    let mut context = TestContext::new();
+    let first_authority = context.create_funded_keypair();
+    let second_authority = context.create_funded_keypair();
+    let wrong_authority = context.create_funded_keypair();
+
+    let first_registry = setup_agent_registry(&mut context, &first_authority);
+    let second_registry = setup_agent_registry(&mut context, &second_authority);
+
+    let entries = [
+        UpdateAgentBatchEntry {
+            agent_authority: first_authority.pubkey(),
+            agent_registry: first_registry,
+            name: "moved_agent".to_string(),
+            inbox_url: "https://moved.com/inbox".to_string(),
+        },
+        UpdateAgentBatchEntry {
+            agent_authority: wrong_authority.pubkey(), // Wrong authority
+            agent_registry: second_registry,
+            name: "hacked_name".to_string(),
+            inbox_url: "https://hacker.com/inbox".to_string(),
+        },
+    ];
+
+    let error = context.send_transaction_expect_error(
+        update_agent_batch(&entries),
+        &[&first_authority, &wrong_authority],
+    );
+    assert!(matches!(error, TransactionError::InstructionError(_, _)));
+
+    // The valid first entry must not have been applied either
+    for registry in [first_registry, second_registry] {
+        let registry = read_registry(&context, &registry);
+        assert_eq!(registry.name, "original_name");
+        assert_eq!(registry.inbox_url, "https://original.com/inbox");
+    }
+}
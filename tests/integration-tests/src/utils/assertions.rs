@@ -22,6 +22,18 @@ pub fn assert_account_not_exists(context: &TestContext, address: &Address) {
     );
 }
 
+/// Assert that the account at `address` exists and is owned by the AgentMail program
+pub fn assert_owned_by_program(context: &TestContext, address: &Address) {
+    let account = context
+        .get_account(address)
+        .unwrap_or_else(|| panic!("Account {address} should exist"));
+    assert!(
+        account.owner == AGENTMAIL_ID,
+        "Account {address} should be owned by {AGENTMAIL_ID}, but is owned by {}",
+        account.owner
+    );
+}
+
 /// Assert that a transaction error contains the expected instruction error
 pub fn assert_instruction_error(tx_error: TransactionError, expected: InstructionError) {
     match tx_error {
@@ -45,11 +57,8 @@ pub fn assert_agent_registry_account(
     expected_name: &str,
     expected_inbox_url: &str,
 ) {
-    let account = context
-        .get_account(registry_pda)
-        .expect("Agent registry account should exist");
-
-    assert_eq!(account.owner, AGENTMAIL_ID);
+    assert_owned_by_program(context, registry_pda);
+    let account = context.get_account(registry_pda).unwrap();
 
     let registry = AgentRegistry::from_bytes(&account.data)
         .expect("Should deserialize agent registry account");
@@ -79,3 +88,42 @@ pub fn assert_agent_registry_account(
         .expect("Inbox URL should be valid UTF-8");
     assert_eq!(url_str, expected_inbox_url);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::register_agent::register_agent, utils::pda_utils::find_agent_registry_pda,
+    };
+    use solana_sdk::signer::Signer;
+
+    #[test]
+    fn test_assert_owned_by_program_registry() {
+        let mut context = TestContext::new();
+        let agent_authority = context.create_funded_keypair();
+        let (agent_registry_pda, bump) = find_agent_registry_pda(&agent_authority.pubkey());
+
+        let instruction = register_agent(
+            &context.payer.pubkey(),
+            &agent_authority.pubkey(),
+            &agent_registry_pda,
+            bump,
+            "owned".to_string(),
+            "https://owned.example.com/inbox".to_string(),
+        );
+        context
+            .send_transaction(instruction, &[&agent_authority])
+            .unwrap();
+
+        assert_owned_by_program(&context, &agent_registry_pda);
+    }
+
+    #[test]
+    #[should_panic(expected = "but is owned by 11111111111111111111111111111111")]
+    fn test_assert_owned_by_program_system_account() {
+        let mut context = TestContext::new();
+        let wallet = context.create_funded_keypair();
+
+        assert_owned_by_program(&context, &wallet.pubkey());
+    }
+}
@@ -3,7 +3,28 @@ use agentmail_client::AGENTMAIL_ID;
 use crate::utils::Address;
 
 const AGENTMAIL_SEED: &[u8] = b"agentmail";
+const RENT_VAULT_SEED: &[u8] = b"rent_vault";
 
 pub fn find_agent_registry_pda(authority: &Address) -> (Address, u8) {
     Address::find_program_address(&[AGENTMAIL_SEED, authority.as_ref()], &AGENTMAIL_ID)
 }
+
+pub fn find_rent_vault_pda(vault_authority: &Address) -> (Address, u8) {
+    Address::find_program_address(&[RENT_VAULT_SEED, vault_authority.as_ref()], &AGENTMAIL_ID)
+}
+
+/// Find a valid registry address for `authority` derived from a non-canonical bump
+pub fn find_non_canonical_agent_registry_pda(authority: &Address) -> (Address, u8) {
+    let (_, canonical_bump) = find_agent_registry_pda(authority);
+    (0..canonical_bump)
+        .rev()
+        .find_map(|bump| {
+            Address::create_program_address(
+                &[AGENTMAIL_SEED, authority.as_ref(), &[bump]],
+                &AGENTMAIL_ID,
+            )
+            .ok()
+            .map(|address| (address, bump))
+        })
+        .expect("no non-canonical bump derives a valid address")
+}
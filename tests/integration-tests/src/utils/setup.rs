@@ -1,4 +1,4 @@
-use litesvm::LiteSVM;
+use litesvm::{types::TransactionMetadata, LiteSVM};
 use solana_program::clock::Clock;
 use solana_sdk::{
     account::Account,
@@ -37,6 +37,8 @@ impl TestContext {
         });
 
         // TODO: Add program binary when cargo-build-sbf works
+        // The binary must be deployed at AGENTMAIL_ID, the address of
+        // keys/agentmail-program.json; test_program_id checks they agree.
         // let program_data = include_bytes!("../../../../target/deploy/agentmail.so");
         // let _ = svm.add_program(AGENTMAIL_ID, program_data);
 
@@ -96,11 +98,30 @@ impl TestContext {
             .expect_err("Transaction should fail")
     }
 
+    /// Sends a transaction and returns the program's return data.
+    pub fn send_transaction_return_data(
+        &mut self,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<Vec<u8>, TransactionError> {
+        self.send_transaction_meta(instruction, signers)
+            .map(|meta| meta.return_data.data)
+    }
+
     fn send_transaction_inner(
         &mut self,
         instruction: Instruction,
         signers: &[&Keypair],
     ) -> Result<u64, TransactionError> {
+        self.send_transaction_meta(instruction, signers)
+            .map(|meta| meta.compute_units_consumed)
+    }
+
+    fn send_transaction_meta(
+        &mut self,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<TransactionMetadata, TransactionError> {
         let mut all_signers = vec![&self.payer as &dyn Signer];
         all_signers.extend(signers.iter().map(|k| *k as &dyn Signer));
 
@@ -111,22 +132,49 @@ impl TestContext {
             self.svm.latest_blockhash(),
         );
 
-        self.svm
-            .send_transaction(transaction)
-            .map(|meta| meta.compute_units_consumed)
-            .map_err(|e| e.err)
+        self.svm.send_transaction(transaction).map_err(|e| e.err)
     }
 
     pub fn get_account(&self, address: &Address) -> Option<Account> {
         self.svm.get_account(address)
     }
 
+    /// Overwrites an account's raw data in place, keeping its owner and lamports.
+    ///
+    /// Used to reach corruption-recovery paths no instruction can produce.
+    pub fn set_account_data(&mut self, address: &Address, data: Vec<u8>) {
+        let mut account = self
+            .svm
+            .get_account(address)
+            .expect("account must exist to overwrite its data");
+        account.data = data;
+        self.svm.set_account(*address, account).unwrap();
+    }
+
+    /// Overwrites an account's lamports in place, keeping its owner and data.
+    pub fn set_account_lamports(&mut self, address: &Address, lamports: u64) {
+        let mut account = self
+            .svm
+            .get_account(address)
+            .expect("account must exist to overwrite its lamports");
+        account.lamports = lamports;
+        self.svm.set_account(*address, account).unwrap();
+    }
+
     pub fn create_funded_keypair(&mut self) -> Keypair {
         let kp = Keypair::new();
         self.svm.airdrop(&kp.pubkey(), MIN_LAMPORTS).unwrap();
         kp
     }
 
+    /// Like [`Self::create_funded_keypair`], but the keypair is derived from
+    /// `seed` so a failing test can be rerun with the exact same keys.
+    pub fn create_funded_keypair_seeded(&mut self, seed: u64) -> Keypair {
+        let kp = seeded_keypair(seed);
+        self.svm.airdrop(&kp.pubkey(), MIN_LAMPORTS).unwrap();
+        kp
+    }
+
     pub fn warp_to_timestamp(&mut self, unix_timestamp: i64) {
         self.svm.set_sysvar(&Clock {
             slot: 1,
@@ -141,19 +189,52 @@ impl TestContext {
         self.svm.get_sysvar::<Clock>().unix_timestamp
     }
 
+    pub fn get_current_slot(&self) -> u64 {
+        self.svm.get_sysvar::<Clock>().slot
+    }
+
+    /// Advances the slot and the clock by one second, so updates made after
+    /// the warp get a later timestamp than those before it.
     pub fn warp_to_next_slot(&mut self) {
         let clock = self.svm.get_sysvar::<Clock>();
         let current_slot = clock.slot;
         self.svm.set_sysvar(&Clock {
             slot: current_slot + 1,
+            unix_timestamp: clock.unix_timestamp + 1,
             ..clock
         });
         self.svm.expire_blockhash();
     }
 }
 
+/// Derives a keypair whose secret key is `seed` (LE) followed by a fixed tag.
+fn seeded_keypair(seed: u64) -> Keypair {
+    const TAG: &[u8; 24] = b"agentmail-test-keypair\0\0";
+    let mut secret = [0u8; 32];
+    secret[..8].copy_from_slice(&seed.to_le_bytes());
+    secret[8..].copy_from_slice(TAG);
+    Keypair::new_from_array(secret)
+}
+
 impl Default for TestContext {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_funded_keypair_seeded_is_deterministic() {
+        let mut context = TestContext::new();
+        let first = context.create_funded_keypair_seeded(42);
+        let second = TestContext::new().create_funded_keypair_seeded(42);
+        let other = context.create_funded_keypair_seeded(43);
+
+        assert_eq!(first.pubkey(), second.pubkey());
+        assert_ne!(first.pubkey(), other.pubkey());
+        assert_eq!(context.svm.get_balance(&first.pubkey()), Some(MIN_LAMPORTS));
+    }
+}
@@ -54,6 +54,26 @@ pub fn test_wrong_current_program<T: InstructionTestFixture>(ctx: &mut TestConte
     assert_instruction_error(error, InstructionError::IncorrectProgramId);
 }
 
+/// Test every account role `T` declares, each in a fresh context
+///
+/// Covers each required signer and writable account, plus the system and
+/// current program accounts when present. `build_valid` must list its
+/// signers in the same order as `required_signers`.
+pub fn test_declared_roles<T: InstructionTestFixture>() {
+    for (signer_vec_index, &account_index) in T::required_signers().iter().enumerate() {
+        test_missing_signer::<T>(&mut TestContext::new(), account_index, signer_vec_index);
+    }
+    for &account_index in T::required_writable() {
+        test_not_writable::<T>(&mut TestContext::new(), account_index);
+    }
+    if T::system_program_index().is_some() {
+        test_wrong_system_program::<T>(&mut TestContext::new());
+    }
+    if T::current_program_index().is_some() {
+        test_wrong_current_program::<T>(&mut TestContext::new());
+    }
+}
+
 /// Test that providing a wrong account at a given index fails
 ///
 /// # Arguments
@@ -105,6 +125,34 @@ pub fn test_invalid_bump<T: InstructionTestFixture>(
     assert_instruction_error(error, InstructionError::InvalidSeeds);
 }
 
+/// Why [`AgentRegistryAccount::try_from_account_data`] rejected account data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The data ends before the last field read
+    TooShort { got: usize, need: usize },
+    /// The name length prefix exceeds the 64-byte name field
+    NameLenInvalid { len: usize },
+    /// The inbox URL length prefix exceeds the 256-byte URL field
+    InboxUrlLenInvalid { len: usize },
+    /// A string field is not valid UTF-8
+    Utf8 { field: &'static str },
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooShort { got, need } => {
+                write!(f, "account data is {got} bytes, need at least {need}")
+            }
+            Self::NameLenInvalid { len } => write!(f, "name length {len} exceeds 64"),
+            Self::InboxUrlLenInvalid { len } => write!(f, "inbox_url length {len} exceeds 256"),
+            Self::Utf8 { field } => write!(f, "{field} is not valid UTF-8"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
 /// Helper struct for parsing AgentRegistry account data in tests
 #[derive(Debug, Clone, PartialEq)]
 pub struct AgentRegistryAccount {
@@ -115,46 +163,54 @@ pub struct AgentRegistryAccount {
     pub inbox_url: String,
     pub created_at: i64,
     pub updated_at: i64,
+    pub created_slot: u64,
 }
 
 impl AgentRegistryAccount {
-    pub fn try_from_account_data(data: &[u8]) -> Result<Self, &'static str> {
-        if data.len() < 318 {
-            // 1 + 1 + 6 + 32 + 68 + 260 + 8 + 8 = 384 total, but discriminator is separate
-            return Err("Invalid account data length");
+    /// Shortest data accepted by [`Self::try_from_account_data`]: the
+    /// discriminator and header version, then the struct through `created_slot`
+    pub const MIN_DATA_LEN: usize = 2 + 1 + 1 + 6 + 32 + 68 + 260 + 8 + 8 + 8;
+
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self, DecodeError> {
+        if data.len() < Self::MIN_DATA_LEN {
+            return Err(DecodeError::TooShort {
+                got: data.len(),
+                need: Self::MIN_DATA_LEN,
+            });
         }
 
-        let bump = data[0];
-        let version = data[1];
-        // Skip padding bytes 2-7
+        // Skip the discriminator and header version at offsets 0-1
+        let bump = data[2];
+        let version = data[3];
+        // Skip url_count, accepting, deleted and padding at offsets 4-9
 
-        // Authority is at offset 8, 32 bytes
-        let authority = Address::from(<[u8; 32]>::try_from(&data[8..40]).unwrap());
+        // Authority is at offset 10, 32 bytes
+        let authority = Address::from(<[u8; 32]>::try_from(&data[10..42]).unwrap());
 
-        // Name is at offset 40, fixed 68 bytes (4 bytes len + up to 64 bytes data)
-        let name_len = u32::from_le_bytes([data[40], data[41], data[42], data[43]]) as usize;
+        // Name is at offset 42, fixed 68 bytes (4 bytes len + up to 64 bytes data)
+        let name_len = u32::from_le_bytes([data[42], data[43], data[44], data[45]]) as usize;
         if name_len > 64 {
-            return Err("Invalid name length");
+            return Err(DecodeError::NameLenInvalid { len: name_len });
         }
-        let name_bytes = &data[44..44 + name_len];
-        let name = String::from_utf8(name_bytes.to_vec()).map_err(|_| "Invalid name UTF-8")?;
+        let name_bytes = &data[46..46 + name_len];
+        let name = String::from_utf8(name_bytes.to_vec())
+            .map_err(|_| DecodeError::Utf8 { field: "name" })?;
 
-        // Inbox URL is at offset 108 (40 + 68), fixed 260 bytes (4 bytes len + up to 256 bytes data)
-        let url_len = u32::from_le_bytes([data[108], data[109], data[110], data[111]]) as usize;
+        // Inbox URL is at offset 110 (42 + 68), fixed 260 bytes (4 bytes len + up to 256 bytes data)
+        let url_len = u32::from_le_bytes([data[110], data[111], data[112], data[113]]) as usize;
         if url_len > 256 {
-            return Err("Invalid inbox_url length");
+            return Err(DecodeError::InboxUrlLenInvalid { len: url_len });
         }
-        let url_bytes = &data[112..112 + url_len];
-        let inbox_url =
-            String::from_utf8(url_bytes.to_vec()).map_err(|_| "Invalid inbox_url UTF-8")?;
-
-        // Timestamps are at offset 368 and 376 (8 bytes each)
-        let created_at = i64::from_le_bytes([
-            data[368], data[369], data[370], data[371], data[372], data[373], data[374], data[375],
-        ]);
-        let updated_at = i64::from_le_bytes([
-            data[376], data[377], data[378], data[379], data[380], data[381], data[382], data[383],
-        ]);
+        let url_bytes = &data[114..114 + url_len];
+        let inbox_url = String::from_utf8(url_bytes.to_vec())
+            .map_err(|_| DecodeError::Utf8 { field: "inbox_url" })?;
+
+        // Timestamps are at offset 370 and 378 (8 bytes each)
+        let created_at = i64::from_le_bytes(data[370..378].try_into().unwrap());
+        let updated_at = i64::from_le_bytes(data[378..386].try_into().unwrap());
+
+        // Creation slot is at offset 386 (8 bytes)
+        let created_slot = u64::from_le_bytes(data[386..394].try_into().unwrap());
 
         Ok(Self {
             bump,
@@ -164,6 +220,123 @@ impl AgentRegistryAccount {
             inbox_url,
             created_at,
             updated_at,
+            created_slot,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Data the decoder accepts: empty name and URL, everything else zero
+    fn valid_data() -> Vec<u8> {
+        vec![0u8; 400]
+    }
+
+    fn set_len(data: &mut [u8], offset: usize, len: u32) {
+        data[offset..offset + 4].copy_from_slice(&len.to_le_bytes());
+    }
+
+    #[test]
+    fn test_decode_valid_data() {
+        let registry = AgentRegistryAccount::try_from_account_data(&valid_data()).unwrap();
+        assert_eq!(registry.name, "");
+        assert_eq!(registry.inbox_url, "");
+    }
+
+    #[test]
+    fn test_decode_program_bytes() {
+        use agentmail::{state::AgentRegistry, traits::AccountSerialize};
+
+        let authority = Address::new_from_array([9u8; 32]);
+        let registry = AgentRegistry::new(
+            254,
+            authority,
+            "nix",
+            "https://nix.example.com/inbox",
+            11,
+            12,
+        )
+        .unwrap();
+        let decoded = AgentRegistryAccount::try_from_account_data(&registry.to_bytes()).unwrap();
+        assert_eq!(decoded.bump, 254);
+        assert_eq!(decoded.version, registry.version);
+        assert_eq!(decoded.authority, authority);
+        assert_eq!(decoded.name, "nix");
+        assert_eq!(decoded.inbox_url, "https://nix.example.com/inbox");
+        assert_eq!(decoded.created_at, 11);
+        assert_eq!(decoded.updated_at, 11);
+        assert_eq!(decoded.created_slot, 12);
+    }
+
+    #[test]
+    fn test_decode_too_short() {
+        let error = AgentRegistryAccount::try_from_account_data(&[0u8; 100]).unwrap_err();
+        assert_eq!(
+            error,
+            DecodeError::TooShort {
+                got: 100,
+                need: AgentRegistryAccount::MIN_DATA_LEN,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_short_of_created_slot() {
+        // Ends inside the inbox_url field, well short of created_slot
+        let error = AgentRegistryAccount::try_from_account_data(&[0u8; 320]).unwrap_err();
+        assert_eq!(
+            error,
+            DecodeError::TooShort {
+                got: 320,
+                need: 394
+            }
+        );
+
+        let data = valid_data();
+        let error = AgentRegistryAccount::try_from_account_data(&data[..393]).unwrap_err();
+        assert_eq!(
+            error,
+            DecodeError::TooShort {
+                got: 393,
+                need: 394
+            }
+        );
+        assert!(AgentRegistryAccount::try_from_account_data(&data[..394]).is_ok());
+    }
+
+    #[test]
+    fn test_decode_name_len_invalid() {
+        let mut data = valid_data();
+        set_len(&mut data, 42, 65);
+        let error = AgentRegistryAccount::try_from_account_data(&data).unwrap_err();
+        assert_eq!(error, DecodeError::NameLenInvalid { len: 65 });
+    }
+
+    #[test]
+    fn test_decode_inbox_url_len_invalid() {
+        let mut data = valid_data();
+        set_len(&mut data, 110, 257);
+        let error = AgentRegistryAccount::try_from_account_data(&data).unwrap_err();
+        assert_eq!(error, DecodeError::InboxUrlLenInvalid { len: 257 });
+    }
+
+    #[test]
+    fn test_decode_name_utf8() {
+        let mut data = valid_data();
+        set_len(&mut data, 42, 1);
+        data[46] = 0xFF;
+        let error = AgentRegistryAccount::try_from_account_data(&data).unwrap_err();
+        assert_eq!(error, DecodeError::Utf8 { field: "name" });
+    }
+
+    #[test]
+    fn test_decode_inbox_url_utf8() {
+        let mut data = valid_data();
+        set_len(&mut data, 110, 1);
+        data[114] = 0xFF;
+        let error = AgentRegistryAccount::try_from_account_data(&data).unwrap_err();
+        assert_eq!(error, DecodeError::Utf8 { field: "inbox_url" });
+    }
+}